@@ -87,6 +87,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn decoding_concatenated_values() -> Result<()> {
+        // ANCHOR: decoding_concatenated_values
+        use fuels::{
+            core::codec::ABIDecoder,
+            types::{param_types::ParamType, Token},
+        };
+
+        let bytes: &[u8] = &[0, 0, 0, 0, 0, 0, 0, 101, 0, 0, 0, 0, 0, 0, 0, 7];
+        let decoder = ABIDecoder::default();
+
+        let (first, consumed) = decoder.decode_prefix(&ParamType::U64, bytes)?;
+        let second = decoder.decode_exact(&ParamType::U32, &bytes[consumed..])?;
+
+        assert_eq!(first, Token::U64(101));
+        assert_eq!(second, Token::U32(7));
+        // ANCHOR_END: decoding_concatenated_values
+
+        Ok(())
+    }
+
     #[test]
     fn configuring_the_decoder() -> Result<()> {
         // ANCHOR: configuring_the_decoder
@@ -96,6 +117,7 @@ mod tests {
         ABIDecoder::new(DecoderConfig {
             max_depth: 5,
             max_tokens: 100,
+            deadline: None,
         });
         // ANCHOR_END: configuring_the_decoder
 
@@ -111,6 +133,7 @@ mod tests {
             max_depth: 5,
             max_tokens: 100,
             max_total_enum_width: 10_000,
+            align_dynamic_data: false,
         });
         // ANCHOR_END: configuring_the_encoder
 
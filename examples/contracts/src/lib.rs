@@ -788,6 +788,7 @@ mod tests {
             .with_decoder_config(DecoderConfig {
                 max_depth: 10,
                 max_tokens: 2_000,
+                deadline: None,
             })
             .call()
             .await?;
@@ -891,6 +892,7 @@ mod tests {
                 max_depth: 10,
                 max_tokens: 2_000,
                 max_total_enum_width: 10_000,
+                align_dynamic_data: false,
             })
             .methods()
             .initialize_counter(42)
@@ -898,6 +900,18 @@ mod tests {
             .await?;
         // ANCHOR_END: contract_encoder_config
 
+        // ANCHOR: call_encoder_config
+        let _ = contract_instance
+            .methods()
+            .initialize_counter(42)
+            .with_encoder_config(EncoderConfig {
+                max_tokens: 2_000,
+                ..Default::default()
+            })
+            .call()
+            .await?;
+        // ANCHOR_END: call_encoder_config
+
         Ok(())
     }
 }
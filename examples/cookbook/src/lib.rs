@@ -308,7 +308,9 @@ mod tests {
 
         // ANCHOR: custom_tx_build
         let tx = tb.build(&provider).await?;
-        let tx_id = provider.send_transaction(tx).await?;
+        let tx_id = tx.id(provider.chain_id());
+        let submitted_tx_id = provider.send_transaction(tx).await?;
+        assert_eq!(tx_id, submitted_tx_id);
         // ANCHOR_END: custom_tx_build
 
         // ANCHOR: custom_tx_verify
@@ -1,28 +1,40 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use fuel_core_client::client::pagination::{PaginatedResult, PaginationRequest};
 use fuel_tx::{Output, Receipt, TxId, TxPointer, UtxoId};
 use fuel_types::{AssetId, Bytes32, ContractId, Nonce};
 use fuels_core::{
+    codec::LogId,
     constants::BASE_ASSET_ID,
     types::{
         bech32::{Bech32Address, Bech32ContractId},
         coin::Coin,
         coin_type::CoinType,
-        errors::Result,
+        coin_type_id::CoinTypeId,
+        errors::{error, Result},
         input::Input,
-        message::Message,
-        transaction::{Transaction, TxPolicies},
+        message::{Message, MessageStatus},
+        param_types::ParamType,
+        transaction::{Transaction, TransactionType, TxPolicies},
         transaction_builders::{
             BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
         },
         transaction_response::TransactionResponse,
+        tx_status::{RevertReason, TxStatus},
+        unsigned_transaction::UnsignedTransaction,
     },
 };
 
 use crate::{
-    accounts_utils::{adjust_inputs_outputs, calculate_missing_base_amount, extract_message_nonce},
+    accounts_utils::{
+        absorb_dust_change, adjust_inputs_outputs, calculate_missing_base_amount,
+        check_attached_data_len, extract_message_nonce,
+    },
+    precondition::StatePrecondition,
     provider::{Provider, ResourceFilter},
 };
 
@@ -32,6 +44,8 @@ pub trait ViewOnlyAccount: std::fmt::Debug + Send + Sync + Clone {
 
     fn try_provider(&self) -> Result<&Provider>;
 
+    /// Gets the transactions involving the account, one page at a time. See
+    /// [`Provider::get_transactions_by_owner`].
     async fn get_transactions(
         &self,
         request: PaginationRequest<String>,
@@ -42,6 +56,17 @@ pub trait ViewOnlyAccount: std::fmt::Debug + Send + Sync + Clone {
             .await?)
     }
 
+    /// Gets the ids of the transactions involving the account, one page at a time. See
+    /// [`Provider::transactions_by_owner`].
+    async fn transaction_history(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<TxId, String>> {
+        self.try_provider()?
+            .transactions_by_owner(self.address(), request)
+            .await
+    }
+
     /// Gets all unspent coins of asset `asset_id` owned by the account.
     async fn get_coins(&self, asset_id: AssetId) -> Result<Vec<Coin>> {
         Ok(self
@@ -50,6 +75,14 @@ pub trait ViewOnlyAccount: std::fmt::Debug + Send + Sync + Clone {
             .await?)
     }
 
+    /// The number of unspent coins of asset `asset_id` owned by the account, see
+    /// [`Provider::coin_count`].
+    async fn utxo_count(&self, asset_id: AssetId) -> Result<u64> {
+        self.try_provider()?
+            .coin_count(self.address(), asset_id)
+            .await
+    }
+
     /// Get the balance of all spendable coins `asset_id` for address `address`. This is different
     /// from getting coins because we are just returning a number (the sum of UTXOs amount) instead
     /// of the UTXOs.
@@ -64,6 +97,32 @@ pub trait ViewOnlyAccount: std::fmt::Debug + Send + Sync + Clone {
         Ok(self.try_provider()?.get_messages(self.address()).await?)
     }
 
+    /// Waits until the message with `nonce` becomes spendable by the account, e.g. once a message
+    /// bridged from L1 gets relayed, and returns it. Fails if that doesn't happen within `timeout`.
+    /// The node is polled following the provider's
+    /// [`PollingStrategy`](crate::provider::PollingStrategy).
+    async fn await_message_spendable(&self, nonce: &Nonce, timeout: Duration) -> Result<Message> {
+        let provider = self.try_provider()?;
+
+        let message = provider
+            .polling_strategy()
+            .poll(Some(timeout), || async {
+                Ok(provider
+                    .get_messages(self.address())
+                    .await?
+                    .into_iter()
+                    .find(|message| message.nonce == *nonce))
+            })
+            .await?;
+
+        message.ok_or_else(|| {
+            error!(
+                Other,
+                "message with nonce `{nonce}` did not become spendable within {timeout:?}"
+            )
+        })
+    }
+
     /// Get all the spendable balances of all assets for the account. This is different from getting
     /// the coins because we are only returning the sum of UTXOs coins amount and not the UTXOs
     /// coins themselves.
@@ -117,9 +176,86 @@ pub trait Account: ViewOnlyAccount {
         ]
     }
 
+    /// Adds inputs of this account covering `amount` of `asset_id` to `tb`, along with the
+    /// witnesses they need, so that resources of several accounts, e.g. of a wallet and of
+    /// predicates, can be spent by the same transaction. A change output returning the rest of
+    /// `asset_id` to the account is added unless `tb` already has one for `asset_id`.
+    ///
+    /// A transaction has a single change output per asset, so the change of every account
+    /// spending `asset_id` goes to the owner of that output, i.e. to the first account added
+    /// unless the caller already put one in `tb`. Add the change output before the inputs to
+    /// choose who gets the change, e.g. so that a predicate's leftover goes to a wallet rather
+    /// than back to the predicate. Once all inputs are added, fund the fee with
+    /// [`adjust_for_fee`](Self::adjust_for_fee) on one of the accounts.
+    async fn add_inputs_for_amount<Tb: TransactionBuilder + Sync>(
+        &self,
+        tb: &mut Tb,
+        asset_id: AssetId,
+        amount: u64,
+    ) -> Result<()> {
+        let inputs = self.get_asset_inputs_for_amount(asset_id, amount).await?;
+
+        let has_inputs_of_account = tb
+            .inputs()
+            .iter()
+            .any(|input| input.owner() == Some(self.address()));
+        if !has_inputs_of_account {
+            self.add_witnesses(tb)?;
+        }
+        tb.inputs_mut().extend(inputs);
+
+        let has_change = tb
+            .outputs()
+            .iter()
+            .any(|output| matches!(output, Output::Change { asset_id: id, .. } if *id == asset_id));
+        if !has_change {
+            tb.outputs_mut()
+                .push(Output::change(self.address().into(), 0, asset_id));
+        }
+
+        Ok(())
+    }
+
+    /// Funds `tb` with inputs of this account covering every `(asset_id, amount)` in `amounts`,
+    /// e.g. the assets a swap sends to a contract, and the fee. Amounts of the same asset are
+    /// added up, so that its resources are selected once, and each asset gets one change
+    /// output, see [`add_inputs_for_amount`](Self::add_inputs_for_amount). The base asset
+    /// inputs cover both its amount, if any, and the fee.
+    async fn fund_for_amounts<Tb: TransactionBuilder + Sync>(
+        &self,
+        tb: &mut Tb,
+        amounts: &[(AssetId, u64)],
+    ) -> Result<()> {
+        let mut totals = BTreeMap::new();
+        for (asset_id, amount) in amounts {
+            let total: &mut u64 = totals.entry(*asset_id).or_default();
+            *total = total.checked_add(*amount).ok_or_else(|| {
+                error!(
+                    Other,
+                    "total amount of asset `{asset_id}` overflows a `u64`"
+                )
+            })?;
+        }
+
+        let base_amount = totals.remove(&BASE_ASSET_ID).unwrap_or_default();
+        for (asset_id, amount) in totals.into_iter().filter(|(_, amount)| *amount > 0) {
+            self.add_inputs_for_amount(tb, asset_id, amount).await?;
+        }
+
+        let has_inputs_of_account = tb
+            .inputs()
+            .iter()
+            .any(|input| input.owner() == Some(self.address()));
+        if !has_inputs_of_account {
+            self.add_witnesses(tb)?;
+        }
+
+        self.adjust_for_fee(tb, base_amount).await
+    }
+
     /// Add base asset inputs to the transaction to cover the estimated fee.
-    /// Requires contract inputs to be at the start of the transactions inputs vec
-    /// so that their indexes are retained
+    /// Contract inputs are moved to the start of the transactions inputs vec, see
+    /// [`TransactionBuilder::ensure_contract_inputs_first`]
     async fn adjust_for_fee<Tb: TransactionBuilder + Sync>(
         &self,
         tb: &mut Tb,
@@ -136,14 +272,280 @@ pub trait Account: ViewOnlyAccount {
             adjust_inputs_outputs(tb, new_base_inputs, self.address());
         };
 
+        if let Some(threshold) = self.dust_change_threshold() {
+            absorb_dust_change(
+                tb,
+                used_base_amount,
+                threshold,
+                self.address(),
+                self.try_provider()?,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Funds the fee of `tb` and its base asset coin outputs while leaving the existing outputs
+    /// untouched, for builders whose outputs are fully specified by the caller. Only base asset
+    /// inputs of this account are appended, along with a single base asset change output
+    /// returning the rest to the account, unless `tb` already has one. Unlike
+    /// [`adjust_for_fee`](Self::adjust_for_fee), inputs are not reordered and no change is
+    /// turned into tip.
+    async fn add_fee_resources<Tb: TransactionBuilder + Sync>(&self, tb: &mut Tb) -> Result<()> {
+        let has_base_change = tb.outputs().iter().any(
+            |output| matches!(output, Output::Change { asset_id, .. } if *asset_id == BASE_ASSET_ID),
+        );
+        if !has_base_change {
+            tb.outputs_mut()
+                .push(Output::change(self.address().into(), 0, BASE_ASSET_ID));
+        }
+
+        let used_base_amount = tb
+            .outputs()
+            .iter()
+            .filter_map(|output| match output {
+                Output::Coin {
+                    amount, asset_id, ..
+                } if *asset_id == BASE_ASSET_ID => Some(*amount),
+                _ => None,
+            })
+            .sum();
+        let missing_base_amount =
+            calculate_missing_base_amount(tb, used_base_amount, self.try_provider()?).await?;
+        if missing_base_amount == 0 {
+            return Ok(());
+        }
+
+        let new_base_inputs = self
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, missing_base_amount)
+            .await?;
+
+        let has_inputs_of_account = tb
+            .inputs()
+            .iter()
+            .any(|input| input.owner() == Some(self.address()));
+        if !has_inputs_of_account {
+            self.add_witnesses(tb)?;
+        }
+        tb.inputs_mut().extend(new_base_inputs);
+
         Ok(())
     }
 
+    /// The amount of base asset change below which [`adjust_for_fee`](Self::adjust_for_fee)
+    /// gives the change to the block producer, as part of the tip, instead of creating a change
+    /// output worth less than the fee needed to spend it later. Any refund of the max fee, e.g.
+    /// when the transaction uses less gas than estimated, is then lost as well. `None`, the
+    /// default, always keeps the change.
+    fn dust_change_threshold(&self) -> Option<u64> {
+        None
+    }
+
+    /// Checks the inputs of `tb` spending resources of this account against the provider and
+    /// replaces those spent since they were selected, e.g. by another process sharing the
+    /// account, with new resources covering at least the same amounts. Returns the number of
+    /// replaced inputs. Costs an extra query or two, see
+    /// [`revalidates_inputs`](Self::revalidates_inputs).
+    async fn replace_spent_inputs<Tb: TransactionBuilder + Sync>(
+        &self,
+        tb: &mut Tb,
+    ) -> Result<usize> {
+        let provider = self.try_provider()?;
+
+        let owned_ids: Vec<_> = tb
+            .inputs()
+            .iter()
+            .filter_map(Input::resource)
+            .filter(|resource| resource.owner() == self.address())
+            .map(CoinType::id)
+            .collect();
+        if owned_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let utxo_ids: Vec<_> = owned_ids
+            .iter()
+            .filter_map(|id| match id {
+                CoinTypeId::UtxoId(utxo_id) => Some(*utxo_id),
+                CoinTypeId::Nonce(_) => None,
+            })
+            .collect();
+        let mut unspent: HashSet<_> = provider
+            .coins(&utxo_ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|coin| CoinTypeId::UtxoId(coin.utxo_id))
+            .collect();
+        if utxo_ids.len() < owned_ids.len() {
+            unspent.extend(
+                provider
+                    .get_messages(self.address())
+                    .await?
+                    .into_iter()
+                    .filter(|message| matches!(message.status, MessageStatus::Unspent))
+                    .map(|message| CoinTypeId::Nonce(message.nonce)),
+            );
+        }
+
+        let (spent, kept): (Vec<_>, Vec<_>) = std::mem::take(tb.inputs_mut())
+            .into_iter()
+            .partition(|input| {
+                input.resource().is_some_and(|resource| {
+                    resource.owner() == self.address() && !unspent.contains(&resource.id())
+                })
+            });
+        *tb.inputs_mut() = kept;
+
+        // The replacements take the form of the first spent input of their asset, e.g. a
+        // predicate input with the same code and data.
+        let mut missing: HashMap<AssetId, (u64, &Input)> = HashMap::new();
+        for input in &spent {
+            let resource = input.resource().expect("only resources are spent");
+            missing.entry(resource.asset_id()).or_insert((0, input)).0 += resource.amount();
+        }
+
+        let (excluded_utxos, excluded_message_nonces) =
+            owned_ids
+                .into_iter()
+                .fold((vec![], vec![]), |(mut utxos, mut nonces), id| {
+                    match id {
+                        CoinTypeId::UtxoId(utxo_id) => utxos.push(utxo_id),
+                        CoinTypeId::Nonce(nonce) => nonces.push(nonce),
+                    }
+                    (utxos, nonces)
+                });
+        for (asset_id, (amount, template)) in missing {
+            let filter = ResourceFilter {
+                from: self.address().clone(),
+                asset_id,
+                amount,
+                excluded_utxos: excluded_utxos.clone(),
+                excluded_message_nonces: excluded_message_nonces.clone(),
+                ..Default::default()
+            };
+            let replacements = provider
+                .get_spendable_resources(filter)
+                .await?
+                .into_iter()
+                .map(|resource| match template {
+                    Input::ResourcePredicate { code, data, .. } => {
+                        Input::resource_predicate(resource, code.clone(), data.clone())
+                    }
+                    _ => Input::resource_signed(resource),
+                });
+            tb.inputs_mut().extend(replacements);
+        }
+
+        Ok(spent.len())
+    }
+
+    /// Whether [`send_funded`](Self::send_funded), and with it the transfers of this account,
+    /// call [`replace_spent_inputs`](Self::replace_spent_inputs) right before sending. Enable it
+    /// for accounts spent from by several processes, where resources can be spent between their
+    /// selection and the submission of a transaction.
+    fn revalidates_inputs(&self) -> bool {
+        false
+    }
+
     // Add signatures to the builder if the underlying account is a wallet
     fn add_witnesses<Tb: TransactionBuilder>(&self, _tb: &mut Tb) -> Result<()> {
         Ok(())
     }
 
+    /// Funds `tb` like [`adjust_for_fee`](Self::adjust_for_fee) and builds it without any
+    /// signatures, so that it can be signed where the keys are kept. See
+    /// [`UnsignedTransaction`] for how to sign the result and
+    /// [`Wallet::build_unsigned`](crate::wallet::Wallet::build_unsigned) for locked wallets.
+    async fn build_unsigned<Tb: TransactionBuilder + Sync>(
+        &self,
+        mut tb: Tb,
+        used_base_amount: u64,
+    ) -> Result<UnsignedTransaction<<Tb as BuildableTransaction>::TxType>> {
+        let provider = self.try_provider()?;
+
+        self.adjust_for_fee(&mut tb, used_base_amount).await?;
+        let tx = tb.build_without_signatures(provider).await?;
+
+        Ok(UnsignedTransaction::new(tx, provider.chain_id()))
+    }
+
+    /// Prepares a transfer of `amount` of `asset_id` to `to`. The returned builder spends enough
+    /// of `asset_id` for the transfer and enough of the base asset to cover the fee, even when
+    /// `asset_id` is not the base asset.
+    async fn fund_transfer(
+        &self,
+        to: &Bech32Address,
+        amount: u64,
+        asset_id: AssetId,
+        tx_policies: TxPolicies,
+    ) -> Result<ScriptTransactionBuilder> {
+        self.fund_transfer_with_data(to, amount, asset_id, vec![], tx_policies)
+            .await
+    }
+
+    /// Same as [`fund_transfer`](Self::fund_transfer) but attaches `data` to the transfer. Coin
+    /// outputs cannot carry data, so it is placed in the script data of the transaction, where it
+    /// can be read back from the transaction, e.g. to reconcile the transfer with an invoice.
+    /// Fails if `data` is longer than the chain's `max_script_data_length`.
+    async fn fund_transfer_with_data(
+        &self,
+        to: &Bech32Address,
+        amount: u64,
+        asset_id: AssetId,
+        data: Vec<u8>,
+        tx_policies: TxPolicies,
+    ) -> Result<ScriptTransactionBuilder> {
+        let max_len = self
+            .try_provider()?
+            .consensus_parameters()
+            .script_params
+            .max_script_data_length;
+        check_attached_data_len(&data, max_len, "max_script_data_length")?;
+
+        let inputs = self.get_asset_inputs_for_amount(asset_id, amount).await?;
+        let outputs = self.get_asset_outputs_for_amount(to, asset_id, amount);
+
+        let mut tx_builder =
+            ScriptTransactionBuilder::prepare_transfer(inputs, outputs, tx_policies)
+                .with_script_data(data);
+
+        self.add_witnesses(&mut tx_builder)?;
+
+        let used_base_amount = if asset_id == AssetId::BASE { amount } else { 0 };
+        self.adjust_for_fee(&mut tx_builder, used_base_amount)
+            .await?;
+
+        Ok(tx_builder)
+    }
+
+    /// Sends `tb`, funded by this account, and waits for it to be committed. If the account
+    /// [`revalidates_inputs`](Self::revalidates_inputs), the inputs spent since their selection
+    /// are replaced right before it is built. `used_base_amount` is the amount given to
+    /// [`adjust_for_fee`](Self::adjust_for_fee) when funding `tb`.
+    async fn send_funded<Tb: TransactionBuilder + Sync>(
+        &self,
+        mut tb: Tb,
+        used_base_amount: u64,
+    ) -> Result<(TxId, Vec<Receipt>)> {
+        let provider = self.try_provider()?;
+
+        if self.revalidates_inputs() && self.replace_spent_inputs(&mut tb).await? > 0 {
+            // The replacements can change the number of inputs and with it the fee.
+            self.adjust_for_fee(&mut tb, used_base_amount).await?;
+        }
+
+        let tx = tb.build(provider).await?;
+        let tx_id = tx.id(provider.chain_id());
+
+        let tx_status = provider.send_transaction_and_await_commit(tx).await?;
+
+        let receipts = tx_status.take_receipts_checked(None)?;
+
+        Ok((tx_id, receipts))
+    }
+
     /// Transfer funds from this account to another `Address`.
     /// Fails if amount for asset ID is larger than address's spendable coins.
     /// Returns the transaction ID that was sent and the list of receipts.
@@ -154,18 +556,85 @@ pub trait Account: ViewOnlyAccount {
         asset_id: AssetId,
         tx_policies: TxPolicies,
     ) -> Result<(TxId, Vec<Receipt>)> {
-        let provider = self.try_provider()?;
+        self.transfer_with_data(to, amount, asset_id, vec![], tx_policies)
+            .await
+    }
 
+    /// Same as [`transfer`](Self::transfer) but attaches `data` to the transfer, see
+    /// [`fund_transfer_with_data`](Self::fund_transfer_with_data).
+    async fn transfer_with_data(
+        &self,
+        to: &Bech32Address,
+        amount: u64,
+        asset_id: AssetId,
+        data: Vec<u8>,
+        tx_policies: TxPolicies,
+    ) -> Result<(TxId, Vec<Receipt>)> {
+        let tx_builder = self
+            .fund_transfer_with_data(to, amount, asset_id, data, tx_policies)
+            .await?;
+
+        let used_base_amount = if asset_id == AssetId::BASE { amount } else { 0 };
+        self.send_funded(tx_builder, used_base_amount).await
+    }
+
+    /// Same as [`fund_transfer`](Self::fund_transfer) but the fee is paid by `fee_payer`, so the
+    /// account only needs to own the transferred `asset_id`. The account gets back the change of
+    /// `asset_id` while `fee_payer` gets back the unused base asset. As a transaction can only
+    /// have one change output per asset, the excess of the account's base asset inputs is
+    /// returned to it with a plain coin output when transferring the base asset.
+    async fn fund_transfer_with_fee_payer<F: Account>(
+        &self,
+        to: &Bech32Address,
+        amount: u64,
+        asset_id: AssetId,
+        fee_payer: &F,
+        tx_policies: TxPolicies,
+    ) -> Result<ScriptTransactionBuilder> {
         let inputs = self.get_asset_inputs_for_amount(asset_id, amount).await?;
-        let outputs = self.get_asset_outputs_for_amount(to, asset_id, amount);
+
+        let mut outputs = vec![Output::coin(to.into(), amount, asset_id)];
+        let used_base_amount = if asset_id == BASE_ASSET_ID {
+            let spent_amount: u64 = inputs.iter().filter_map(Input::amount).sum();
+            let excess = spent_amount - amount;
+            if excess > 0 {
+                outputs.push(Output::coin(self.address().into(), excess, asset_id));
+            }
+
+            spent_amount
+        } else {
+            outputs.push(Output::change(self.address().into(), 0, asset_id));
+
+            0
+        };
 
         let mut tx_builder =
             ScriptTransactionBuilder::prepare_transfer(inputs, outputs, tx_policies);
 
         self.add_witnesses(&mut tx_builder)?;
+        fee_payer.add_witnesses(&mut tx_builder)?;
 
-        let used_base_amount = if asset_id == AssetId::BASE { amount } else { 0 };
-        self.adjust_for_fee(&mut tx_builder, used_base_amount)
+        fee_payer
+            .adjust_for_fee(&mut tx_builder, used_base_amount)
+            .await?;
+
+        Ok(tx_builder)
+    }
+
+    /// Same as [`transfer`](Self::transfer) but the fee is paid by `fee_payer`, see
+    /// [`fund_transfer_with_fee_payer`](Self::fund_transfer_with_fee_payer).
+    async fn transfer_with_fee_payer<F: Account>(
+        &self,
+        to: &Bech32Address,
+        amount: u64,
+        asset_id: AssetId,
+        fee_payer: &F,
+        tx_policies: TxPolicies,
+    ) -> Result<(TxId, Vec<Receipt>)> {
+        let provider = self.try_provider()?;
+
+        let tx_builder = self
+            .fund_transfer_with_fee_payer(to, amount, asset_id, fee_payer, tx_policies)
             .await?;
 
         let tx = tx_builder.build(provider).await?;
@@ -245,16 +714,37 @@ pub trait Account: ViewOnlyAccount {
         to: &Bech32Address,
         amount: u64,
         tx_policies: TxPolicies,
+    ) -> Result<(TxId, Nonce, Vec<Receipt>)> {
+        self.withdraw_to_base_layer_with_data(to, amount, vec![], tx_policies)
+            .await
+    }
+
+    /// Same as [`withdraw_to_base_layer`](Self::withdraw_to_base_layer) but the message sent to
+    /// the base chain carries `data`.
+    /// Fails if `data` is longer than the chain's `max_message_data_length`.
+    async fn withdraw_to_base_layer_with_data(
+        &self,
+        to: &Bech32Address,
+        amount: u64,
+        data: Vec<u8>,
+        tx_policies: TxPolicies,
     ) -> Result<(TxId, Nonce, Vec<Receipt>)> {
         let provider = self.try_provider()?;
 
+        let max_len = provider
+            .consensus_parameters()
+            .predicate_params
+            .max_message_data_length;
+        check_attached_data_len(&data, max_len, "max_message_data_length")?;
+
         let inputs = self
             .get_asset_inputs_for_amount(BASE_ASSET_ID, amount)
             .await?;
 
-        let mut tb = ScriptTransactionBuilder::prepare_message_to_output(
+        let mut tb = ScriptTransactionBuilder::prepare_message_to_output_with_data(
             to.into(),
             amount,
+            data,
             inputs,
             tx_policies,
         );
@@ -274,6 +764,99 @@ pub trait Account: ViewOnlyAccount {
 
         Ok((tx_id, nonce, receipts))
     }
+
+    /// Dry runs `tx` and returns the decoded reason if it reverts, or `None` if it doesn't.
+    /// The value logged by a failing `require` is decoded using the types in `log_types`.
+    async fn dry_run_revert_reason<T: Transaction + Send>(
+        &self,
+        tx: T,
+        log_types: &HashMap<LogId, ParamType>,
+    ) -> Result<Option<RevertReason>> {
+        self.try_provider()?
+            .dry_run(tx)
+            .await?
+            .revert_reason(log_types)
+    }
+
+    /// Submits `tx` only if `precondition` holds, checking it against the chain state right
+    /// before sending. Fails with `Reason::PreconditionFailed` otherwise, without submitting.
+    ///
+    /// This is best effort: the state can still change between the check and the inclusion of
+    /// `tx` in a block.
+    async fn send_if<T: Transaction + Send>(
+        &self,
+        tx: T,
+        precondition: StatePrecondition,
+    ) -> Result<TxId> {
+        let provider = self.try_provider()?;
+
+        precondition.verify(provider).await?;
+
+        provider.send_transaction(tx).await
+    }
+
+    /// Replaces the pending script transaction `tx_id` with one spending the same inputs but
+    /// paying the higher `tip`, so that the node drops the original in its favor. Only this
+    /// account's signatures are added to the replacement.
+    /// Returns the ID of the replacement transaction.
+    async fn bump_fee(&self, tx_id: &TxId, tip: u64) -> Result<TxId> {
+        let provider = self.try_provider()?;
+
+        let response = provider
+            .get_transaction_by_id(tx_id)
+            .await?
+            .ok_or_else(|| error!(Other, "transaction `{tx_id}` not found"))?;
+
+        match response.status {
+            TxStatus::Submitted => {}
+            TxStatus::SqueezedOut { reason } => {
+                return Err(error!(
+                    Other,
+                    "transaction `{tx_id}` was squeezed out: {reason}. Submit a new transaction instead"
+                ))
+            }
+            TxStatus::Success { .. } | TxStatus::Revert { .. } => {
+                return Err(error!(
+                    Other,
+                    "transaction `{tx_id}` is already included in a block and its inputs are spent"
+                ))
+            }
+        }
+
+        let TransactionType::Script(tx) = response.transaction else {
+            return Err(error!(
+                Other,
+                "only script transactions can have their fee bumped"
+            ));
+        };
+
+        if tip <= tx.tip() {
+            return Err(error!(
+                Other,
+                "new tip `{tip}` must be greater than the current tip `{}`",
+                tx.tip()
+            ));
+        }
+
+        let tx_policies = TxPolicies::default()
+            .with_tip(tip)
+            .with_maturity(tx.maturity().into())
+            .with_script_gas_limit(tx.gas_limit());
+        let inputs = tx.inputs().iter().cloned().map(Input::from).collect();
+
+        let mut tb = ScriptTransactionBuilder::default()
+            .with_script(tx.script().clone())
+            .with_script_data(tx.script_data().clone())
+            .with_inputs(inputs)
+            .with_outputs(tx.outputs().clone())
+            .with_tx_policies(tx_policies);
+
+        self.add_witnesses(&mut tb)?;
+
+        let tx = tb.build(provider).await?;
+
+        provider.send_transaction(tx).await
+    }
 }
 
 #[cfg(test)]
@@ -281,10 +864,13 @@ mod tests {
     use std::str::FromStr;
 
     use fuel_crypto::{Message, SecretKey, Signature};
-    use fuel_tx::{Address, ConsensusParameters, Output, Transaction as FuelTransaction};
+    use fuel_tx::{Address, ConsensusParameters, Output, Transaction as FuelTransaction, Witness};
     use fuels_core::{
         traits::Signer,
-        types::{transaction::Transaction, transaction_builders::DryRunner},
+        types::{
+            transaction::{ScriptTransaction, Transaction},
+            transaction_builders::DryRunner,
+        },
     };
     use rand::{rngs::StdRng, RngCore, SeedableRng};
 
@@ -404,4 +990,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn unsigned_tx_is_signed_after_a_bytes_roundtrip(
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let secret = SecretKey::from_str(
+            "5f70feeff1f229e4a95e1056e8b4d80d0b24b565674860cc213bdb07127ce1b1",
+        )?;
+        let wallet = WalletUnlocked::new_from_private_key(secret, None);
+
+        let input_coin = Input::ResourceSigned {
+            resource: CoinType::Coin(Coin {
+                amount: 10000000,
+                owner: wallet.address().clone(),
+                ..Default::default()
+            }),
+        };
+        let tb = ScriptTransactionBuilder::prepare_transfer(
+            vec![input_coin],
+            vec![Output::coin(Address::zeroed(), 1, Default::default())],
+            Default::default(),
+        );
+
+        let tx = tb
+            .build_without_signatures(&MockDryRunner::default())
+            .await?;
+        let unsigned = UnsignedTransaction::new(tx, 0.into());
+        assert!(unsigned.transaction().witnesses().is_empty());
+
+        let received = UnsignedTransaction::<ScriptTransaction>::from_bytes(&unsigned.to_bytes())?;
+        assert_eq!(received.chain_id(), unsigned.chain_id());
+
+        let tx = received.sign(&wallet).await?;
+
+        let message = Message::from_bytes(*tx.id(0.into()));
+        let expected_witness: Witness = wallet.sign(message).await?.as_ref().into();
+        assert_eq!(*tx.witnesses(), vec![expected_witness]);
+
+        Ok(())
+    }
 }
@@ -1,16 +1,16 @@
-use fuel_tx::{Output, Receipt};
-use fuel_types::Nonce;
+use fuel_tx::{Output, Receipt, TransactionFee};
+use fuel_types::{Address, Nonce};
 use fuels_core::{
     constants::BASE_ASSET_ID,
     types::{
         bech32::Bech32Address,
         errors::{error, error_transaction, Error, Result},
         input::Input,
-        transaction_builders::TransactionBuilder,
+        transaction_builders::{DryRunner, TransactionBuilder},
     },
 };
 
-use crate::provider::Provider;
+use crate::provider::{FeeCacheKey, Provider};
 
 pub fn extract_message_nonce(receipts: &[Receipt]) -> Option<Nonce> {
     receipts.iter().find_map(|m| m.nonce()).copied()
@@ -21,13 +21,7 @@ pub async fn calculate_missing_base_amount(
     used_base_amount: u64,
     provider: &Provider,
 ) -> Result<u64> {
-    let transaction_fee = tb
-        .fee_checked_from_tx(provider)
-        .await?
-        .ok_or(error_transaction!(
-            Other,
-            "error calculating `TransactionFee`"
-        ))?;
+    let transaction_fee = estimate_fee(tb, provider).await?;
 
     let available_amount = available_base_amount(tb);
 
@@ -45,6 +39,81 @@ pub async fn calculate_missing_base_amount(
     Ok(missing_amount)
 }
 
+/// Gives the base asset change of `tb` to the block producer, by raising the tip, and drops the
+/// base asset change output of `address` if the change is positive but below `threshold`.
+/// Returns whether the change was absorbed, which never happens if the base asset change goes to
+/// another address. The fee is estimated without the fee cache since the tip changes.
+pub(crate) async fn absorb_dust_change(
+    tb: &mut impl TransactionBuilder,
+    used_base_amount: u64,
+    threshold: u64,
+    address: &Bech32Address,
+    provider: &Provider,
+) -> Result<bool> {
+    let owner = Address::from(address);
+    let is_own_base_change = |output: &Output| matches!(output, Output::Change { to, asset_id, .. } if *asset_id == BASE_ASSET_ID && *to == owner);
+    if !tb.outputs().iter().any(is_own_base_change) {
+        return Ok(false);
+    }
+
+    let available_amount = available_base_amount(tb);
+    let change = |fee: TransactionFee| {
+        available_amount.saturating_sub(fee.max_fee().saturating_add(used_base_amount))
+    };
+
+    let dust = change(fee_checked_from_tx(tb, provider).await?);
+    if dust == 0 || dust >= threshold {
+        return Ok(false);
+    }
+
+    tb.outputs_mut()
+        .retain(|output| !is_own_base_change(output));
+    // Setting the tip policy first so that raising it doesn't change the size of the
+    // transaction and with it the fee.
+    let tip = tb.tx_policies().tip().unwrap_or_default();
+    *tb.tx_policies_mut() = tb.tx_policies().with_tip(tip);
+
+    let dust = change(fee_checked_from_tx(tb, provider).await?);
+    *tb.tx_policies_mut() = tb.tx_policies().with_tip(tip + dust);
+
+    Ok(true)
+}
+
+async fn estimate_fee(tb: &impl TransactionBuilder, provider: &Provider) -> Result<TransactionFee> {
+    if !provider.is_fee_cache_enabled() {
+        return fee_checked_from_tx(tb, provider).await;
+    }
+
+    // Keyed on the latest gas price, which is cached, so that a hit costs no request. The fee
+    // itself is computed with the price estimated for the horizon of the builder.
+    let key = FeeCacheKey::new(
+        tb.fee_fingerprint(),
+        tb.estimation_horizon(),
+        provider.current_gas_price().await?,
+    );
+
+    if let Some(fee) = provider.cached_fee(&key) {
+        return Ok(fee);
+    }
+
+    let fee = fee_checked_from_tx(tb, provider).await?;
+    provider.cache_fee(key, fee);
+
+    Ok(fee)
+}
+
+async fn fee_checked_from_tx(
+    tb: &impl TransactionBuilder,
+    provider: impl DryRunner,
+) -> Result<TransactionFee> {
+    tb.fee_checked_from_tx(provider)
+        .await?
+        .ok_or(error_transaction!(
+            Other,
+            "error calculating `TransactionFee`"
+        ))
+}
+
 fn available_base_amount(tb: &impl TransactionBuilder) -> u64 {
     tb.inputs()
         .iter()
@@ -67,6 +136,7 @@ pub fn adjust_inputs_outputs(
     address: &Bech32Address,
 ) {
     tb.inputs_mut().extend(new_base_inputs);
+    tb.ensure_contract_inputs_first();
 
     let is_base_change_present = tb.outputs().iter().any(|output| {
         matches!(output , Output::Change { asset_id , .. }
@@ -85,3 +155,36 @@ pub(crate) fn try_provider_error() -> Error {
         "no provider available. Make sure to use `set_provider`"
     )
 }
+
+/// Rejects switching from `current` to a provider connected to a different chain.
+pub(crate) fn check_same_chain(current: Option<&Provider>, new: &Provider) -> Result<()> {
+    let Some(current) = current else {
+        return Ok(());
+    };
+
+    let (current_chain_id, new_chain_id) = (current.chain_id(), new.chain_id());
+    if current_chain_id != new_chain_id {
+        return Err(error!(
+            Other,
+            "cannot rotate from a provider on chain `{current_chain_id}` to one on chain \
+            `{new_chain_id}`. Use `set_provider` to switch chains deliberately"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Rejects data attached to a transfer that is longer than the chain allows, `limit_name` being
+/// the consensus parameter that `max_len` was read from.
+pub(crate) fn check_attached_data_len(data: &[u8], max_len: u64, limit_name: &str) -> Result<()> {
+    let data_len = data.len() as u64;
+
+    if data_len > max_len {
+        return Err(error!(
+            Other,
+            "attached data is `{data_len}` bytes long but `{limit_name}` is `{max_len}`"
+        ));
+    }
+
+    Ok(())
+}
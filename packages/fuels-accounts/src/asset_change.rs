@@ -0,0 +1,91 @@
+use fuel_tx::{ContractIdExt, Receipt};
+use fuel_types::AssetId;
+use fuels_core::types::bech32::Bech32ContractId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetChangeKind {
+    Mint,
+    Burn,
+}
+
+/// A change in the supply of a native asset, decoded from a `Mint` or `Burn` receipt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetChange {
+    pub kind: AssetChangeKind,
+    pub asset_id: AssetId,
+    pub amount: u64,
+    /// The contract whose balance was credited (on mint) or debited (on burn). Minted coins
+    /// are owned by the minting contract until it transfers them.
+    pub recipient: Bech32ContractId,
+}
+
+impl AssetChange {
+    /// Decodes all `Mint` and `Burn` receipts in `receipts`, in order of appearance.
+    pub fn from_receipts(receipts: &[Receipt]) -> Vec<AssetChange> {
+        receipts.iter().filter_map(Self::from_receipt).collect()
+    }
+
+    pub fn from_receipt(receipt: &Receipt) -> Option<AssetChange> {
+        let (kind, sub_id, contract_id, amount) = match receipt {
+            Receipt::Mint {
+                sub_id,
+                contract_id,
+                val,
+                ..
+            } => (AssetChangeKind::Mint, sub_id, contract_id, *val),
+            Receipt::Burn {
+                sub_id,
+                contract_id,
+                val,
+                ..
+            } => (AssetChangeKind::Burn, sub_id, contract_id, *val),
+            _ => return None,
+        };
+
+        Some(AssetChange {
+            kind,
+            asset_id: contract_id.asset_id(sub_id),
+            amount,
+            recipient: (*contract_id).into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_types::{Bytes32, ContractId};
+
+    use super::*;
+
+    #[test]
+    fn mint_and_burn_receipts_are_decoded() {
+        let contract_id = ContractId::from([1; 32]);
+        let sub_id = Bytes32::from([2; 32]);
+        let receipts = [
+            Receipt::mint(sub_id, contract_id, 100, 0, 0),
+            Receipt::ret(contract_id, 0, 0, 0),
+            Receipt::burn(sub_id, contract_id, 40, 0, 0),
+        ];
+
+        let changes = AssetChange::from_receipts(&receipts);
+
+        let expected_asset_id = contract_id.asset_id(&sub_id);
+        assert_eq!(
+            changes,
+            vec![
+                AssetChange {
+                    kind: AssetChangeKind::Mint,
+                    asset_id: expected_asset_id,
+                    amount: 100,
+                    recipient: contract_id.into(),
+                },
+                AssetChange {
+                    kind: AssetChangeKind::Burn,
+                    asset_id: expected_asset_id,
+                    amount: 40,
+                    recipient: contract_id.into(),
+                },
+            ]
+        );
+    }
+}
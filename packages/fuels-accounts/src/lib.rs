@@ -2,9 +2,14 @@
 mod account;
 #[cfg(feature = "std")]
 mod accounts_utils;
+pub mod asset_change;
+#[cfg(feature = "std")]
+pub mod precondition;
 #[cfg(feature = "std")]
 pub mod provider;
 #[cfg(feature = "std")]
+pub mod template;
+#[cfg(feature = "std")]
 pub mod wallet;
 
 #[cfg(feature = "std")]
@@ -0,0 +1,111 @@
+use std::{fmt::Debug, sync::Arc};
+
+use fuel_tx::Receipt;
+use fuel_types::AssetId;
+use fuels_core::types::{
+    bech32::Bech32ContractId,
+    errors::{error_transaction, Result},
+    transaction::ScriptTransaction,
+};
+
+use crate::provider::Provider;
+
+type ReceiptsCheck = Arc<dyn Fn(&[Receipt]) -> bool + Send + Sync>;
+
+/// Chain state that must hold for [`Account::send_if`](crate::Account::send_if) to submit a
+/// transaction.
+#[derive(Clone)]
+pub enum StatePrecondition {
+    /// The contract holds exactly `amount` of `asset_id`.
+    ContractBalance {
+        contract_id: Bech32ContractId,
+        asset_id: AssetId,
+        amount: u64,
+    },
+    /// `check` returns `true` for the receipts of dry running `tx`. Use a script calling a
+    /// contract getter to compare a storage slot against an expected value.
+    DryRun {
+        tx: ScriptTransaction,
+        check: ReceiptsCheck,
+    },
+}
+
+impl StatePrecondition {
+    pub fn contract_balance(
+        contract_id: impl Into<Bech32ContractId>,
+        asset_id: AssetId,
+        amount: u64,
+    ) -> Self {
+        Self::ContractBalance {
+            contract_id: contract_id.into(),
+            asset_id,
+            amount,
+        }
+    }
+
+    pub fn dry_run(
+        tx: ScriptTransaction,
+        check: impl Fn(&[Receipt]) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self::DryRun {
+            tx,
+            check: Arc::new(check),
+        }
+    }
+
+    /// Reads the current chain state and fails with `Reason::PreconditionFailed` if the
+    /// precondition doesn't hold.
+    pub async fn verify(&self, provider: &Provider) -> Result<()> {
+        match self {
+            Self::ContractBalance {
+                contract_id,
+                asset_id,
+                amount,
+            } => {
+                let balance = provider
+                    .get_contract_asset_balance(contract_id, *asset_id)
+                    .await?;
+
+                if balance != *amount {
+                    return Err(error_transaction!(
+                        PreconditionFailed,
+                        "contract `{contract_id}` holds `{balance}` of asset `{asset_id}`, expected `{amount}`"
+                    ));
+                }
+            }
+            Self::DryRun { tx, check } => {
+                let receipts = provider.dry_run(tx.clone()).await?.take_receipts();
+
+                if !check(&receipts) {
+                    return Err(error_transaction!(
+                        PreconditionFailed,
+                        "check rejected the receipts of the dry run"
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for StatePrecondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ContractBalance {
+                contract_id,
+                asset_id,
+                amount,
+            } => f
+                .debug_struct("ContractBalance")
+                .field("contract_id", contract_id)
+                .field("asset_id", asset_id)
+                .field("amount", amount)
+                .finish(),
+            Self::DryRun { tx, .. } => f
+                .debug_struct("DryRun")
+                .field("tx", tx)
+                .finish_non_exhaustive(),
+        }
+    }
+}
@@ -1,21 +1,28 @@
-use std::{fmt::Debug, fs};
+use std::{fmt::Debug, fs, sync::Arc};
 
 #[cfg(feature = "std")]
 use fuels_core::types::{input::Input, AssetId};
 use fuels_core::{
-    types::{bech32::Bech32Address, errors::Result, unresolved_bytes::UnresolvedBytes},
-    Configurables,
+    codec::{ABIEncoder, ConfigurablesEncoder, EncoderConfig},
+    types::{
+        bech32::Bech32Address,
+        errors::{error, Result},
+        unresolved_bytes::UnresolvedBytes,
+        Token,
+    },
+    ConfigurableSlots, Configurables,
 };
 
 #[cfg(feature = "std")]
-use crate::accounts_utils::try_provider_error;
+use crate::accounts_utils::{check_same_chain, try_provider_error};
 #[cfg(feature = "std")]
 use crate::{provider::Provider, Account, ViewOnlyAccount};
 
 #[derive(Debug, Clone)]
 pub struct Predicate {
     address: Bech32Address,
-    code: Vec<u8>,
+    /// Shared between clones and only copied when modified.
+    code: Arc<Vec<u8>>,
     data: UnresolvedBytes,
     #[cfg(feature = "std")]
     provider: Option<Provider>,
@@ -26,7 +33,7 @@ impl Predicate {
         &self.address
     }
 
-    pub fn code(&self) -> &Vec<u8> {
+    pub fn code(&self) -> &[u8] {
         &self.code
     }
 
@@ -46,22 +53,59 @@ impl Predicate {
     pub fn from_code(code: Vec<u8>) -> Self {
         Self {
             address: Self::calculate_address(&code),
-            code,
+            code: Arc::new(code),
             data: Default::default(),
             #[cfg(feature = "std")]
             provider: None,
         }
     }
 
+    /// Returns `true` if non-empty predicate data has been configured.
+    pub fn data_is_set(&self) -> bool {
+        self.data != UnresolvedBytes::default()
+    }
+
+    /// Sets the predicate data, replacing any previously set data. Data depending on the
+    /// transaction, e.g. the index of a witness holding a signature, can be set on the
+    /// transaction builder instead with `TransactionBuilder::set_predicate_data`.
     pub fn with_data(mut self, data: UnresolvedBytes) -> Self {
         self.data = data;
         self
     }
 
+    /// Same as [`with_data`](Self::with_data) but fails if the predicate data was already set.
+    pub fn try_with_data(self, data: UnresolvedBytes) -> Result<Self> {
+        if self.data_is_set() {
+            return Err(error!(
+                Other,
+                "predicate data is already set. Use `with_data` to override it"
+            ));
+        }
+
+        Ok(self.with_data(data))
+    }
+
+    /// Sets the predicate data to `tokens` encoded with `encoder_config`. If a provider is
+    /// attached, fails when the encoded data is longer than the chain's
+    /// `max_predicate_data_length`, which would get the transaction rejected.
+    pub fn with_data_from_tokens(
+        self,
+        tokens: &[Token],
+        encoder_config: EncoderConfig,
+    ) -> Result<Self> {
+        let data = ABIEncoder::new(encoder_config).encode(tokens)?;
+        let predicate = self.with_data(data);
+
+        #[cfg(feature = "std")]
+        predicate.check_data_size()?;
+
+        Ok(predicate)
+    }
+
     pub fn with_code(self, code: Vec<u8>) -> Self {
         let address = Self::calculate_address(&code);
         Self {
-            code,
+            code: Arc::new(code),
             address,
             ..self
         }
@@ -69,11 +113,23 @@ impl Predicate {
 
     pub fn with_configurables(mut self, configurables: impl Into<Configurables>) -> Self {
         let configurables: Configurables = configurables.into();
-        configurables.update_constants_in(&mut self.code);
+        configurables.update_constants_in(Arc::make_mut(&mut self.code));
         let address = Self::calculate_address(&self.code);
         self.address = address;
         self
     }
+
+    /// Sets configurables from `(name, token)` pairs instead of the typed `abigen!` bindings.
+    /// The offset and type of each configurable is looked up in `slots`.
+    pub fn with_configurable_tokens<'a>(
+        self,
+        slots: &ConfigurableSlots,
+        values: impl IntoIterator<Item = (&'a str, Token)>,
+    ) -> Result<Self> {
+        let configurables = slots.encode(values, &ConfigurablesEncoder::default())?;
+
+        Ok(self.with_configurables(configurables))
+    }
 }
 
 #[cfg(feature = "std")]
@@ -82,16 +138,51 @@ impl Predicate {
         self.provider.as_ref()
     }
 
+    /// See [`Wallet::set_provider`](crate::wallet::Wallet::set_provider).
     pub fn set_provider(&mut self, provider: Provider) {
         self.provider = Some(provider);
     }
 
+    /// See [`Wallet::rotate_provider`](crate::wallet::Wallet::rotate_provider).
+    pub fn rotate_provider(&mut self, provider: Provider) -> Result<()> {
+        check_same_chain(self.provider(), &provider)?;
+        self.set_provider(provider);
+
+        Ok(())
+    }
+
     pub fn with_provider(self, provider: Provider) -> Self {
         Self {
             provider: Some(provider),
             ..self
         }
     }
+
+    fn check_data_size(&self) -> Result<()> {
+        let Some(provider) = &self.provider else {
+            return Ok(());
+        };
+
+        let max_len = provider
+            .consensus_parameters()
+            .predicate_params
+            .max_predicate_data_length;
+
+        self.check_data_size_against(max_len)
+    }
+
+    fn check_data_size_against(&self, max_len: u64) -> Result<()> {
+        let data_len = self.data.resolve(0).len() as u64;
+
+        if data_len > max_len {
+            return Err(error!(
+                Codec,
+                "predicate data is `{data_len}` bytes long but `max_predicate_data_length` is `{max_len}`"
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "std")]
@@ -123,3 +214,80 @@ impl Account for Predicate {
             .collect::<Vec<Input>>())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fuels_core::types::unresolved_bytes::Data;
+
+    use super::*;
+
+    fn given_some_data() -> UnresolvedBytes {
+        UnresolvedBytes::new(vec![Data::Inline(vec![1, 2, 3])])
+    }
+
+    #[test]
+    fn data_is_set_only_after_configuring_data() {
+        let predicate = Predicate::from_code(vec![]);
+        assert!(!predicate.data_is_set());
+
+        let predicate = predicate.with_data(given_some_data());
+        assert!(predicate.data_is_set());
+    }
+
+    #[test]
+    fn try_with_data_refuses_to_override_data() -> Result<()> {
+        let predicate = Predicate::from_code(vec![]).try_with_data(given_some_data())?;
+
+        let err = predicate
+            .clone()
+            .try_with_data(UnresolvedBytes::default())
+            .expect_err("should fail because the data is already set");
+        assert!(err.to_string().contains("predicate data is already set"));
+
+        let overridden = predicate.with_data(UnresolvedBytes::default());
+        assert!(!overridden.data_is_set());
+
+        Ok(())
+    }
+
+    #[test]
+    fn clones_share_the_code_until_it_is_modified() {
+        let predicate = Predicate::from_code(vec![0; 16]);
+
+        let clone = predicate.clone();
+        assert_eq!(clone.code().as_ptr(), predicate.code().as_ptr());
+
+        let configured = clone.with_configurables(Configurables::new(vec![(0, vec![1])]));
+        assert_ne!(configured.code().as_ptr(), predicate.code().as_ptr());
+        assert_eq!(predicate.code()[0], 0);
+        assert_eq!(configured.code()[0], 1);
+    }
+
+    #[test]
+    fn data_can_be_set_from_tokens() -> Result<()> {
+        let predicate = Predicate::from_code(vec![])
+            .with_data_from_tokens(&[Token::U64(1)], EncoderConfig::default())?;
+
+        assert_eq!(predicate.data().resolve(0), 1u64.to_be_bytes());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn data_longer_than_the_limit_is_rejected() {
+        let predicate = Predicate::from_code(vec![]).with_data(given_some_data());
+
+        predicate
+            .check_data_size_against(3)
+            .expect("data fits the limit");
+        let err = predicate
+            .check_data_size_against(2)
+            .expect_err("data exceeds the limit");
+
+        assert_eq!(
+            err.to_string(),
+            "codec: predicate data is `3` bytes long but `max_predicate_data_length` is `2`"
+        );
+    }
+}
@@ -1,13 +1,23 @@
-use std::{collections::HashMap, fmt::Debug, net::SocketAddr};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    net::SocketAddr,
+    sync::{Arc, Mutex as StdMutex},
+    time::{Duration, Instant},
+};
 
+mod failover;
+mod fee_cache;
+mod gas_price_cache;
+mod polling;
 mod retry_util;
 mod retryable_client;
+mod subscription;
 mod supported_versions;
 
-#[cfg(feature = "coin-cache")]
-use std::sync::Arc;
-
 use chrono::{DateTime, Utc};
+pub use failover::FailoverPolicy;
+pub(crate) use fee_cache::FeeCacheKey;
 use fuel_core_client::client::{
     pagination::{PageDirection, PaginatedResult, PaginationRequest},
     types::{
@@ -17,33 +27,40 @@ use fuel_core_client::client::{
     },
 };
 use fuel_core_types::services::executor::{TransactionExecutionResult, TransactionExecutionStatus};
+use fuel_crypto::Hasher;
 use fuel_tx::{
-    AssetId, ConsensusParameters, Receipt, Transaction as FuelTransaction, TxId, UtxoId,
+    field::Outputs, AssetId, ConsensusParameters, Contract as FuelContract, Output, Receipt,
+    Transaction as FuelTransaction, TransactionFee, TxId, UniqueIdentifier, UtxoId,
 };
-use fuel_types::{Address, BlockHeight, Bytes32, ChainId, Nonce};
+use fuel_types::{Address, BlockHeight, Bytes32, ChainId, ContractId, Nonce};
 #[cfg(feature = "coin-cache")]
 use fuels_core::types::coin_type_id::CoinTypeId;
 use fuels_core::{
+    codec::{decode_logs_as_tokens, DecodedLog, DecoderConfig, LogId},
     constants::{
         BASE_ASSET_ID, DEFAULT_GAS_ESTIMATION_BLOCK_HORIZON, DEFAULT_GAS_ESTIMATION_TOLERANCE,
     },
     types::{
         bech32::{Bech32Address, Bech32ContractId},
-        block::Block,
+        block::{Block, FullBlock, Header},
         chain_info::ChainInfo,
         coin::Coin,
         coin_type::CoinType,
-        errors::{error, Result},
+        errors::{error, transaction::Reason, Error, Result},
         message::Message,
         message_proof::MessageProof,
         node_info::NodeInfo,
-        transaction::{Transaction, Transactions},
+        param_types::ParamType,
+        transaction::{Transaction, TransactionType, Transactions},
         transaction_builders::DryRunner,
         transaction_response::TransactionResponse,
         tx_status::TxStatus,
     },
 };
+use futures::{future::try_join_all, Stream, StreamExt};
+pub use polling::PollingStrategy;
 pub use retry_util::{Backoff, RetryConfig};
+pub use subscription::SubscriptionEvent;
 use supported_versions::{check_fuel_core_version_compatibility, VersionCompatibility};
 use tai64::Tai64;
 #[cfg(feature = "coin-cache")]
@@ -51,7 +68,9 @@ use tokio::sync::Mutex;
 
 #[cfg(feature = "coin-cache")]
 use crate::coin_cache::CoinsCache;
-use crate::provider::retryable_client::RetryableClient;
+use crate::provider::{
+    fee_cache::FeeCache, gas_price_cache::GasPriceCache, retryable_client::RetryableClient,
+};
 
 #[derive(Debug)]
 // ANCHOR: transaction_cost
@@ -63,6 +82,56 @@ pub struct TransactionCost {
 }
 // ANCHOR_END: transaction_cost
 
+/// The result of [`Provider::estimate_transaction`].
+#[derive(Debug, Clone)]
+pub struct TxEstimate<T> {
+    /// The transaction with the gas used by its predicates filled in.
+    pub tx: T,
+    /// The gas used by the predicate of each input, in the order of the inputs. `None` for
+    /// inputs without a predicate.
+    pub predicate_gas_used: Vec<Option<u64>>,
+    pub gas_price: u64,
+    pub gas_used: u64,
+    pub metered_bytes_size: u64,
+    pub total_fee: u64,
+}
+
+/// Fee data of a recent block, see [`Provider::fee_history`].
+#[derive(Debug, Clone)]
+pub struct BlockFeeInfo {
+    pub height: u32,
+    pub time: Option<DateTime<Utc>>,
+    pub transactions_count: u64,
+    /// The tips paid by the block's script transactions, in ascending order.
+    pub tips: Vec<u64>,
+    /// The gas used by the block's script transactions.
+    pub gas_used: u64,
+}
+
+impl BlockFeeInfo {
+    /// The share of `block_gas_limit` used by the block, between 0 and 1. The node doesn't
+    /// report its block gas limit, so it is the one of the chain config.
+    pub fn utilization(&self, block_gas_limit: u64) -> f64 {
+        if block_gas_limit == 0 {
+            return 0.0;
+        }
+
+        self.gas_used as f64 / block_gas_limit as f64
+    }
+
+    pub fn min_tip(&self) -> Option<u64> {
+        self.tips.first().copied()
+    }
+
+    pub fn median_tip(&self) -> Option<u64> {
+        self.tips.get(self.tips.len() / 2).copied()
+    }
+
+    pub fn max_tip(&self) -> Option<u64> {
+        self.tips.last().copied()
+    }
+}
+
 pub(crate) struct ResourceQueries {
     utxos: Vec<UtxoId>,
     messages: Vec<Nonce>,
@@ -105,6 +174,8 @@ pub struct ResourceFilter {
     pub amount: u64,
     pub excluded_utxos: Vec<UtxoId>,
     pub excluded_message_nonces: Vec<Nonce>,
+    pub min_amount: Option<u64>,
+    pub max_amount: Option<u64>,
 }
 // ANCHOR_END: resource_filter
 
@@ -121,6 +192,15 @@ impl ResourceFilter {
             self.amount,
         )
     }
+
+    fn has_amount_bounds(&self) -> bool {
+        self.min_amount.is_some() || self.max_amount.is_some()
+    }
+
+    fn is_amount_in_bounds(&self, amount: u64) -> bool {
+        self.min_amount.map_or(true, |min| amount >= min)
+            && self.max_amount.map_or(true, |max| amount <= max)
+    }
 }
 
 impl Default for ResourceFilter {
@@ -131,19 +211,51 @@ impl Default for ResourceFilter {
             amount: Default::default(),
             excluded_utxos: Default::default(),
             excluded_message_nonces: Default::default(),
+            min_amount: None,
+            max_amount: None,
         }
     }
 }
 
+/// A snapshot of the chain and the node a [`Provider`] is connected to. It is fetched when
+/// connecting and kept until [`Provider::bootstrap`] is called again.
+#[derive(Debug, Clone)]
+pub struct ProviderState {
+    pub consensus_parameters: ConsensusParameters,
+    pub node_info: NodeInfo,
+}
+
+impl ProviderState {
+    pub fn chain_id(&self) -> ChainId {
+        self.consensus_parameters.chain_id
+    }
+
+    pub fn base_asset_id(&self) -> AssetId {
+        BASE_ASSET_ID
+    }
+
+    /// Identifies the version of the consensus parameters. It is the hash of their serialized
+    /// form, so it changes whenever a network upgrade changes any of the parameters.
+    pub fn consensus_parameters_hash(&self) -> Bytes32 {
+        let serialized = serde_json::to_vec(&self.consensus_parameters)
+            .expect("consensus parameters are serializable");
+
+        Hasher::hash(serialized)
+    }
+}
+
 /// Encapsulates common client operations in the SDK.
 /// Note that you may also use `client`, which is an instance
 /// of `FuelClient`, directly, which provides a broader API.
 #[derive(Debug, Clone)]
 pub struct Provider {
     client: RetryableClient,
-    consensus_parameters: ConsensusParameters,
+    state: ProviderState,
     #[cfg(feature = "coin-cache")]
     cache: Arc<Mutex<CoinsCache>>,
+    fee_cache: Option<Arc<StdMutex<FeeCache>>>,
+    gas_price_cache: Arc<StdMutex<GasPriceCache>>,
+    polling_strategy: PollingStrategy,
 }
 
 impl Provider {
@@ -159,30 +271,97 @@ impl Provider {
     /// Connects to an existing node at the given address.
     pub async fn connect(url: impl AsRef<str>) -> Result<Provider> {
         let client = RetryableClient::new(&url, Default::default())?;
-        let consensus_parameters = client.chain_info().await?.consensus_parameters;
-        let node_info = client.node_info().await?.into();
 
-        Self::ensure_client_version_is_supported(&node_info)?;
+        Self::with_client(client).await
+    }
+
+    /// Connects to several nodes of the same chain, listed by preference. Requests go to the
+    /// first healthy node and fail over to the next one when a node can't be reached, i.e. on
+    /// connection errors and timeouts, while errors returned by a node are not retried
+    /// elsewhere. `policy` sets when a node is considered unhealthy and when it is tried again.
+    ///
+    /// Sending a transaction estimates, checks and submits it through a single node, see
+    /// [`pinned`](Self::pinned). Only the first reachable node is checked when connecting, so
+    /// all of `urls` have to point to nodes of the same chain.
+    pub async fn connect_multi(urls: &[String], policy: FailoverPolicy) -> Result<Provider> {
+        let client = RetryableClient::new_multi(urls, Default::default(), policy)?;
+
+        Self::with_client(client).await
+    }
+
+    async fn with_client(client: RetryableClient) -> Result<Provider> {
+        let state = Self::fetch_state(&client).await?;
 
         Ok(Self {
             client,
-            consensus_parameters,
+            state,
             #[cfg(feature = "coin-cache")]
             cache: Default::default(),
+            fee_cache: None,
+            gas_price_cache: Default::default(),
+            polling_strategy: Default::default(),
         })
     }
 
+    /// The url of the node the next request goes to. Only changes when connected to several
+    /// nodes, see [`connect_multi`](Self::connect_multi).
     pub fn url(&self) -> &str {
         self.client.url()
     }
 
+    /// The urls of the nodes of the provider, in order of preference, along with whether they
+    /// are currently considered healthy.
+    pub fn node_health(&self) -> Vec<(&str, bool)> {
+        self.client.node_health()
+    }
+
+    /// Returns a provider sending all of its requests to the node this one currently routes
+    /// to, without failing over, so that related reads, e.g. estimating a transaction before
+    /// submitting it, see the state of a single node. Caches and settings are shared with this
+    /// provider. Connected to a single node, this is the same as cloning the provider.
+    pub fn pinned(&self) -> Provider {
+        Provider {
+            client: self.client.pinned(),
+            ..self.clone()
+        }
+    }
+
+    /// Fetches the consensus parameters and the node info concurrently and caches them,
+    /// replacing the snapshot taken when connecting. Fails, keeping the previous snapshot, if
+    /// the node was upgraded to an unsupported version.
+    pub async fn bootstrap(&mut self) -> Result<ProviderState> {
+        let state = Self::fetch_state(&self.client).await?;
+        self.state = state.clone();
+
+        Ok(state)
+    }
+
+    /// The snapshot of the chain state taken when connecting or by the last
+    /// [`bootstrap`](Self::bootstrap).
+    pub fn state(&self) -> &ProviderState {
+        &self.state
+    }
+
+    async fn fetch_state(client: &RetryableClient) -> Result<ProviderState> {
+        let (chain_info, node_info) = tokio::try_join!(client.chain_info(), client.node_info())?;
+        let node_info: NodeInfo = node_info.into();
+
+        Self::ensure_client_version_is_supported(&node_info)?;
+
+        Ok(ProviderState {
+            consensus_parameters: chain_info.consensus_parameters,
+            node_info,
+        })
+    }
+
     /// Sends a transaction to the underlying Provider's client.
     pub async fn send_transaction_and_await_commit<T: Transaction>(
         &self,
         tx: T,
     ) -> Result<TxStatus> {
-        let tx = self.prepare_transaction_for_sending(tx).await?;
-        let tx_status = self
+        let provider = self.pinned();
+        let tx = provider.prepare_transaction_for_sending(tx).await?;
+        let tx_status = provider
             .client
             .submit_and_await_commit(&tx.clone().into())
             .await?
@@ -217,15 +396,75 @@ impl Provider {
         Ok(tx)
     }
 
+    /// Submits `tx` and waits until it is committed, but not past `deadline`. Only the wait is
+    /// bounded by the deadline, the submission itself is not. If the deadline passes first, an
+    /// `Error::Transaction(Reason::Timeout { tx_id })` is returned so that the status of the
+    /// transaction can be checked later.
+    pub async fn submit_and_await_commit_with_deadline<T: Transaction>(
+        &self,
+        tx: T,
+        deadline: Instant,
+    ) -> Result<TxStatus> {
+        let tx_id = self.send_transaction(tx).await?;
+
+        tokio::time::timeout_at(deadline.into(), self.await_transaction_commit::<T>(tx_id))
+            .await
+            .map_err(|_| Error::Transaction(Reason::Timeout { tx_id }))?
+    }
+
     pub async fn send_transaction<T: Transaction>(&self, tx: T) -> Result<TxId> {
-        let tx = self.prepare_transaction_for_sending(tx).await?;
-        self.submit(tx).await
+        let provider = self.pinned();
+        let tx = provider.prepare_transaction_for_sending(tx).await?;
+        provider.submit(tx).await
     }
 
     pub async fn await_transaction_commit<T: Transaction>(&self, id: TxId) -> Result<TxStatus> {
         Ok(self.client.await_transaction_commit(&id).await?.into())
     }
 
+    /// Subscribes to the status of the transaction `tx_id`, yielding its current status and then
+    /// every change until it is final. If the subscription drops, e.g. on a transient network
+    /// failure, it is reestablished and a [`SubscriptionEvent::Reconnected`] is yielded, so that
+    /// a status missed in the meantime can be backfilled with [`tx_status`](Self::tx_status).
+    pub fn transaction_status_updates<'a>(
+        &'a self,
+        tx_id: &'a TxId,
+    ) -> impl Stream<Item = Result<SubscriptionEvent<TxStatus>>> + 'a {
+        subscription::resubscribing(
+            move || async move {
+                let subscription = self.client.subscribe_transaction_status(tx_id).await?;
+
+                Ok::<_, Error>(
+                    subscription.map(|status| status.map(TxStatus::from).map_err(Error::from)),
+                )
+            },
+            |status| !matches!(status, TxStatus::Submitted),
+            |last, new| std::mem::discriminant(last) == std::mem::discriminant(new),
+        )
+    }
+
+    /// Same as [`await_transaction_commit`](Self::await_transaction_commit) but polls the status
+    /// of the transaction, following the [`PollingStrategy`] of the provider, instead of
+    /// subscribing to it. Useful when the node, or a proxy in front of it, doesn't support
+    /// subscriptions.
+    pub async fn poll_transaction_commit(&self, id: TxId) -> Result<TxStatus> {
+        let status = self
+            .polling_strategy
+            .poll(None, || async {
+                let status = self.tx_status(&id).await?;
+
+                Ok((!matches!(status, TxStatus::Submitted)).then_some(status))
+            })
+            .await?;
+
+        status.ok_or_else(|| {
+            error!(
+                Other,
+                "transaction `{id}` was not committed within {:?}", self.polling_strategy.timeout
+            )
+        })
+    }
+
     async fn validate_transaction<T: Transaction>(&self, tx: T) -> Result<()> {
         let tolerance = 0.0;
         let TransactionCost { gas_used, .. } = self
@@ -255,12 +494,49 @@ impl Provider {
         Ok(self.client.transaction_status(tx_id).await?.into())
     }
 
+    /// Decodes the logs of the executed transaction `tx_id` using the types in `log_types`.
+    /// Logs without a registered type are skipped. Pending transactions have no logs yet.
+    pub async fn receipts_decoded(
+        &self,
+        tx_id: &TxId,
+        log_types: &HashMap<LogId, ParamType>,
+    ) -> Result<Vec<DecodedLog>> {
+        let receipts = self.tx_status(tx_id).await?.take_receipts();
+
+        decode_logs_as_tokens(&receipts, log_types, DecoderConfig::default())
+    }
+
     pub async fn chain_info(&self) -> Result<ChainInfo> {
         Ok(self.client.chain_info().await?.into())
     }
 
     pub fn consensus_parameters(&self) -> &ConsensusParameters {
-        &self.consensus_parameters
+        &self.state.consensus_parameters
+    }
+
+    /// See [`ProviderState::consensus_parameters_hash`].
+    pub fn consensus_parameters_hash(&self) -> Bytes32 {
+        self.state.consensus_parameters_hash()
+    }
+
+    /// Fetches the consensus parameters from the node and compares them to the cached ones.
+    /// Returns `true` if they changed, e.g. because of a network upgrade, in which case the
+    /// cached ones are replaced, so a change is only reported once. The cached fees and gas
+    /// prices, computed under the old parameters, are dropped.
+    pub async fn consensus_parameters_changed(&mut self) -> Result<bool> {
+        let chain_info = self.client.chain_info().await?;
+
+        let changed = chain_info.consensus_parameters != self.state.consensus_parameters;
+        if changed {
+            self.state.consensus_parameters = chain_info.consensus_parameters;
+            self.clear_fee_cache();
+            self.gas_price_cache
+                .lock()
+                .expect("gas price cache lock poisoned")
+                .clear();
+        }
+
+        Ok(changed)
     }
 
     fn ensure_client_version_is_supported(node_info: &NodeInfo) -> Result<()> {
@@ -296,21 +572,111 @@ impl Provider {
     }
 
     pub fn chain_id(&self) -> ChainId {
-        self.consensus_parameters.chain_id
+        self.state.chain_id()
     }
 
     pub async fn node_info(&self) -> Result<NodeInfo> {
         Ok(self.client.node_info().await?.into())
     }
 
+    /// Runs an arbitrary GraphQL `query`, with the given `variables`, against the node and
+    /// returns the `data` of the response as is. Meant as an escape hatch for node features not
+    /// yet exposed by the `Provider`.
+    ///
+    /// This API is unstable: the schema depends on the version of the node and may change
+    /// without notice.
+    pub async fn query_raw(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        Ok(self.client.query_raw(query, &variables).await?)
+    }
+
     pub async fn latest_gas_price(&self) -> Result<LatestGasPrice> {
         Ok(self.client.latest_gas_price().await?)
     }
 
+    /// Returns the latest gas price. It is cached for a second, see
+    /// [`with_gas_price_cache_ttl`](Self::with_gas_price_cache_ttl), so it can be read often
+    /// without querying the node every time. The fee cache is keyed on it, so that fees are
+    /// estimated anew once it changes.
+    pub async fn current_gas_price(&self) -> Result<u64> {
+        let cached = self
+            .gas_price_cache
+            .lock()
+            .expect("gas price cache lock poisoned")
+            .get(Instant::now());
+
+        match cached {
+            Some(gas_price) => Ok(gas_price),
+            None => self.fetch_gas_price().await,
+        }
+    }
+
+    /// Yields the current gas price right away and then every time it changes, checking it
+    /// every `interval`. Errors are yielded as well, without ending the stream. Every check
+    /// refreshes the price read by [`current_gas_price`](Self::current_gas_price), and a change
+    /// drops the cached estimates used to fund transactions.
+    pub fn gas_price_updates(&self, interval: Duration) -> impl Stream<Item = Result<u64>> {
+        let state = (self.clone(), None, false);
+
+        futures::stream::unfold(state, move |(provider, last, polled)| async move {
+            let mut polled = polled;
+            loop {
+                if polled {
+                    tokio::time::sleep(interval).await;
+                }
+                polled = true;
+
+                match provider.fetch_gas_price().await {
+                    Ok(gas_price) if last == Some(gas_price) => continue,
+                    Ok(gas_price) => {
+                        return Some((Ok(gas_price), (provider, Some(gas_price), polled)))
+                    }
+                    Err(err) => return Some((Err(err), (provider, last, polled))),
+                }
+            }
+        })
+    }
+
+    async fn fetch_gas_price(&self) -> Result<u64> {
+        let gas_price = self.latest_gas_price().await?.gas_price;
+        self.gas_price_cache
+            .lock()
+            .expect("gas price cache lock poisoned")
+            .insert(gas_price, Instant::now());
+
+        Ok(gas_price)
+    }
+
     pub async fn estimate_gas_price(&self, block_horizon: u32) -> Result<EstimateGasPrice> {
         Ok(self.client.estimate_gas_price(block_horizon).await?)
     }
 
+    /// The gas price estimated for `block_horizon` blocks ahead, cached like
+    /// [`current_gas_price`](Self::current_gas_price) and dropped once it changes. Funding,
+    /// fee estimation and [`estimate_transaction_cost`](Self::estimate_transaction_cost) read
+    /// the gas price through it.
+    async fn cached_gas_price_estimate(&self, block_horizon: u32) -> Result<u64> {
+        let cached = self
+            .gas_price_cache
+            .lock()
+            .expect("gas price cache lock poisoned")
+            .get_estimate(block_horizon, Instant::now());
+        if let Some(gas_price) = cached {
+            return Ok(gas_price);
+        }
+
+        let gas_price = self.estimate_gas_price(block_horizon).await?.gas_price;
+        self.gas_price_cache
+            .lock()
+            .expect("gas price cache lock poisoned")
+            .insert_estimate(block_horizon, gas_price, Instant::now());
+
+        Ok(gas_price)
+    }
+
     pub async fn dry_run(&self, tx: impl Transaction) -> Result<TxStatus> {
         let [(_, tx_status)] = self
             .client
@@ -383,6 +749,18 @@ impl Provider {
             .collect())
     }
 
+    /// Gets the coin created by the output `utxo_id`, or `None` if it was already spent or
+    /// never existed.
+    pub async fn coin(&self, utxo_id: &UtxoId) -> Result<Option<Coin>> {
+        Ok(self.client.coin(utxo_id).await?.map(Into::into))
+    }
+
+    /// Same as [`coin`](Self::coin) for every id in `utxo_ids`, in the same order. The coins
+    /// are queried concurrently.
+    pub async fn coins(&self, utxo_ids: &[UtxoId]) -> Result<Vec<Option<Coin>>> {
+        try_join_all(utxo_ids.iter().map(|utxo_id| self.coin(utxo_id))).await
+    }
+
     /// Gets all unspent coins owned by address `from`, with asset ID `asset_id`.
     pub async fn get_coins(&self, from: &Bech32Address, asset_id: AssetId) -> Result<Vec<Coin>> {
         let mut coins: Vec<Coin> = vec![];
@@ -413,7 +791,43 @@ impl Provider {
         Ok(coins)
     }
 
-    async fn request_coins_to_spend(&self, filter: ResourceFilter) -> Result<Vec<CoinType>> {
+    /// The number of unspent coins of `asset_id` owned by `owner`, to measure how fragmented
+    /// its balance is, e.g. to decide when to consolidate it. The node has no query counting
+    /// coins, so they are still paged through, but only counted instead of being collected.
+    pub async fn coin_count(&self, owner: &Bech32Address, asset_id: AssetId) -> Result<u64> {
+        let mut count = 0;
+
+        let mut cursor = None;
+
+        loop {
+            let res = self
+                .client
+                .coins(
+                    &owner.into(),
+                    Some(&asset_id),
+                    PaginationRequest {
+                        cursor: cursor.clone(),
+                        results: 100,
+                        direction: PageDirection::Forward,
+                    },
+                )
+                .await?;
+
+            if res.results.is_empty() {
+                break;
+            }
+            count += res.results.len() as u64;
+            cursor = res.cursor;
+        }
+
+        Ok(count)
+    }
+
+    async fn request_coins_to_spend(&self, mut filter: ResourceFilter) -> Result<Vec<CoinType>> {
+        if filter.has_amount_bounds() {
+            self.extend_filter_with_out_of_bounds(&mut filter).await?;
+        }
+
         let queries = filter.resource_queries();
 
         let res = self
@@ -432,6 +846,30 @@ impl Provider {
         Ok(res)
     }
 
+    /// The node cannot filter resources by their amount, so the ones outside of the filter's
+    /// bounds are excluded explicitly.
+    async fn extend_filter_with_out_of_bounds(&self, filter: &mut ResourceFilter) -> Result<()> {
+        let coins = self.get_coins(&filter.from, filter.asset_id).await?;
+        filter.excluded_utxos.extend(
+            coins
+                .into_iter()
+                .filter(|coin| !filter.is_amount_in_bounds(coin.amount))
+                .map(|coin| coin.utxo_id),
+        );
+
+        if filter.asset_id == BASE_ASSET_ID {
+            let messages = self.get_messages(&filter.from).await?;
+            filter.excluded_message_nonces.extend(
+                messages
+                    .into_iter()
+                    .filter(|message| !filter.is_amount_in_bounds(message.amount))
+                    .map(|message| message.nonce),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Get some spendable coins of asset `asset_id` for address `from` that add up at least to
     /// amount `amount`. The returned coins (UTXOs) are actual coins that can be spent. The number
     /// of coins (UXTOs) is optimized to prevent dust accumulation.
@@ -454,6 +892,33 @@ impl Provider {
         self.request_coins_to_spend(filter).await
     }
 
+    /// Same as [`get_spendable_resources`](Self::get_spendable_resources) for several owners,
+    /// e.g. many deposit predicates being swept. The requests are sent concurrently and the
+    /// results are keyed by `filter.from`. Fails if two filters have the same owner, as they
+    /// could return the same resources.
+    pub async fn get_spendable_resources_by_owner(
+        &self,
+        filters: Vec<ResourceFilter>,
+    ) -> Result<HashMap<Bech32Address, Vec<CoinType>>> {
+        let mut owners = HashSet::new();
+        if let Some(filter) = filters.iter().find(|filter| !owners.insert(&filter.from)) {
+            return Err(error!(
+                Other,
+                "more than one resource filter for owner `{}`", filter.from
+            ));
+        }
+
+        let owners: Vec<_> = filters.iter().map(|filter| filter.from.clone()).collect();
+        let resources = try_join_all(
+            filters
+                .into_iter()
+                .map(|filter| self.get_spendable_resources(filter)),
+        )
+        .await?;
+
+        Ok(owners.into_iter().zip(resources).collect())
+    }
+
     #[cfg(feature = "coin-cache")]
     async fn extend_filter_with_cached(&self, filter: &mut ResourceFilter) {
         let mut cache = self.cache.lock().await;
@@ -569,10 +1034,119 @@ impl Provider {
         Ok(balances)
     }
 
+    /// Returns the bytecode of the deployed contract `contract_id`, or `None` if there is no such
+    /// contract.
+    pub async fn contract_bytecode(
+        &self,
+        contract_id: &Bech32ContractId,
+    ) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .client
+            .contract(&contract_id.into())
+            .await?
+            .map(|contract| contract.bytecode))
+    }
+
+    /// Returns the code root of the deployed contract `contract_id`, or `None` if there is no such
+    /// contract. Compare it with `Contract::code_root` to check that a local build matches what
+    /// was deployed.
+    pub async fn contract_root(&self, contract_id: &Bech32ContractId) -> Result<Option<Bytes32>> {
+        Ok(self
+            .contract_bytecode(contract_id)
+            .await?
+            .map(|bytecode| FuelContract::from(bytecode).root()))
+    }
+
+    /// Returns the id of the transaction that deployed the contract `contract_id` together with
+    /// the header of the block including it, or `None` if there is no such contract or it is
+    /// part of the genesis state. The node doesn't index contracts by their deployment, so the
+    /// transactions of the chain are scanned starting with the oldest one. At most
+    /// `max_transactions` of them are scanned, and a deployment not found among them is an
+    /// error rather than `None`.
+    pub async fn contract_deployment(
+        &self,
+        contract_id: &Bech32ContractId,
+        max_transactions: usize,
+    ) -> Result<Option<(TxId, Header)>> {
+        const PAGE_SIZE: usize = 100;
+
+        if self.contract_bytecode(contract_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let contract_id = ContractId::from(contract_id);
+        let chain_id = self.chain_id();
+        let mut cursor = None;
+        let mut scanned = 0;
+        loop {
+            let remaining = max_transactions.saturating_sub(scanned);
+            if remaining == 0 {
+                return Err(error!(
+                    Provider,
+                    "deployment of contract `{contract_id}` not found within the first `{max_transactions}` transactions"
+                ));
+            }
+
+            let page = self
+                .client
+                .transactions(PaginationRequest {
+                    cursor,
+                    results: remaining.min(PAGE_SIZE) as i32,
+                    direction: PageDirection::Forward,
+                })
+                .await?;
+            scanned += page.results.len();
+
+            for response in page.results {
+                let FuelTransaction::Create(create) = &response.transaction else {
+                    continue;
+                };
+                let deploys_contract = create.outputs().iter().any(|output| {
+                    matches!(output, Output::ContractCreated { contract_id: id, .. } if *id == contract_id)
+                });
+                if !deploys_contract {
+                    continue;
+                }
+
+                let tx_id = response.transaction.id(&chain_id);
+                let Some(height) = TransactionResponse::from(response).block_height else {
+                    continue;
+                };
+
+                return Ok(self
+                    .block_by_height(height)
+                    .await?
+                    .map(|block| (tx_id, block.header)));
+            }
+
+            if !page.has_next_page {
+                return Ok(None);
+            }
+            cursor = page.cursor;
+        }
+    }
+
     pub async fn get_transaction_by_id(&self, tx_id: &TxId) -> Result<Option<TransactionResponse>> {
         Ok(self.client.transaction(tx_id).await?.map(Into::into))
     }
 
+    /// Returns the header of the block that included the transaction `tx_id`, or `None` if the
+    /// transaction is unknown or not yet included in a block.
+    pub async fn transaction_block(&self, tx_id: &TxId) -> Result<Option<Header>> {
+        let Some(height) = self
+            .get_transaction_by_id(tx_id)
+            .await?
+            .and_then(|response| response.block_height)
+        else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .block_by_height(height)
+            .await?
+            .map(|block| block.header))
+    }
+
     pub async fn get_transactions(
         &self,
         request: PaginationRequest<String>,
@@ -587,7 +1161,9 @@ impl Provider {
         })
     }
 
-    // Get transaction(s) by owner
+    /// Gets the transactions involving `owner`, e.g. spending its resources or sending it
+    /// assets, one page at a time. Fails with `Failure::Unsupported` if the node doesn't index
+    /// transactions by owner.
     pub async fn get_transactions_by_owner(
         &self,
         owner: &Bech32Address,
@@ -606,6 +1182,28 @@ impl Provider {
         })
     }
 
+    /// Gets the ids of the transactions involving `owner`, one page at a time. See
+    /// [`Provider::get_transactions_by_owner`], including for nodes without the owner index.
+    pub async fn transactions_by_owner(
+        &self,
+        owner: &Bech32Address,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<TxId, String>> {
+        let chain_id = self.chain_id();
+        let pr = self.get_transactions_by_owner(owner, request).await?;
+
+        Ok(PaginatedResult {
+            cursor: pr.cursor,
+            results: pr
+                .results
+                .into_iter()
+                .map(|response| response.transaction.id(chain_id))
+                .collect(),
+            has_next_page: pr.has_next_page,
+            has_previous_page: pr.has_previous_page,
+        })
+    }
+
     pub async fn latest_block_height(&self) -> Result<u32> {
         Ok(self.chain_info().await?.latest_block.header.height)
     }
@@ -614,6 +1212,14 @@ impl Provider {
         Ok(self.chain_info().await?.latest_block.header.time)
     }
 
+    /// The header of the latest block that can no longer be reverted. Fuel nodes produce
+    /// blocks through a single authority, so they don't expose a separate finality signal and
+    /// every block is final once produced. This returns the header of the latest block, and
+    /// will only differ from it if the node starts distinguishing finalized blocks.
+    pub async fn latest_finalized_block(&self) -> Result<Header> {
+        Ok(self.chain_info().await?.latest_block.header)
+    }
+
     pub async fn produce_blocks(
         &self,
         blocks_to_produce: u32,
@@ -636,6 +1242,95 @@ impl Provider {
         Ok(self.client.block_by_height(height).await?.map(Into::into))
     }
 
+    /// Same as `block` but also fetches all of the block's transactions.
+    pub async fn block_with_transactions(&self, block_id: &Bytes32) -> Result<Option<FullBlock>> {
+        match self.block(block_id).await? {
+            Some(block) => Ok(Some(self.fetch_block_transactions(block).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Same as `block_by_height` but also fetches all of the block's transactions.
+    pub async fn block_by_height_with_transactions(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<FullBlock>> {
+        match self.block_by_height(height).await? {
+            Some(block) => Ok(Some(self.fetch_block_transactions(block).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_block_transactions(&self, block: Block) -> Result<FullBlock> {
+        let transactions = try_join_all(block.transactions.iter().map(|tx_id| async move {
+            self.get_transaction_by_id(tx_id).await?.ok_or_else(|| {
+                error!(
+                    Provider,
+                    "transaction `{tx_id}` from block `{}` not found", block.id
+                )
+            })
+        }))
+        .await?;
+
+        Ok(FullBlock {
+            id: block.id,
+            header: block.header,
+            transactions,
+        })
+    }
+
+    /// Returns the fee data of the latest `block_count` blocks, newest first. The node doesn't
+    /// keep the gas price of past blocks, so the history is made of the tips paid and the gas
+    /// used in each block. Combine it with `latest_gas_price` or `estimate_gas_price` to choose
+    /// a tip.
+    pub async fn fee_history(&self, block_count: u32) -> Result<Vec<BlockFeeInfo>> {
+        let results = i32::try_from(block_count).map_err(|_| {
+            error!(
+                Provider,
+                "cannot fetch the fee history of `{block_count}` blocks, the maximum is `{}`",
+                i32::MAX
+            )
+        })?;
+        let request = PaginationRequest {
+            cursor: None,
+            results,
+            direction: PageDirection::Backward,
+        };
+
+        let blocks = self.get_blocks(request).await?.results;
+        let blocks = try_join_all(
+            blocks
+                .into_iter()
+                .map(|block| self.fetch_block_transactions(block)),
+        )
+        .await?;
+
+        let history = blocks
+            .into_iter()
+            .map(|block| {
+                let mut tips = vec![];
+                let mut gas_used = 0;
+                for response in block.transactions {
+                    if let TransactionType::Script(tx) = &response.transaction {
+                        tips.push(tx.tip());
+                        gas_used += self.get_gas_used(&response.status.take_receipts());
+                    }
+                }
+                tips.sort_unstable();
+
+                BlockFeeInfo {
+                    height: block.header.height,
+                    time: block.header.time,
+                    transactions_count: block.header.transactions_count,
+                    tips,
+                    gas_used,
+                }
+            })
+            .collect();
+
+        Ok(history)
+    }
+
     // - Get block(s)
     pub async fn get_blocks(
         &self,
@@ -660,7 +1355,7 @@ impl Provider {
         let block_horizon = block_horizon.unwrap_or(DEFAULT_GAS_ESTIMATION_BLOCK_HORIZON);
         let tolerance = tolerance.unwrap_or(DEFAULT_GAS_ESTIMATION_TOLERANCE);
 
-        let EstimateGasPrice { gas_price, .. } = self.estimate_gas_price(block_horizon).await?;
+        let gas_price = self.cached_gas_price_estimate(block_horizon).await?;
 
         let gas_used = self
             .get_gas_used_with_tolerance(tx.clone(), tolerance)
@@ -668,7 +1363,7 @@ impl Provider {
 
         let transaction_fee = tx
             .clone()
-            .fee_checked_from_tx(&self.consensus_parameters, gas_price)
+            .fee_checked_from_tx(&self.state.consensus_parameters, gas_price)
             .expect("Error calculating TransactionFee");
 
         Ok(TransactionCost {
@@ -679,6 +1374,46 @@ impl Provider {
         })
     }
 
+    /// Estimates the predicates of `tx`, checks it against the consensus parameters and
+    /// computes its cost in one go, the node being queried concurrently for the gas price, the
+    /// block height and the gas used. Fails if the estimated transaction isn't valid, e.g. if
+    /// it exceeds the gas or size limits, so `tx` should already be signed.
+    pub async fn estimate_transaction<T: Transaction>(&self, mut tx: T) -> Result<TxEstimate<T>> {
+        let consensus_parameters = &self.state.consensus_parameters;
+
+        if tx.is_using_predicates() {
+            tx.estimate_predicates(consensus_parameters)?;
+        }
+
+        let provider = self.pinned();
+        let (gas_price, block_height, gas_used) = tokio::try_join!(
+            provider.cached_gas_price_estimate(DEFAULT_GAS_ESTIMATION_BLOCK_HORIZON),
+            provider.latest_block_height(),
+            provider.get_gas_used_with_tolerance(tx.clone(), DEFAULT_GAS_ESTIMATION_TOLERANCE),
+        )?;
+
+        tx.check(block_height, consensus_parameters)?;
+
+        let total_fee = tx
+            .fee_checked_from_tx(consensus_parameters, gas_price)
+            .ok_or_else(|| error!(Provider, "fee calculation overflowed"))?
+            .max_fee();
+        let predicate_gas_used = tx
+            .inputs()
+            .iter()
+            .map(|input| input.predicate_gas_used())
+            .collect();
+
+        Ok(TxEstimate {
+            metered_bytes_size: tx.metered_bytes_size() as u64,
+            tx,
+            predicate_gas_used,
+            gas_price,
+            gas_used,
+            total_fee,
+        })
+    }
+
     // Increase estimated gas by the provided tolerance
     async fn get_gas_used_with_tolerance<T: Transaction>(
         &self,
@@ -746,6 +1481,77 @@ impl Provider {
 
         self
     }
+
+    /// Fails every request to the node, including the ones waiting for a transaction to be
+    /// committed, that takes longer than `timeout` with an `Error::Request(Failure::Timeout)`.
+    /// Each retry attempt gets its own `timeout`.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.client.set_request_timeout(timeout);
+
+        self
+    }
+
+    /// Sets how the provider, and the accounts using it, poll the node while waiting, e.g. in
+    /// [`poll_transaction_commit`](Self::poll_transaction_commit).
+    pub fn with_polling_strategy(mut self, polling_strategy: PollingStrategy) -> Self {
+        self.polling_strategy = polling_strategy;
+
+        self
+    }
+
+    pub fn polling_strategy(&self) -> &PollingStrategy {
+        &self.polling_strategy
+    }
+
+    /// Sets how long [`current_gas_price`](Self::current_gas_price) reuses a fetched gas price.
+    /// A zero `ttl` disables caching. The cache is shared between clones of this `Provider`.
+    pub fn with_gas_price_cache_ttl(self, ttl: Duration) -> Self {
+        self.gas_price_cache
+            .lock()
+            .expect("gas price cache lock poisoned")
+            .set_ttl(ttl);
+
+        self
+    }
+
+    /// Enables reusing the estimated fee of transactions that have the same
+    /// [`fee_fingerprint`](fuels_core::types::transaction_builders::TransactionBuilder::fee_fingerprint)
+    /// and gas price as a previously estimated one, i.e. that only differ in the coins they
+    /// spend. The cache is shared between clones of this `Provider`.
+    pub fn with_fee_cache(mut self) -> Self {
+        self.fee_cache = Some(Default::default());
+
+        self
+    }
+
+    pub fn clear_fee_cache(&self) {
+        if let Some(fee_cache) = &self.fee_cache {
+            fee_cache.lock().expect("fee cache lock poisoned").clear();
+        }
+    }
+
+    pub(crate) fn is_fee_cache_enabled(&self) -> bool {
+        self.fee_cache.is_some()
+    }
+
+    pub(crate) fn cached_fee(&self, key: &FeeCacheKey) -> Option<TransactionFee> {
+        self.fee_cache.as_ref().and_then(|fee_cache| {
+            fee_cache
+                .lock()
+                .expect("fee cache lock poisoned")
+                .get(&self.state.consensus_parameters, key)
+        })
+    }
+
+    pub(crate) fn cache_fee(&self, key: FeeCacheKey, fee: TransactionFee) {
+        if let Some(fee_cache) = &self.fee_cache {
+            fee_cache.lock().expect("fee cache lock poisoned").insert(
+                &self.state.consensus_parameters,
+                key,
+                fee,
+            );
+        }
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
@@ -764,7 +1570,7 @@ impl DryRunner for Provider {
     }
 
     async fn estimate_gas_price(&self, block_horizon: u32) -> Result<u64> {
-        Ok(self.estimate_gas_price(block_horizon).await?.gas_price)
+        self.cached_gas_price_estimate(block_horizon).await
     }
 
     fn consensus_parameters(&self) -> &ConsensusParameters {
@@ -0,0 +1,164 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// How a [`Provider`](crate::provider::Provider) connected to several nodes, see
+/// [`Provider::connect_multi`](crate::provider::Provider::connect_multi), routes requests.
+#[derive(Debug, Clone)]
+pub struct FailoverPolicy {
+    /// The number of consecutive requests failing with a connection error or a timeout after
+    /// which a node is considered unhealthy and skipped.
+    pub max_failures: u32,
+    /// How long an unhealthy node is skipped before requests are routed to it again.
+    pub recheck_after: Duration,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        Self {
+            max_failures: 3,
+            recheck_after: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct NodeHealth {
+    failures: u32,
+    unhealthy_since: Option<Instant>,
+}
+
+/// Tracks the health of the nodes of a provider, shared by its clones, and picks the node the
+/// next request goes to.
+#[derive(Debug, Clone)]
+pub(crate) struct NodeRouter {
+    policy: FailoverPolicy,
+    health: Arc<Mutex<Vec<NodeHealth>>>,
+}
+
+impl NodeRouter {
+    pub(crate) fn new(node_count: usize, policy: FailoverPolicy) -> Self {
+        Self {
+            policy,
+            health: Arc::new(Mutex::new(vec![NodeHealth::default(); node_count])),
+        }
+    }
+
+    /// The first node, in order of preference, that is healthy or due for a recheck. If there
+    /// is none, the node that has been unhealthy the longest.
+    pub(crate) fn select(&self) -> usize {
+        self.select_untried(&[])
+            .expect("there is at least one node and none was tried")
+    }
+
+    /// Same as [`select`](Self::select) but skips the nodes in `tried`, e.g. those a request
+    /// already failed on. `None` once every node was tried.
+    pub(crate) fn select_untried(&self, tried: &[usize]) -> Option<usize> {
+        let health = self.health.lock().expect("health lock is not poisoned");
+        let untried = health
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !tried.contains(index));
+
+        let available = untried.clone().find(|(_, node)| {
+            node.unhealthy_since
+                .map_or(true, |since| since.elapsed() >= self.policy.recheck_after)
+        });
+
+        available
+            .or_else(|| untried.min_by_key(|(_, node)| node.unhealthy_since))
+            .map(|(index, _)| index)
+    }
+
+    /// Records the outcome of a request to `node`. Only connection errors and timeouts count
+    /// as failures, as other errors come from a node that is up.
+    pub(crate) fn record(&self, node: usize, failed: bool) {
+        let mut health = self.health.lock().expect("health lock is not poisoned");
+        let node = &mut health[node];
+
+        if !failed {
+            *node = NodeHealth::default();
+            return;
+        }
+
+        node.failures = node.failures.saturating_add(1);
+        if node.failures >= self.policy.max_failures {
+            // Also restarts the wait of a node failing its recheck
+            node.unhealthy_since = Some(Instant::now());
+        }
+    }
+
+    pub(crate) fn is_healthy(&self, node: usize) -> bool {
+        self.health.lock().expect("health lock is not poisoned")[node]
+            .unhealthy_since
+            .is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router(recheck_after: Duration) -> NodeRouter {
+        NodeRouter::new(
+            3,
+            FailoverPolicy {
+                max_failures: 2,
+                recheck_after,
+            },
+        )
+    }
+
+    #[test]
+    fn unhealthy_nodes_are_skipped() {
+        let router = router(Duration::from_secs(60));
+
+        router.record(0, true);
+        assert_eq!(router.select(), 0);
+
+        router.record(0, true);
+        assert!(!router.is_healthy(0));
+        assert_eq!(router.select(), 1);
+
+        router.record(1, true);
+        router.record(1, false);
+        assert!(router.is_healthy(1));
+        assert_eq!(router.select(), 1);
+    }
+
+    #[test]
+    fn unhealthy_nodes_are_rechecked() {
+        let router = router(Duration::ZERO);
+
+        router.record(0, true);
+        router.record(0, true);
+        assert!(!router.is_healthy(0));
+
+        assert_eq!(router.select(), 0);
+    }
+
+    #[test]
+    fn the_longest_unhealthy_node_is_used_when_none_is_healthy() {
+        let router = router(Duration::from_secs(60));
+
+        for node in [1, 2, 0] {
+            router.record(node, true);
+            router.record(node, true);
+        }
+
+        assert_eq!(router.select(), 1);
+    }
+
+    #[test]
+    fn tried_nodes_are_skipped_even_if_healthy() {
+        let router = NodeRouter::new(3, FailoverPolicy::default());
+
+        router.record(0, true);
+        assert!(router.is_healthy(0));
+
+        assert_eq!(router.select_untried(&[0]), Some(1));
+        assert_eq!(router.select_untried(&[0, 1]), Some(2));
+        assert_eq!(router.select_untried(&[0, 1, 2]), None);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use fuel_tx::{ConsensusParameters, TransactionFee};
+
+/// Identifies the fee of a transaction, see
+/// [`TransactionBuilder::fee_fingerprint`](fuels_core::types::transaction_builders::TransactionBuilder::fee_fingerprint).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct FeeCacheKey {
+    fingerprint: u64,
+    block_horizon: u32,
+    gas_price: u64,
+}
+
+impl FeeCacheKey {
+    pub(crate) fn new(fingerprint: u64, block_horizon: u32, gas_price: u64) -> Self {
+        Self {
+            fingerprint,
+            block_horizon,
+            gas_price,
+        }
+    }
+}
+
+/// Remembers the fees of previously estimated transactions. Entries are only valid for the
+/// consensus parameters they were computed with and are dropped once those change.
+#[derive(Debug, Default)]
+pub(crate) struct FeeCache {
+    consensus_parameters: Option<ConsensusParameters>,
+    fees: HashMap<FeeCacheKey, TransactionFee>,
+}
+
+impl FeeCache {
+    pub(crate) fn get(
+        &mut self,
+        consensus_parameters: &ConsensusParameters,
+        key: &FeeCacheKey,
+    ) -> Option<TransactionFee> {
+        self.invalidate_if_changed(consensus_parameters);
+
+        self.fees.get(key).cloned()
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        consensus_parameters: &ConsensusParameters,
+        key: FeeCacheKey,
+        fee: TransactionFee,
+    ) {
+        self.invalidate_if_changed(consensus_parameters);
+
+        self.fees.insert(key, fee);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.fees.clear();
+    }
+
+    fn invalidate_if_changed(&mut self, consensus_parameters: &ConsensusParameters) {
+        if self.consensus_parameters.as_ref() != Some(consensus_parameters) {
+            self.fees.clear();
+            self.consensus_parameters = Some(consensus_parameters.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn given_a_key(fingerprint: u64) -> FeeCacheKey {
+        FeeCacheKey::new(fingerprint, 1, 1)
+    }
+
+    #[test]
+    fn fee_is_reused_for_the_same_fingerprint() {
+        let mut cache = FeeCache::default();
+        let consensus_parameters = ConsensusParameters::default();
+        let fee = TransactionFee::default();
+
+        cache.insert(&consensus_parameters, given_a_key(1), fee);
+
+        assert_eq!(cache.get(&consensus_parameters, &given_a_key(1)), Some(fee));
+        assert_eq!(cache.get(&consensus_parameters, &given_a_key(2)), None);
+    }
+
+    #[test]
+    fn cache_is_invalidated_when_consensus_parameters_change() {
+        let mut cache = FeeCache::default();
+        let consensus_parameters = ConsensusParameters::default();
+        cache.insert(
+            &consensus_parameters,
+            given_a_key(1),
+            TransactionFee::default(),
+        );
+
+        let mut changed_parameters = consensus_parameters.clone();
+        changed_parameters.chain_id = 42.into();
+
+        assert_eq!(cache.get(&changed_parameters, &given_a_key(1)), None);
+        assert_eq!(cache.get(&consensus_parameters, &given_a_key(1)), None);
+    }
+
+    #[test]
+    fn cache_can_be_cleared() {
+        let mut cache = FeeCache::default();
+        let consensus_parameters = ConsensusParameters::default();
+        cache.insert(
+            &consensus_parameters,
+            given_a_key(1),
+            TransactionFee::default(),
+        );
+
+        cache.clear();
+
+        assert_eq!(cache.get(&consensus_parameters, &given_a_key(1)), None);
+    }
+}
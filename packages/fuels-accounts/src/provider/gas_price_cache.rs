@@ -0,0 +1,127 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Remembers the latest gas price, and the estimates for the block horizons asked for, for
+/// `ttl`, so that reading them repeatedly, e.g. when funding many transactions, doesn't query
+/// the node every time. A change of the latest price drops the estimates, which were made
+/// before it.
+#[derive(Debug)]
+pub(crate) struct GasPriceCache {
+    ttl: Duration,
+    latest: Option<(u64, Instant)>,
+    estimates: HashMap<u32, (u64, Instant)>,
+}
+
+impl Default for GasPriceCache {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(1),
+            latest: None,
+            estimates: HashMap::new(),
+        }
+    }
+}
+
+impl GasPriceCache {
+    pub(crate) fn get(&self, now: Instant) -> Option<u64> {
+        self.fresh(self.latest, now)
+    }
+
+    pub(crate) fn insert(&mut self, gas_price: u64, now: Instant) {
+        if self.latest.map(|(latest, _)| latest) != Some(gas_price) {
+            self.estimates.clear();
+        }
+        self.latest = Some((gas_price, now));
+    }
+
+    pub(crate) fn get_estimate(&self, block_horizon: u32, now: Instant) -> Option<u64> {
+        self.fresh(self.estimates.get(&block_horizon).copied(), now)
+    }
+
+    pub(crate) fn insert_estimate(&mut self, block_horizon: u32, gas_price: u64, now: Instant) {
+        self.estimates.insert(block_horizon, (gas_price, now));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.latest = None;
+        self.estimates.clear();
+    }
+
+    pub(crate) fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+
+    fn fresh(&self, entry: Option<(u64, Instant)>, now: Instant) -> Option<u64> {
+        entry
+            .filter(|(_, fetched_at)| now.saturating_duration_since(*fetched_at) < self.ttl)
+            .map(|(gas_price, _)| gas_price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_price_expires_after_the_ttl() {
+        let mut cache = GasPriceCache::default();
+        cache.set_ttl(Duration::from_secs(2));
+        let now = Instant::now();
+
+        assert_eq!(cache.get(now), None);
+
+        cache.insert(7, now);
+        cache.insert_estimate(10, 8, now);
+
+        assert_eq!(cache.get(now + Duration::from_secs(1)), Some(7));
+        assert_eq!(
+            cache.get_estimate(10, now + Duration::from_secs(1)),
+            Some(8)
+        );
+        assert_eq!(cache.get(now + Duration::from_secs(2)), None);
+        assert_eq!(cache.get_estimate(10, now + Duration::from_secs(2)), None);
+    }
+
+    #[test]
+    fn zero_ttl_disables_caching() {
+        let mut cache = GasPriceCache::default();
+        cache.set_ttl(Duration::ZERO);
+        let now = Instant::now();
+
+        cache.insert(7, now);
+
+        assert_eq!(cache.get(now), None);
+    }
+
+    #[test]
+    fn a_new_latest_price_drops_the_estimates() {
+        let mut cache = GasPriceCache::default();
+        let now = Instant::now();
+
+        cache.insert(7, now);
+        cache.insert_estimate(10, 8, now);
+
+        cache.insert(7, now);
+        assert_eq!(cache.get_estimate(10, now), Some(8));
+
+        cache.insert(9, now);
+        assert_eq!(cache.get(now), Some(9));
+        assert_eq!(cache.get_estimate(10, now), None);
+    }
+
+    #[test]
+    fn clearing_drops_the_latest_price_and_the_estimates() {
+        let mut cache = GasPriceCache::default();
+        let now = Instant::now();
+
+        cache.insert(7, now);
+        cache.insert_estimate(10, 8, now);
+
+        cache.clear();
+
+        assert_eq!(cache.get(now), None);
+        assert_eq!(cache.get_estimate(10, now), None);
+    }
+}
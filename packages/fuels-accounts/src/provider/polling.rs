@@ -0,0 +1,116 @@
+use std::{future::Future, time::Duration};
+
+use fuels_core::types::errors::Result;
+
+/// Controls how the `Provider` polls the node while waiting for something to happen, e.g. a
+/// message becoming spendable. The delay between two polls starts at `initial_delay` and is
+/// multiplied by `backoff` after every poll, up to `max_delay`. Waiting is given up after
+/// `timeout`, unless the wait was given its own timeout.
+// ANCHOR: polling_strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollingStrategy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub backoff: f64,
+    pub timeout: Duration,
+}
+// ANCHOR_END: polling_strategy
+
+impl Default for PollingStrategy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+            backoff: 2.0,
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl PollingStrategy {
+    /// The delay following the `attempt`-th poll, starting from `0`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self
+            .backoff
+            .max(1.0)
+            .powi(i32::try_from(attempt).unwrap_or(i32::MAX));
+
+        Duration::try_from_secs_f64(self.initial_delay.as_secs_f64() * factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// Calls `poll` until it returns a value, sleeping between calls. Returns `None` if no value
+    /// was returned within `timeout`, or within the strategy's timeout if `timeout` is `None`.
+    /// Errors returned by `poll` stop the polling.
+    pub(crate) async fn poll<T, Fut>(
+        &self,
+        timeout: Option<Duration>,
+        mut poll: impl FnMut() -> Fut,
+    ) -> Result<Option<T>>
+    where
+        Fut: Future<Output = Result<Option<T>>>,
+    {
+        let polling = async {
+            let mut attempt = 0;
+            loop {
+                if let Some(value) = poll().await? {
+                    return Ok(value);
+                }
+
+                tokio::time::sleep(self.delay(attempt)).await;
+                attempt = attempt.saturating_add(1);
+            }
+        };
+
+        match tokio::time::timeout(timeout.unwrap_or(self.timeout), polling).await {
+            Ok(result) => result.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delays_grow_up_to_the_maximum() {
+        let strategy = PollingStrategy {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            backoff: 2.0,
+            ..Default::default()
+        };
+
+        let delays: Vec<_> = (0..5).map(|attempt| strategy.delay(attempt)).collect();
+
+        let expected = [100, 200, 400, 500, 500].map(Duration::from_millis);
+        assert_eq!(delays, expected);
+        assert_eq!(strategy.delay(u32::MAX), strategy.max_delay);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polling_stops_once_a_value_is_returned_or_on_timeout() -> Result<()> {
+        let strategy = PollingStrategy {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(100),
+            backoff: 1.0,
+            timeout: Duration::from_secs(1),
+        };
+
+        let mut polls = 0;
+        let value = strategy
+            .poll(None, || {
+                polls += 1;
+                let value = (polls == 3).then_some(polls);
+                async move { Ok(value) }
+            })
+            .await?;
+        assert_eq!(value, Some(3));
+
+        let value: Option<()> = strategy.poll(None, || async { Ok(None) }).await?;
+        assert_eq!(value, None);
+
+        Ok(())
+    }
+}
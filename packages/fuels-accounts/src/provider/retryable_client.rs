@@ -1,114 +1,296 @@
-use std::{future::Future, io};
+use std::{error::Error as StdError, future::Future, io, time::Duration};
 
+use cynic::Operation;
 use fuel_core_client::client::{
     pagination::{PaginatedResult, PaginationRequest},
     types::{
         gas_price::{EstimateGasPrice, LatestGasPrice},
         primitives::{BlockId, TransactionId},
-        Balance, Block, ChainInfo, Coin, CoinType, ContractBalance, Message, MessageProof,
-        NodeInfo, TransactionResponse, TransactionStatus,
+        Balance, Block, ChainInfo, Coin, CoinType, Contract, ContractBalance, Message,
+        MessageProof, NodeInfo, TransactionResponse, TransactionStatus,
     },
     FuelClient,
 };
 use fuel_core_types::services::executor::TransactionExecutionStatus;
 use fuel_tx::{Transaction, TxId, UtxoId};
 use fuel_types::{Address, AssetId, BlockHeight, ContractId, Nonce};
-use fuels_core::types::errors::{error, Error, Result};
+use fuels_core::types::errors::{error, request::Failure, Error, Result};
+use futures::{Stream, StreamExt};
 
-use crate::provider::{retry_util, RetryConfig};
+use crate::provider::{
+    failover::{FailoverPolicy, NodeRouter},
+    retry_util, RetryConfig,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum RequestError {
     #[error(transparent)]
     IO(#[from] io::Error),
+    #[error("request timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("{0}")]
+    Unsupported(String),
 }
 
 type RequestResult<T> = std::result::Result<T, RequestError>;
 
+impl RequestError {
+    /// Whether the node couldn't be reached, as opposed to it rejecting the request.
+    fn is_node_failure(&self) -> bool {
+        match self {
+            RequestError::Timeout(_) => true,
+            RequestError::IO(e) => is_connection_error(e),
+            RequestError::Unsupported(_) => false,
+        }
+    }
+}
+
 impl From<RequestError> for Error {
     fn from(e: RequestError) -> Self {
-        Error::Provider(e.to_string())
+        let failure = match e {
+            RequestError::Timeout(timeout) => Failure::Timeout(timeout),
+            RequestError::IO(e) if is_connection_error(&e) => Failure::Connection(e.to_string()),
+            RequestError::Unsupported(msg) => Failure::Unsupported(msg),
+            RequestError::IO(e) => Failure::NodeError(e.to_string()),
+        };
+
+        Error::Request(failure)
+    }
+}
+
+/// Nodes without the index backing the GraphQL field `query` don't have it in their schema and
+/// reject the query before executing it, naming the field. Only meant for the errors of the
+/// query itself, whose other errors don't name it.
+fn unsupported_query(e: RequestError, query: &str) -> RequestError {
+    match e {
+        RequestError::IO(e) if e.to_string().contains(&format!(r#"field "{query}""#)) => {
+            RequestError::Unsupported(e.to_string())
+        }
+        e => e,
     }
 }
 
+/// The client wraps transport errors, so the whole chain of sources is searched for the
+/// underlying IO error.
+fn is_connection_error(e: &io::Error) -> bool {
+    let mut source: Option<&(dyn StdError + 'static)> = Some(e);
+
+    while let Some(err) = source {
+        if let Some(io_err) = err.downcast_ref::<io::Error>() {
+            if matches!(
+                io_err.kind(),
+                io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::NotConnected
+                    | io::ErrorKind::AddrNotAvailable
+                    | io::ErrorKind::BrokenPipe
+            ) {
+                return true;
+            }
+
+            source = io_err
+                .get_ref()
+                .map(|inner| inner as &(dyn StdError + 'static));
+        } else {
+            source = err.source();
+        }
+    }
+
+    false
+}
+
 #[derive(Debug, Clone)]
-pub(crate) struct RetryableClient {
+struct Node {
     client: FuelClient,
     url: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RetryableClient {
+    nodes: Vec<Node>,
+    router: NodeRouter,
+    pinned: Option<usize>,
     retry_config: RetryConfig,
+    request_timeout: Option<Duration>,
 }
 
 impl RetryableClient {
     pub(crate) fn new(url: impl AsRef<str>, retry_config: RetryConfig) -> Result<Self> {
-        let url = url.as_ref().to_string();
-        let client = FuelClient::new(&url).map_err(|e| error!(Provider, "{e}"))?;
+        Self::new_multi(&[url], retry_config, FailoverPolicy::default())
+    }
+
+    pub(crate) fn new_multi(
+        urls: &[impl AsRef<str>],
+        retry_config: RetryConfig,
+        policy: FailoverPolicy,
+    ) -> Result<Self> {
+        if urls.is_empty() {
+            return Err(error!(Provider, "at least one node url is required"));
+        }
+
+        let nodes = urls
+            .iter()
+            .map(|url| {
+                let url = url.as_ref().to_string();
+                let client = FuelClient::new(&url).map_err(|e| error!(Provider, "{e}"))?;
+
+                Ok(Node { client, url })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Self {
-            client,
+            router: NodeRouter::new(nodes.len(), policy),
+            nodes,
+            pinned: None,
             retry_config,
-            url,
+            request_timeout: None,
         })
     }
 
+    /// The url of the node the next request goes to.
     pub(crate) fn url(&self) -> &str {
-        &self.url
+        &self.nodes[self.current_node()].url
+    }
+
+    /// The urls of the nodes along with whether they are healthy, in order of preference.
+    pub(crate) fn node_health(&self) -> Vec<(&str, bool)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.url.as_str(), self.router.is_healthy(index)))
+            .collect()
+    }
+
+    /// A client sending every request to the node the next request would go to, without
+    /// failing over.
+    pub(crate) fn pinned(&self) -> Self {
+        Self {
+            pinned: Some(self.current_node()),
+            ..self.clone()
+        }
+    }
+
+    fn current_node(&self) -> usize {
+        self.pinned.unwrap_or_else(|| self.router.select())
     }
 
     pub(crate) fn set_retry_config(&mut self, retry_config: RetryConfig) {
         self.retry_config = retry_config;
     }
 
-    async fn our_retry<T, Fut>(&self, action: impl Fn() -> Fut) -> RequestResult<T>
+    pub(crate) fn set_request_timeout(&mut self, request_timeout: Duration) {
+        self.request_timeout = Some(request_timeout);
+    }
+
+    /// Retries `action` on the current node following the retry config, then fails over to
+    /// the next healthy node not tried yet if the node couldn't be reached, unless the client
+    /// is pinned.
+    async fn our_retry<'a, T, Fut>(
+        &'a self,
+        action: impl Fn(&'a FuelClient) -> Fut,
+    ) -> RequestResult<T>
     where
         Fut: Future<Output = io::Result<T>>,
     {
-        Ok(retry_util::retry(action, &self.retry_config, |result| result.is_err()).await?)
+        let mut node = self.current_node();
+        let mut tried_nodes = vec![];
+        loop {
+            let client = &self.nodes[node].client;
+
+            let result = retry_util::retry(
+                || self.with_timeout(action(client)),
+                &self.retry_config,
+                |result| result.is_err(),
+            )
+            .await;
+
+            let node_failed = matches!(&result, Err(err) if err.is_node_failure());
+            self.router.record(node, node_failed);
+
+            if !node_failed || self.pinned.is_some() {
+                return result;
+            }
+
+            // The failed node may stay healthy until it reaches `max_failures`, so it is
+            // skipped explicitly rather than left to the router
+            tried_nodes.push(node);
+            match self.router.select_untried(&tried_nodes) {
+                Some(next) => node = next,
+                None => return result,
+            }
+        }
+    }
+
+    async fn with_timeout<T>(
+        &self,
+        request: impl Future<Output = io::Result<T>>,
+    ) -> RequestResult<T> {
+        let Some(timeout) = self.request_timeout else {
+            return Ok(request.await?);
+        };
+
+        tokio::time::timeout(timeout, request)
+            .await
+            .map_err(|_| RequestError::Timeout(timeout))?
+            .map_err(Into::into)
     }
 
     // DELEGATION START
     pub async fn health(&self) -> RequestResult<bool> {
-        self.our_retry(|| self.client.health()).await
+        self.our_retry(|client| client.health()).await
     }
 
     pub async fn transaction(&self, id: &TxId) -> RequestResult<Option<TransactionResponse>> {
-        self.our_retry(|| self.client.transaction(id)).await
+        self.our_retry(|client| client.transaction(id)).await
     }
 
     pub(crate) async fn chain_info(&self) -> RequestResult<ChainInfo> {
-        self.our_retry(|| self.client.chain_info()).await
+        self.our_retry(|client| client.chain_info()).await
     }
 
     pub async fn await_transaction_commit(&self, id: &TxId) -> RequestResult<TransactionStatus> {
-        self.our_retry(|| self.client.await_transaction_commit(id))
+        self.our_retry(|client| client.await_transaction_commit(id))
             .await
     }
 
+    pub async fn subscribe_transaction_status<'a>(
+        &'a self,
+        id: &'a TxId,
+    ) -> RequestResult<impl Stream<Item = RequestResult<TransactionStatus>> + 'a> {
+        let subscription = self
+            .our_retry(|client| client.subscribe_transaction_status(id))
+            .await?;
+
+        Ok(subscription.map(|status| status.map_err(RequestError::from)))
+    }
+
     pub async fn submit_and_await_commit(
         &self,
         tx: &Transaction,
     ) -> RequestResult<TransactionStatus> {
-        self.our_retry(|| self.client.submit_and_await_commit(tx))
+        self.our_retry(|client| client.submit_and_await_commit(tx))
             .await
     }
 
     pub async fn submit(&self, tx: &Transaction) -> RequestResult<TransactionId> {
-        self.our_retry(|| self.client.submit(tx)).await
+        self.our_retry(|client| client.submit(tx)).await
     }
 
     pub async fn transaction_status(&self, id: &TxId) -> RequestResult<TransactionStatus> {
-        self.our_retry(|| self.client.transaction_status(id)).await
+        self.our_retry(|client| client.transaction_status(id)).await
     }
 
     pub async fn node_info(&self) -> RequestResult<NodeInfo> {
-        self.our_retry(|| self.client.node_info()).await
+        self.our_retry(|client| client.node_info()).await
     }
 
     pub async fn latest_gas_price(&self) -> RequestResult<LatestGasPrice> {
-        self.our_retry(|| self.client.latest_gas_price()).await
+        self.our_retry(|client| client.latest_gas_price()).await
     }
 
     pub async fn estimate_gas_price(&self, block_horizon: u32) -> RequestResult<EstimateGasPrice> {
-        self.our_retry(|| self.client.estimate_gas_price(block_horizon))
+        self.our_retry(|client| client.estimate_gas_price(block_horizon))
             .await
             .map(Into::into)
     }
@@ -117,7 +299,7 @@ impl RetryableClient {
         &self,
         tx: &[Transaction],
     ) -> RequestResult<Vec<TransactionExecutionStatus>> {
-        self.our_retry(|| self.client.dry_run(tx)).await
+        self.our_retry(|client| client.dry_run(tx)).await
     }
 
     pub async fn dry_run_opt(
@@ -125,17 +307,30 @@ impl RetryableClient {
         tx: &[Transaction],
         utxo_validation: Option<bool>,
     ) -> RequestResult<Vec<TransactionExecutionStatus>> {
-        self.our_retry(|| self.client.dry_run_opt(tx, utxo_validation))
+        self.our_retry(|client| client.dry_run_opt(tx, utxo_validation))
+            .await
+    }
+
+    pub async fn query_raw(
+        &self,
+        query: &str,
+        variables: &serde_json::Value,
+    ) -> RequestResult<serde_json::Value> {
+        self.our_retry(|client| client.query(Operation::new(query.to_string(), variables.clone())))
             .await
     }
 
+    pub async fn coin(&self, id: &UtxoId) -> RequestResult<Option<Coin>> {
+        self.our_retry(|client| client.coin(id)).await
+    }
+
     pub async fn coins(
         &self,
         owner: &Address,
         asset_id: Option<&AssetId>,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Coin, String>> {
-        self.our_retry(move || self.client.coins(owner, asset_id, request.clone()))
+        self.our_retry(move |client| client.coins(owner, asset_id, request.clone()))
             .await
     }
 
@@ -145,24 +340,27 @@ impl RetryableClient {
         spend_query: Vec<(AssetId, u64, Option<u32>)>,
         excluded_ids: Option<(Vec<UtxoId>, Vec<Nonce>)>,
     ) -> RequestResult<Vec<Vec<CoinType>>> {
-        self.our_retry(move || {
-            self.client
-                .coins_to_spend(owner, spend_query.clone(), excluded_ids.clone())
+        self.our_retry(move |client| {
+            client.coins_to_spend(owner, spend_query.clone(), excluded_ids.clone())
         })
         .await
     }
 
     pub async fn balance(&self, owner: &Address, asset_id: Option<&AssetId>) -> RequestResult<u64> {
-        self.our_retry(|| self.client.balance(owner, asset_id))
+        self.our_retry(|client| client.balance(owner, asset_id))
             .await
     }
 
+    pub async fn contract(&self, id: &ContractId) -> RequestResult<Option<Contract>> {
+        self.our_retry(|client| client.contract(id)).await
+    }
+
     pub async fn contract_balance(
         &self,
         id: &ContractId,
         asset: Option<&AssetId>,
     ) -> RequestResult<u64> {
-        self.our_retry(|| self.client.contract_balance(id, asset))
+        self.our_retry(|client| client.contract_balance(id, asset))
             .await
     }
 
@@ -171,7 +369,7 @@ impl RetryableClient {
         contract: &ContractId,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<ContractBalance, String>> {
-        self.our_retry(|| self.client.contract_balances(contract, request.clone()))
+        self.our_retry(|client| client.contract_balances(contract, request.clone()))
             .await
     }
 
@@ -180,7 +378,7 @@ impl RetryableClient {
         owner: &Address,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Balance, String>> {
-        self.our_retry(|| self.client.balances(owner, request.clone()))
+        self.our_retry(|client| client.balances(owner, request.clone()))
             .await
     }
 
@@ -188,7 +386,7 @@ impl RetryableClient {
         &self,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<TransactionResponse, String>> {
-        self.our_retry(|| self.client.transactions(request.clone()))
+        self.our_retry(|client| client.transactions(request.clone()))
             .await
     }
 
@@ -197,8 +395,9 @@ impl RetryableClient {
         owner: &Address,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<TransactionResponse, String>> {
-        self.our_retry(|| self.client.transactions_by_owner(owner, request.clone()))
+        self.our_retry(|client| client.transactions_by_owner(owner, request.clone()))
             .await
+            .map_err(|e| unsupported_query(e, "transactionsByOwner"))
     }
 
     pub async fn produce_blocks(
@@ -206,26 +405,25 @@ impl RetryableClient {
         blocks_to_produce: u32,
         start_timestamp: Option<u64>,
     ) -> RequestResult<BlockHeight> {
-        self.our_retry(|| {
-            self.client
-                .produce_blocks(blocks_to_produce, start_timestamp)
-        })
-        .await
+        self.our_retry(|client| client.produce_blocks(blocks_to_produce, start_timestamp))
+            .await
     }
 
     pub async fn block(&self, id: &BlockId) -> RequestResult<Option<Block>> {
-        self.our_retry(|| self.client.block(id)).await
+        self.our_retry(|client| client.block(id)).await
     }
 
     pub async fn block_by_height(&self, height: BlockHeight) -> RequestResult<Option<Block>> {
-        self.our_retry(|| self.client.block_by_height(height)).await
+        self.our_retry(|client| client.block_by_height(height))
+            .await
     }
 
     pub async fn blocks(
         &self,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Block, String>> {
-        self.our_retry(|| self.client.blocks(request.clone())).await
+        self.our_retry(|client| client.blocks(request.clone()))
+            .await
     }
 
     pub async fn messages(
@@ -233,7 +431,7 @@ impl RetryableClient {
         owner: Option<&Address>,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Message, String>> {
-        self.our_retry(|| self.client.messages(owner, request.clone()))
+        self.our_retry(|client| client.messages(owner, request.clone()))
             .await
     }
 
@@ -245,11 +443,59 @@ impl RetryableClient {
         commit_block_id: Option<&BlockId>,
         commit_block_height: Option<BlockHeight>,
     ) -> RequestResult<Option<MessageProof>> {
-        self.our_retry(|| {
-            self.client
-                .message_proof(transaction_id, nonce, commit_block_id, commit_block_height)
+        self.our_retry(|client| {
+            client.message_proof(transaction_id, nonce, commit_block_id, commit_block_height)
         })
         .await
     }
     // DELEGATION END
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_errors_are_classified() {
+        let refused = io::Error::new(
+            io::ErrorKind::Other,
+            io::Error::from(io::ErrorKind::ConnectionRefused),
+        );
+        let node_error = io::Error::new(io::ErrorKind::Other, "Response errors; NotFound");
+        let unsupported = io::Error::new(
+            io::ErrorKind::Other,
+            r#"Response errors; Unknown field "transactionsByOwner" on type "Query"."#,
+        );
+
+        assert!(matches!(
+            Error::from(RequestError::IO(refused)),
+            Error::Request(Failure::Connection(_))
+        ));
+        assert!(matches!(
+            Error::from(RequestError::IO(node_error)),
+            Error::Request(Failure::NodeError(msg)) if msg == "Response errors; NotFound"
+        ));
+        assert!(matches!(
+            Error::from(unsupported_query(
+                RequestError::IO(unsupported),
+                "transactionsByOwner"
+            )),
+            Error::Request(Failure::Unsupported(_))
+        ));
+        let other_field = io::Error::new(
+            io::ErrorKind::Other,
+            r#"Response errors; Unknown field "balances" on type "Query"."#,
+        );
+        assert!(matches!(
+            Error::from(unsupported_query(
+                RequestError::IO(other_field),
+                "transactionsByOwner"
+            )),
+            Error::Request(Failure::NodeError(_))
+        ));
+        assert!(matches!(
+            Error::from(RequestError::Timeout(Duration::from_secs(1))),
+            Error::Request(Failure::Timeout(_))
+        ));
+    }
+}
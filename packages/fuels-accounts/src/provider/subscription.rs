@@ -0,0 +1,189 @@
+use std::{future::Future, pin::Pin};
+
+use fuels_core::types::errors::{error, Result};
+use futures::{Stream, StreamExt};
+
+/// An item of a [`Provider`](crate::provider::Provider) subscription that resubscribes when
+/// the connection to the node drops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionEvent<T> {
+    Item(T),
+    /// The subscription dropped and was reestablished. If `possible_gap` is set, items sent by
+    /// the node in the meantime may have been missed and should be backfilled by querying the
+    /// current state. Otherwise the subscription resumed with the last item received.
+    Reconnected {
+        possible_gap: bool,
+    },
+}
+
+struct State<T, S, F> {
+    subscribe: F,
+    subscription: Option<Pin<Box<S>>>,
+    last: Option<T>,
+    pending: Option<T>,
+    reconnecting: bool,
+    done: bool,
+}
+
+/// Yields the items of the subscriptions created by `subscribe`, resubscribing whenever one
+/// drops, with an error or by ending, before yielding a final item according to `is_final`.
+/// A `Reconnected` event is yielded along with the first item of the new subscription, which
+/// is compared with the last item received using `same` to detect gaps, and skipped if equal.
+/// A subscription dropping before yielding any item isn't retried, so that a node rejecting
+/// it doesn't cause an endless loop. The error is yielded instead and the stream ends.
+pub(crate) fn resubscribing<'a, T, S, Fut>(
+    subscribe: impl Fn() -> Fut + 'a,
+    is_final: fn(&T) -> bool,
+    same: fn(&T, &T) -> bool,
+) -> impl Stream<Item = Result<SubscriptionEvent<T>>> + 'a
+where
+    T: Clone + 'a,
+    S: Stream<Item = Result<T>> + 'a,
+    Fut: Future<Output = Result<S>> + 'a,
+{
+    let state = State {
+        subscribe,
+        subscription: None,
+        last: None,
+        pending: None,
+        reconnecting: false,
+        done: false,
+    };
+
+    futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.take() {
+                state.last = Some(item.clone());
+                return Some((Ok(SubscriptionEvent::Item(item)), state));
+            }
+            if state.done {
+                return None;
+            }
+
+            let Some(subscription) = state.subscription.as_mut() else {
+                match (state.subscribe)().await {
+                    Ok(subscription) => state.subscription = Some(Box::pin(subscription)),
+                    Err(err) => {
+                        state.done = true;
+                        return Some((Err(err), state));
+                    }
+                }
+                continue;
+            };
+
+            let item = match subscription.next().await {
+                Some(Ok(item)) => item,
+                dropped => {
+                    state.subscription = None;
+
+                    if state.last.is_none() || state.reconnecting {
+                        state.done = true;
+                        let err = match dropped {
+                            Some(Err(err)) => err,
+                            _ => error!(Provider, "subscription ended before a final item"),
+                        };
+                        return Some((Err(err), state));
+                    }
+
+                    state.reconnecting = true;
+                    continue;
+                }
+            };
+            state.done = is_final(&item);
+
+            if std::mem::take(&mut state.reconnecting) {
+                let resumed = state.last.as_ref().is_some_and(|last| same(last, &item));
+                if !resumed {
+                    state.pending = Some(item);
+                }
+                let event = SubscriptionEvent::Reconnected {
+                    possible_gap: !resumed,
+                };
+                return Some((Ok(event), state));
+            }
+
+            state.last = Some(item.clone());
+            return Some((Ok(SubscriptionEvent::Item(item)), state));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use futures::stream;
+
+    use super::*;
+
+    fn is_final(item: &u64) -> bool {
+        *item == 3
+    }
+
+    fn same(last: &u64, new: &u64) -> bool {
+        last == new
+    }
+
+    /// Yields the items of `subscriptions` as if the node sent them, one subscription per call.
+    async fn events(subscriptions: Vec<Vec<Result<u64>>>) -> Vec<Result<SubscriptionEvent<u64>>> {
+        let subscriptions = RefCell::new(subscriptions.into_iter());
+        let subscribe = || {
+            let items = subscriptions
+                .borrow_mut()
+                .next()
+                .expect("too many subscriptions");
+            async move { Ok(stream::iter(items)) }
+        };
+
+        resubscribing(subscribe, is_final, same).collect().await
+    }
+
+    fn dropped() -> Result<u64> {
+        Err(error!(Provider, "connection reset"))
+    }
+
+    #[tokio::test]
+    async fn resumes_without_gap_when_the_last_item_is_sent_again() {
+        let events = events(vec![vec![Ok(1), Ok(2), dropped()], vec![Ok(2), Ok(3)]]).await;
+
+        let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            events,
+            [
+                SubscriptionEvent::Item(1),
+                SubscriptionEvent::Item(2),
+                SubscriptionEvent::Reconnected {
+                    possible_gap: false
+                },
+                SubscriptionEvent::Item(3),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn signals_a_possible_gap() {
+        // The second subscription ends without a final item, like a dropped connection
+        let events = events(vec![vec![Ok(1)], vec![Ok(3)]]).await;
+
+        let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            events,
+            [
+                SubscriptionEvent::Item(1),
+                SubscriptionEvent::Reconnected { possible_gap: true },
+                SubscriptionEvent::Item(3),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_resubscribe_if_nothing_was_received() {
+        let events = events(vec![vec![Ok(1), dropped()], vec![dropped()]]).await;
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Ok(SubscriptionEvent::Item(1))));
+        assert!(events[1]
+            .as_ref()
+            .is_err_and(|err| err.to_string().contains("connection reset")));
+    }
+}
@@ -0,0 +1,184 @@
+use fuel_tx::Output;
+use fuel_types::AssetId;
+use fuels_core::types::{
+    bech32::Bech32Address,
+    errors::{error, Result},
+    transaction::TxPolicies,
+    transaction_builders::ScriptTransactionBuilder,
+};
+
+use crate::Account;
+
+/// The amount of a transfer of a [`TransactionTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateAmount {
+    Fixed(u64),
+    /// Filled in by [`TransactionTemplate::instantiate`] with the amount given for this name.
+    Placeholder(String),
+}
+
+impl TemplateAmount {
+    pub fn placeholder(name: impl Into<String>) -> Self {
+        Self::Placeholder(name.into())
+    }
+}
+
+impl From<u64> for TemplateAmount {
+    fn from(amount: u64) -> Self {
+        Self::Fixed(amount)
+    }
+}
+
+/// The structure of a transfer transaction whose amounts may be left as placeholders, for
+/// tooling sending many similar transactions. The outputs and the fixed amounts to fund are
+/// assembled once, when the template is built. Each [`instantiate`](Self::instantiate) fills
+/// in the placeholders and still selects the resources for every amount, fixed ones included,
+/// and funds the fee, since the coins picked for an earlier instance may be spent by then.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionTemplate {
+    outputs: Vec<Output>,
+    // The outputs whose amount is a placeholder, along with its name
+    placeholders: Vec<(usize, String)>,
+    // The amounts of the transfers that aren't placeholders
+    fixed_amounts: Vec<(AssetId, u64)>,
+    script_data: Vec<u8>,
+    tx_policies: TxPolicies,
+}
+
+impl TransactionTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a transfer of `amount` of `asset_id` to `to`. The same placeholder can be used by
+    /// several transfers, which then receive the same amount.
+    pub fn with_transfer(
+        mut self,
+        to: &Bech32Address,
+        asset_id: AssetId,
+        amount: impl Into<TemplateAmount>,
+    ) -> Self {
+        let amount = match amount.into() {
+            TemplateAmount::Fixed(amount) => {
+                self.fixed_amounts.push((asset_id, amount));
+                amount
+            }
+            TemplateAmount::Placeholder(name) => {
+                self.placeholders.push((self.outputs.len(), name));
+                0
+            }
+        };
+        self.outputs.push(Output::coin(to.into(), amount, asset_id));
+
+        self
+    }
+
+    pub fn with_script_data(mut self, script_data: Vec<u8>) -> Self {
+        self.script_data = script_data;
+        self
+    }
+
+    pub fn with_tx_policies(mut self, tx_policies: TxPolicies) -> Self {
+        self.tx_policies = tx_policies;
+        self
+    }
+
+    /// The names of the placeholders, in the order of their first use.
+    pub fn placeholders(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = vec![];
+        for (_, name) in &self.placeholders {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+
+    /// Fills in the placeholders with `amounts`, given by name, and funds the transfers and the
+    /// fee with resources of `account`, see [`Account::fund_for_amounts`]. Fails if a
+    /// placeholder has no amount or an amount is given for an unknown placeholder.
+    pub async fn instantiate<A: Account>(
+        &self,
+        account: &A,
+        amounts: &[(&str, u64)],
+    ) -> Result<ScriptTransactionBuilder> {
+        if let Some((unknown, _)) = amounts
+            .iter()
+            .find(|(name, _)| !self.placeholders.iter().any(|(_, p)| p == name))
+        {
+            return Err(error!(
+                Other,
+                "the transaction template has no placeholder `{unknown}`"
+            ));
+        }
+
+        let mut outputs = self.outputs.clone();
+        let mut spent = self.fixed_amounts.clone();
+        for (index, name) in &self.placeholders {
+            let (_, value) = amounts
+                .iter()
+                .find(|(given, _)| given == name)
+                .ok_or_else(|| error!(Other, "placeholder `{name}` was not filled"))?;
+
+            if let Output::Coin {
+                amount, asset_id, ..
+            } = &mut outputs[*index]
+            {
+                *amount = *value;
+                spent.push((*asset_id, *value));
+            }
+        }
+
+        let mut tb = ScriptTransactionBuilder::default()
+            .with_outputs(outputs)
+            .with_tx_policies(self.tx_policies)
+            .with_script_data(self.script_data.clone());
+        account.fund_for_amounts(&mut tb, &spent).await?;
+
+        Ok(tb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels_core::constants::BASE_ASSET_ID;
+
+    use super::*;
+    use crate::wallet::WalletUnlocked;
+
+    fn template() -> TransactionTemplate {
+        let to = WalletUnlocked::new_random(None).address().clone();
+
+        TransactionTemplate::new()
+            .with_transfer(&to, BASE_ASSET_ID, TemplateAmount::placeholder("payout"))
+            .with_transfer(&to, AssetId::from([1; 32]), 10)
+            .with_transfer(&to, BASE_ASSET_ID, TemplateAmount::placeholder("payout"))
+            .with_transfer(&to, BASE_ASSET_ID, TemplateAmount::placeholder("fee"))
+    }
+
+    #[test]
+    fn placeholders_are_listed_once() {
+        assert_eq!(template().placeholders(), ["payout", "fee"]);
+    }
+
+    #[tokio::test]
+    async fn unfilled_placeholders_are_rejected() {
+        let wallet = WalletUnlocked::new_random(None);
+
+        let err = template()
+            .instantiate(&wallet, &[("payout", 100)])
+            .await
+            .expect_err("should fail because `fee` has no amount");
+        assert_eq!(err.to_string(), "placeholder `fee` was not filled");
+
+        let err = template()
+            .instantiate(&wallet, &[("payout", 100), ("fee", 1), ("tip", 1)])
+            .await
+            .expect_err("should fail because there is no `tip` placeholder");
+        assert_eq!(
+            err.to_string(),
+            "the transaction template has no placeholder `tip`"
+        );
+    }
+}
@@ -3,20 +3,34 @@ use std::{fmt, ops, path::Path};
 use async_trait::async_trait;
 use elliptic_curve::rand_core;
 use fuel_crypto::{Message, PublicKey, SecretKey, Signature};
+use fuel_tx::Output;
 use fuels_core::{
+    constants::BASE_ASSET_ID,
     traits::Signer,
     types::{
         bech32::{Bech32Address, FUEL_BECH32_HRP},
+        coin_type::CoinType,
+        coin_type_id::CoinTypeId,
         errors::{error, Result},
         input::Input,
-        transaction_builders::TransactionBuilder,
+        transaction::TxPolicies,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        unsigned_transaction::UnsignedTransaction,
         AssetId,
     },
 };
 use rand::{CryptoRng, Rng};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::{accounts_utils::try_provider_error, provider::Provider, Account, ViewOnlyAccount};
+use crate::{
+    accounts_utils::{
+        adjust_inputs_outputs, calculate_missing_base_amount, check_same_chain, try_provider_error,
+    },
+    provider::Provider,
+    Account, ViewOnlyAccount,
+};
 
 pub const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/1179993420'";
 
@@ -45,6 +59,12 @@ pub struct WalletUnlocked {
     #[zeroize(skip)]
     wallet: Wallet,
     pub(crate) private_key: SecretKey,
+    #[zeroize(skip)]
+    auto_consolidate_threshold: Option<usize>,
+    #[zeroize(skip)]
+    revalidate_inputs: bool,
+    #[zeroize(skip)]
+    dust_change_threshold: Option<u64>,
 }
 
 impl Wallet {
@@ -57,10 +77,24 @@ impl Wallet {
         self.provider.as_ref()
     }
 
+    /// Replaces the provider without any checks, even if the new one is connected to a different
+    /// chain. Operations already running keep using the provider, and the consensus parameters,
+    /// they started with. Prefer [`rotate_provider`](Self::rotate_provider) when failing over
+    /// to another node of the same chain.
     pub fn set_provider(&mut self, provider: Provider) {
         self.provider = Some(provider);
     }
 
+    /// Switches to `provider` if it is connected to the same chain as the current one, otherwise
+    /// fails and keeps the current provider. Use [`set_provider`](Self::set_provider) to
+    /// switch chains.
+    pub fn rotate_provider(&mut self, provider: Provider) -> Result<()> {
+        check_same_chain(self.provider(), &provider)?;
+        self.set_provider(provider);
+
+        Ok(())
+    }
+
     pub fn address(&self) -> &Bech32Address {
         &self.address
     }
@@ -73,6 +107,9 @@ impl Wallet {
         WalletUnlocked {
             wallet: self,
             private_key,
+            auto_consolidate_threshold: None,
+            revalidate_inputs: false,
+            dust_change_threshold: None,
         }
     }
 }
@@ -87,6 +124,52 @@ impl ViewOnlyAccount for Wallet {
     }
 }
 
+impl Wallet {
+    /// Funds `tb` with resources of this wallet like
+    /// [`Account::adjust_for_fee`](crate::Account::adjust_for_fee) and builds it without any
+    /// signatures. A locked wallet can't send transactions itself, so this is its only way to
+    /// take part in one: sign the result where the private key is kept, see
+    /// [`UnsignedTransaction`].
+    pub async fn build_unsigned<Tb: TransactionBuilder + Sync>(
+        &self,
+        tb: Tb,
+        used_base_amount: u64,
+    ) -> Result<UnsignedTransaction<<Tb as BuildableTransaction>::TxType>> {
+        Account::build_unsigned(&LockedFunder(self), tb, used_base_amount).await
+    }
+}
+
+/// Lends the funding of [`Account`] to a locked [`Wallet`], which can't be an [`Account`]
+/// itself since it can't sign the transactions it would send.
+#[derive(Debug, Clone)]
+struct LockedFunder<'a>(&'a Wallet);
+
+impl ViewOnlyAccount for LockedFunder<'_> {
+    fn address(&self) -> &Bech32Address {
+        self.0.address()
+    }
+
+    fn try_provider(&self) -> Result<&Provider> {
+        self.0.try_provider()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Account for LockedFunder<'_> {
+    async fn get_asset_inputs_for_amount(
+        &self,
+        asset_id: AssetId,
+        amount: u64,
+    ) -> Result<Vec<Input>> {
+        Ok(self
+            .get_spendable_resources(asset_id, amount)
+            .await?
+            .into_iter()
+            .map(Input::resource_signed)
+            .collect())
+    }
+}
+
 impl WalletUnlocked {
     /// Lock the wallet by securely `zeroize`-ing and `drop`ping the private key from memory.
     pub fn lock(mut self) -> Wallet {
@@ -102,6 +185,11 @@ impl WalletUnlocked {
         self.wallet.set_provider(provider);
     }
 
+    /// See [`Wallet::rotate_provider`].
+    pub fn rotate_provider(&mut self, provider: Provider) -> Result<()> {
+        self.wallet.rotate_provider(provider)
+    }
+
     /// Creates a new wallet with a random private key.
     pub fn new_random(provider: Option<Provider>) -> Self {
         let mut rng = rand::thread_rng();
@@ -187,6 +275,113 @@ impl WalletUnlocked {
     pub fn address(&self) -> &Bech32Address {
         &self.address
     }
+
+    /// Makes the wallet merge its other coins of an asset into the transaction being funded
+    /// whenever funding it selects more than `max_inputs` of them, so that later transactions
+    /// need fewer inputs. The merged coins are added as inputs of the same transaction and come
+    /// back through its change output, so no other transaction is sent and the caller sees them
+    /// in the builder. The coins of an asset never take more than half of the chain's input
+    /// limit, leaving room for the inputs paying the fee and spending other assets. No coin is
+    /// merged if the selected ones already reach that share.
+    pub fn auto_consolidate_when_inputs_exceed(mut self, max_inputs: usize) -> Self {
+        self.auto_consolidate_threshold = Some(max_inputs);
+        self
+    }
+
+    /// Makes the transfers of the wallet replace inputs spent since their selection before
+    /// being sent. See [`Account::replace_spent_inputs`].
+    pub fn revalidate_inputs_before_sending(mut self, enabled: bool) -> Self {
+        self.revalidate_inputs = enabled;
+        self
+    }
+
+    /// Makes the wallet give base asset change below `threshold` to the block producer instead
+    /// of creating a change output for it. See [`Account::dust_change_threshold`].
+    pub fn suppress_change_below(mut self, threshold: u64) -> Self {
+        self.dust_change_threshold = Some(threshold);
+        self
+    }
+
+    /// Merges the smallest coins of `asset_id` owned by the wallet into a single coin, spending
+    /// as many of them as the chain allows in one transaction. The fee is paid with the base
+    /// asset, either from the merged coins themselves or from additional base asset coins.
+    /// Does nothing if the wallet has less than two coins of `asset_id`.
+    pub async fn consolidate(&self, asset_id: AssetId) -> Result<()> {
+        let provider = self.try_provider()?;
+
+        let mut coins = self.get_coins(asset_id).await?;
+        if coins.len() < 2 {
+            return Ok(());
+        }
+        coins.sort_by_key(|coin| coin.amount);
+
+        // Leaves room for a base asset input paying the fee.
+        let max_inputs = usize::from(provider.consensus_parameters().tx_params().max_inputs);
+        let inputs = coins
+            .into_iter()
+            .take(max_inputs.saturating_sub(1))
+            .map(|coin| Input::resource_signed(CoinType::Coin(coin)))
+            .collect();
+        let outputs = vec![Output::change(self.address().into(), 0, asset_id)];
+
+        let mut tb =
+            ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+        tb.add_signer(self.clone())?;
+
+        // `adjust_for_fee` is not used as it could merge more coins into the transaction.
+        let missing_base_amount = calculate_missing_base_amount(&tb, 0, provider).await?;
+        if missing_base_amount > 0 {
+            if asset_id == BASE_ASSET_ID {
+                return Err(error!(
+                    Other,
+                    "the coins to consolidate do not cover the fee of their consolidation"
+                ));
+            }
+
+            let base_inputs = self
+                .get_spendable_resources(BASE_ASSET_ID, missing_base_amount)
+                .await?
+                .into_iter()
+                .map(Input::resource_signed);
+            adjust_inputs_outputs(&mut tb, base_inputs, self.address());
+        }
+
+        let tx = tb.build(provider).await?;
+        provider
+            .send_transaction_and_await_commit(tx)
+            .await?
+            .check(None)?;
+
+        Ok(())
+    }
+
+    /// The other coins of `asset_id` to merge into a transaction spending `selected`, the
+    /// smallest first. See
+    /// [`auto_consolidate_when_inputs_exceed`](Self::auto_consolidate_when_inputs_exceed).
+    async fn coins_to_merge(
+        &self,
+        asset_id: AssetId,
+        selected: &[CoinType],
+    ) -> Result<Vec<CoinType>> {
+        let provider = self.try_provider()?;
+
+        let max_inputs = usize::from(provider.consensus_parameters().tx_params().max_inputs);
+        let room = (max_inputs / 2).saturating_sub(selected.len());
+        if room == 0 {
+            return Ok(vec![]);
+        }
+
+        let selected_ids: Vec<_> = selected.iter().map(CoinType::id).collect();
+        let mut coins: Vec<_> = self
+            .get_coins(asset_id)
+            .await?
+            .into_iter()
+            .filter(|coin| !selected_ids.contains(&CoinTypeId::UtxoId(coin.utxo_id)))
+            .collect();
+        coins.sort_by_key(|coin| coin.amount);
+
+        Ok(coins.into_iter().take(room).map(CoinType::Coin).collect())
+    }
 }
 
 impl ViewOnlyAccount for WalletUnlocked {
@@ -210,14 +405,30 @@ impl Account for WalletUnlocked {
         asset_id: AssetId,
         amount: u64,
     ) -> Result<Vec<Input>> {
-        Ok(self
-            .get_spendable_resources(asset_id, amount)
-            .await?
+        let mut resources = self.get_spendable_resources(asset_id, amount).await?;
+
+        if self
+            .auto_consolidate_threshold
+            .is_some_and(|threshold| resources.len() > threshold)
+        {
+            let coins = self.coins_to_merge(asset_id, &resources).await?;
+            resources.extend(coins);
+        }
+
+        Ok(resources
             .into_iter()
             .map(Input::resource_signed)
             .collect::<Vec<Input>>())
     }
 
+    fn revalidates_inputs(&self) -> bool {
+        self.revalidate_inputs
+    }
+
+    fn dust_change_threshold(&self) -> Option<u64> {
+        self.dust_change_threshold
+    }
+
     fn add_witnesses<Tb: TransactionBuilder>(&self, tb: &mut Tb) -> Result<()> {
         tb.add_signer(self.clone())?;
 
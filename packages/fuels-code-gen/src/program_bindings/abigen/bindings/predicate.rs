@@ -40,6 +40,14 @@ pub(crate) fn predicate_bindings(
                     encoder: ::fuels::core::codec::ABIEncoder::new(encoder_config)
                 }
             }
+
+            pub fn with_encoder_config(mut self, encoder_config: ::fuels::core::codec::EncoderConfig)
+                -> Self
+            {
+                self.encoder = ::fuels::core::codec::ABIEncoder::new(encoder_config);
+
+                self
+            }
         }
 
         #constant_configuration_code
@@ -109,12 +109,12 @@ fn expand_fn(abi: &FullProgramABI) -> Result<TokenStream> {
 
     let arg_tokens = generator.tokenized_args();
     let body = quote! {
-            let encoded_args = ::fuels::core::codec::ABIEncoder::new(self.encoder_config).encode(&#arg_tokens);
             let provider = ::fuels::accounts::ViewOnlyAccount::try_provider(&self.account).expect("Provider not set up")
                 .clone();
-            ::fuels::programs::script_calls::ScriptCallHandler::new(
+            ::fuels::programs::script_calls::ScriptCallHandler::new_with_args(
                 self.binary.clone(),
-                encoded_args,
+                ::std::vec::Vec::from(#arg_tokens),
+                self.encoder_config,
                 self.account.clone(),
                 provider,
                 self.log_decoder.clone()
@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fuels_core::{codec::ABIDecoder, types::param_types::ParamType};
+
+fn inputs() -> Vec<(&'static str, ParamType)> {
+    vec![
+        ("bool", ParamType::Bool),
+        ("u64", ParamType::U64),
+        ("u256", ParamType::U256),
+        ("b256", ParamType::B256),
+    ]
+}
+
+fn decode_scalar(c: &mut Criterion) {
+    let decoder = ABIDecoder::default();
+    let bytes = [7u8; 32];
+    let mut group = c.benchmark_group("decode_scalar");
+
+    for (name, param_type) in inputs() {
+        group.bench_with_input(
+            BenchmarkId::new("fast_path", name),
+            &param_type,
+            |b, param_type| b.iter(|| decoder.decode(black_box(param_type), black_box(&bytes))),
+        );
+
+        // Goes through the limits and the collection of the decoded tokens
+        group.bench_with_input(
+            BenchmarkId::new("general", name),
+            &param_type,
+            |b, param_type| {
+                b.iter(|| {
+                    decoder.decode_multiple(
+                        std::slice::from_ref(black_box(param_type)),
+                        black_box(&bytes),
+                    )
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, decode_scalar);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fuels_core::{
+    codec::{token_trees, ABIEncoder, EncoderConfig},
+    types::Token,
+};
+
+fn inputs() -> Vec<(&'static str, Vec<Token>)> {
+    vec![
+        ("flat_scalars", token_trees::flat_scalars(1_000)),
+        (
+            "deep_nesting",
+            vec![token_trees::nested_structs(
+                EncoderConfig::default().max_depth,
+            )],
+        ),
+        ("large_vector", vec![token_trees::large_vector(5_000)]),
+        (
+            "many_dynamic_segments",
+            token_trees::many_dynamic_segments(1_000),
+        ),
+        ("mixed_struct", vec![token_trees::mixed_struct()]),
+    ]
+}
+
+fn encode(c: &mut Criterion) {
+    let encoder = ABIEncoder::default();
+    let mut group = c.benchmark_group("encode");
+
+    for (name, tokens) in inputs() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &tokens, |b, tokens| {
+            b.iter(|| encoder.encode(black_box(tokens)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn encode_with_buffer_pool(c: &mut Criterion) {
+    let encoder = ABIEncoder::default().with_buffer_pool();
+    let mut group = c.benchmark_group("encode_with_buffer_pool");
+
+    for (name, tokens) in inputs() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &tokens, |b, tokens| {
+            b.iter(|| encoder.encode(black_box(tokens)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn resolve(c: &mut Criterion) {
+    let encoder = ABIEncoder::default();
+    let mut group = c.benchmark_group("resolve");
+
+    for (name, tokens) in inputs() {
+        let unresolved = encoder.encode(&tokens).unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &unresolved,
+            |b, unresolved| b.iter(|| unresolved.resolve(black_box(0))),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, encode, encode_with_buffer_pool, resolve);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fuels_core::{
+    codec::{token_trees, EncoderConfig},
+    types::{
+        param_types::{ParamType, TokenValidator},
+        Token,
+    },
+};
+
+fn inputs() -> Vec<(&'static str, Token)> {
+    vec![
+        (
+            "deep_nesting",
+            token_trees::nested_structs(EncoderConfig::default().max_depth - 1),
+        ),
+        ("large_vector", token_trees::large_vector(5_000)),
+        ("mixed_struct", token_trees::mixed_struct()),
+    ]
+}
+
+fn validate(c: &mut Criterion) {
+    let max_depth = EncoderConfig::default().max_depth;
+    let mut group = c.benchmark_group("validate");
+
+    for (name, token) in inputs() {
+        let param_type = ParamType::infer_from(&token).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("recursive", name), &token, |b, token| {
+            b.iter(|| {
+                param_type
+                    .validate_token(black_box(token), max_depth)
+                    .unwrap()
+            })
+        });
+
+        let validator = TokenValidator::compile(&param_type);
+        group.bench_with_input(BenchmarkId::new("compiled", name), &token, |b, token| {
+            b.iter(|| validator.validate(black_box(token)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, validate);
+criterion_main!(benches);
@@ -1,7 +1,15 @@
 mod abi_decoder;
 mod abi_encoder;
 mod function_selector;
+#[cfg(any(test, feature = "generators"))]
+pub mod generators;
 mod logs;
+#[cfg(test)]
+mod snapshots;
+#[cfg(any(test, feature = "token-assertions"))]
+pub mod token_assertions;
+#[cfg(any(test, feature = "token-trees"))]
+pub mod token_trees;
 mod utils;
 
 pub use abi_decoder::*;
@@ -1,15 +1,25 @@
 mod bounded_decoder;
+mod columnar;
 mod decode_as_debug_str;
 #[cfg(experimental)]
 mod experimental_bounded_decoder;
 
+use std::time::Duration;
+
+pub use columnar::Column;
+
 #[cfg(experimental)]
 use crate::codec::abi_decoder::experimental_bounded_decoder::ExperimentalBoundedDecoder;
 use crate::{
     codec::abi_decoder::{
-        bounded_decoder::BoundedDecoder, decode_as_debug_str::decode_as_debug_str,
+        bounded_decoder::BoundedDecoder, columnar::ColumnarDecoder,
+        decode_as_debug_str::decode_as_debug_str,
+    },
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        Token,
     },
-    types::{errors::Result, param_types::ParamType, Token},
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -18,8 +28,15 @@ pub struct DecoderConfig {
     /// the current depth becomes greater than `max_depth` configured here.
     pub max_depth: usize,
     /// Every decoded Token will increase the token count. Decoding will fail if the current
-    /// token count becomes greater than `max_tokens` configured here.
+    /// token count becomes greater than `max_tokens` configured here. This bounds the size of
+    /// the decoded `Token` tree even when a small input declares huge or deeply repeated
+    /// structures, so keep it low when decoding untrusted data.
     pub max_tokens: usize,
+    /// Decoding will fail once it takes longer than `deadline`, so that inputs that are slow
+    /// to decode despite the other limits, such as deeply nested types, can't pin a CPU.
+    /// `None` disables the check. Time can't be read on `wasm32`, so decoding fails there
+    /// with a `Codec` error while a deadline is set.
+    pub deadline: Option<Duration>,
 }
 
 // ANCHOR: default_decoder_config
@@ -28,6 +45,7 @@ impl Default for DecoderConfig {
         Self {
             max_depth: 45,
             max_tokens: 10_000,
+            deadline: None,
         }
     }
 }
@@ -44,6 +62,8 @@ impl ABIDecoder {
     }
 
     /// Decodes `bytes` following the schema described in `param_type` into its respective `Token`.
+    /// Bytes after the decoded value are ignored, see [`decode_exact`](Self::decode_exact) to
+    /// reject them and [`decode_prefix`](Self::decode_prefix) to learn how many were used.
     ///
     /// # Arguments
     ///
@@ -64,9 +84,56 @@ impl ABIDecoder {
     /// assert_eq!(u64::from_token(token).unwrap(), 7u64);
     /// ```
     pub fn decode(&self, param_type: &ParamType, bytes: &[u8]) -> Result<Token> {
+        // A scalar is a single token decoded at once, so the limits can't be exceeded unless
+        // no token or no time at all is allowed
+        if self.config.max_tokens > 0 && self.config.deadline.is_none() {
+            if let Some(token) = BoundedDecoder::decode_scalar(param_type, bytes) {
+                return token;
+            }
+        }
+
         BoundedDecoder::new(self.config).decode(param_type, bytes)
     }
 
+    /// Decodes a value of `param_type` from the start of `bytes`, returning it along with the
+    /// number of bytes it took up, padding included. The rest of `bytes` is ignored, so that
+    /// values can be sliced one after the other out of a larger buffer.
+    ///
+    /// Heap types such as `Vec` and `Bytes` take up all of `bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fuels_core::codec::ABIDecoder;
+    /// use fuels_core::types::{param_types::ParamType, Token};
+    ///
+    /// let decoder = ABIDecoder::default();
+    /// let data: &[u8] = &[0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 8];
+    ///
+    /// let (token, consumed) = decoder.decode_prefix(&ParamType::U64, data).unwrap();
+    ///
+    /// assert_eq!(token, Token::U64(7));
+    /// assert_eq!(consumed, 8);
+    /// ```
+    pub fn decode_prefix(&self, param_type: &ParamType, bytes: &[u8]) -> Result<(Token, usize)> {
+        BoundedDecoder::new(self.config).decode_prefix(param_type, bytes)
+    }
+
+    /// Same as `decode` but fails if any bytes remain after the decoded value.
+    pub fn decode_exact(&self, param_type: &ParamType, bytes: &[u8]) -> Result<Token> {
+        let (token, consumed) = self.decode_prefix(param_type, bytes)?;
+
+        if consumed != bytes.len() {
+            return Err(error!(
+                Codec,
+                "{} trailing bytes after decoding a value of {param_type:?} from {consumed} bytes",
+                bytes.len() - consumed
+            ));
+        }
+
+        Ok(token)
+    }
+
     /// Same as `decode` but decodes multiple `ParamType`s in one go.
     /// # Examples
     /// ```
@@ -111,6 +178,21 @@ impl ABIDecoder {
         decode_as_debug_str(param_type, &token)
     }
 
+    /// Decodes many records of the same `param_type` into one column per leaf of the type,
+    /// e.g. for indexing large numbers of identical logs. The columns are named like the paths
+    /// of `Token::flatten` and hold the values of every record in order. Building no `Token`
+    /// per record makes this much faster than calling [`decode`](Self::decode) repeatedly.
+    ///
+    /// Only types of a fixed layout are supported, i.e. no enums or heap types, and the records
+    /// have to follow the layout read by `decode`.
+    pub fn decode_columns<R: AsRef<[u8]>>(
+        &self,
+        param_type: &ParamType,
+        records: impl IntoIterator<Item = R>,
+    ) -> Result<Vec<(String, Column)>> {
+        ColumnarDecoder::new(param_type, self.config)?.decode(records)
+    }
+
     #[cfg(experimental)]
     pub fn experimental_decode(&self, param_type: &ParamType, bytes: &[u8]) -> Result<Token> {
         ExperimentalBoundedDecoder::new(self.config).decode(param_type, bytes)
@@ -151,6 +233,82 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn scalars_decode_like_the_general_path() -> Result<()> {
+        let data: Vec<u8> = (1..=32).collect();
+        let config = DecoderConfig::default();
+
+        for param_type in [
+            ParamType::Unit,
+            ParamType::U8,
+            ParamType::Bool,
+            ParamType::U16,
+            ParamType::U32,
+            ParamType::U64,
+            ParamType::U128,
+            ParamType::U256,
+            ParamType::B256,
+        ] {
+            let fast = ABIDecoder::new(config).decode(&param_type, &data)?;
+            let general = BoundedDecoder::new(config).decode(&param_type, &data)?;
+            assert_eq!(fast, general);
+
+            if param_type != ParamType::Unit {
+                let short = &data[..1];
+                assert_eq!(
+                    ABIDecoder::new(config)
+                        .decode(&param_type, short)
+                        .map_err(|err| err.to_string()),
+                    BoundedDecoder::new(config)
+                        .decode(&param_type, short)
+                        .map_err(|err| err.to_string())
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn concatenated_values_are_decoded_one_prefix_at_a_time() -> Result<()> {
+        let pair = ParamType::Struct {
+            name: "Pair".to_string(),
+            fields: vec![
+                ("a".to_string(), ParamType::U64),
+                ("b".to_string(), ParamType::U8),
+            ],
+            generics: vec![],
+        };
+        let data = [
+            0, 0, 0, 0, 0, 0, 0, 1, // pair.a
+            2, 0, 0, 0, 0, 0, 0, 0, // pair.b, padded to a word
+            0, 0, 0, 0, 0, 0, 0, 3, // u32
+        ];
+        let decoder = ABIDecoder::default();
+
+        let (token, consumed) = decoder.decode_prefix(&pair, &data)?;
+        assert_eq!(token, Token::Struct(vec![Token::U64(1), Token::U8(2)]));
+        assert_eq!(consumed, 16);
+
+        let (token, consumed) = decoder.decode_prefix(&ParamType::U32, &data[consumed..])?;
+        assert_eq!(token, Token::U32(3));
+        assert_eq!(consumed, 8);
+
+        decoder.decode_exact(&pair, &data[..16])?;
+        // The padding of the last value may be left out
+        decoder.decode_exact(&pair, &data[..9])?;
+
+        let err = decoder
+            .decode_exact(&ParamType::U64, &data[..16])
+            .expect_err("should fail because of the trailing bytes");
+        assert_eq!(
+            err.to_string(),
+            "codec: 8 trailing bytes after decoding a value of U64 from 8 bytes"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn decode_multiple_int() -> Result<()> {
         let types = vec![
@@ -750,6 +908,33 @@ mod tests {
             })
     }
 
+    #[test]
+    fn deadline_aborts_decoding() {
+        const DEPTH: usize = 40;
+        let param_type = nested_enum(DEPTH);
+        // a discriminant per enum and the innermost u8
+        let data = [0; (DEPTH + 1) * WORD_SIZE];
+
+        let config = DecoderConfig {
+            deadline: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        assert_decoding_failed_w_data(
+            config,
+            &param_type,
+            "deadline of `0ns` reached while decoding. The input may be malicious",
+            &data,
+        );
+
+        let config = DecoderConfig {
+            deadline: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        ABIDecoder::new(config)
+            .decode(&param_type, &data)
+            .expect("should decode well within the deadline");
+    }
+
     #[test]
     fn depth_is_not_reached() {
         const MAX_DEPTH: usize = 3;
@@ -807,6 +992,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn small_input_cannot_blow_up_into_a_huge_token_tree() {
+        // every 8 byte element holds a million units, so 80 bytes would decode into ten million
+        // tokens
+        let element = ParamType::Tuple(vec![
+            ParamType::Array(Box::new(ParamType::Unit), 1_000_000),
+            ParamType::U8,
+        ]);
+        let param_type = ParamType::Vector(Box::new(element));
+        let data = [0; 10 * WORD_SIZE];
+
+        assert_decoding_failed_w_data(
+            DecoderConfig::default(),
+            &param_type,
+            "token limit `10000` reached while decoding. Try increasing it",
+            &data,
+        );
+    }
+
+    #[cfg(experimental)]
+    #[test]
+    fn declared_lengths_cannot_blow_up_into_a_huge_token_tree() {
+        let param_type = ParamType::Vector(Box::new(ParamType::Vector(Box::new(ParamType::Unit))));
+        // one inner vector declaring `u64::MAX` units
+        let data = [1u64.to_be_bytes(), u64::MAX.to_be_bytes()].concat();
+
+        let err = ABIDecoder::default()
+            .experimental_decode(&param_type, &data)
+            .expect_err("should hit the token limit");
+
+        assert_eq!(
+            err.to_string(),
+            "codec: token limit `10000` reached while decoding. Try increasing it"
+        );
+    }
+
     #[test]
     fn vectors_of_zst_are_not_supported() {
         let param_type = ParamType::Vector(Box::new(ParamType::StringArray(0)));
@@ -3,7 +3,7 @@ use std::{convert::TryInto, str};
 use crate::{
     checked_round_up_to_word_alignment,
     codec::{
-        utils::{CodecDirection, CounterWithLimit},
+        utils::{CodecDirection, CounterWithLimit, Deadline},
         DecoderConfig,
     },
     constants::WORD_SIZE,
@@ -19,6 +19,7 @@ use crate::{
 pub(crate) struct BoundedDecoder {
     depth_tracker: CounterWithLimit,
     token_tracker: CounterWithLimit,
+    deadline: Deadline,
     config: DecoderConfig,
 }
 
@@ -35,29 +36,72 @@ impl BoundedDecoder {
         Self {
             depth_tracker,
             token_tracker,
+            deadline: Deadline::new(config.deadline),
             config,
         }
     }
 
     pub(crate) fn decode(&mut self, param_type: &ParamType, bytes: &[u8]) -> Result<Token> {
+        self.decode_prefix(param_type, bytes)
+            .map(|(token, _)| token)
+    }
+
+    /// Decodes a value from the start of `bytes`, returning it along with the number of bytes
+    /// it took up, padding included. Any bytes after those are ignored.
+    pub(crate) fn decode_prefix(
+        &mut self,
+        param_type: &ParamType,
+        bytes: &[u8],
+    ) -> Result<(Token, usize)> {
         param_type.validate_is_decodable(self.config.max_depth)?;
-        match param_type {
+        let decoded = match param_type {
             // Unit, U8 and Bool are returned as u64 from receipt "Return"
+            ParamType::Unit => Decoded {
+                token: Token::Unit,
+                bytes_read: 0,
+            },
+            ParamType::U8 => {
+                let value = peek_u64(bytes)?;
+                Decoded {
+                    token: Token::U8(value as u8),
+                    bytes_read: WORD_SIZE,
+                }
+            }
+            ParamType::Bool => {
+                let value = peek_u64(bytes)?;
+                Decoded {
+                    token: Token::Bool(value != 0),
+                    bytes_read: WORD_SIZE,
+                }
+            }
+            _ => self.decode_param(param_type, bytes)?,
+        };
+
+        // Values are padded to a whole word, e.g. a struct ending in a `u8`
+        let consumed = checked_round_up_to_word_alignment(decoded.bytes_read)?.min(bytes.len());
+
+        Ok((decoded.token, consumed))
+    }
+
+    /// Decodes a top-level scalar, such as the value returned by most contract calls, straight
+    /// from `bytes` without setting up the decoding limits. Reads the same layout as
+    /// [`decode`](Self::decode) and returns `None` for non-scalar types.
+    pub(crate) fn decode_scalar(param_type: &ParamType, bytes: &[u8]) -> Option<Result<Token>> {
+        let token = match param_type {
             ParamType::Unit => Ok(Token::Unit),
-            ParamType::U8 => Self::decode_u64(bytes).map(|r| {
-                Token::U8(match r.token {
-                    Token::U64(v) => v as u8,
-                    _ => unreachable!("decode_u64 returning unexpected token"),
-                })
-            }),
-            ParamType::Bool => Self::decode_u64(bytes).map(|r| {
-                Token::Bool(match r.token {
-                    Token::U64(v) => v != 0,
-                    _ => unreachable!("decode_u64 returning unexpected token"),
-                })
-            }),
-            _ => self.decode_param(param_type, bytes).map(|x| x.token),
-        }
+            // Returned as u64 from receipt "Return", see `decode`
+            ParamType::U8 => peek_u64(bytes).map(|value| Token::U8(value as u8)),
+            ParamType::Bool => peek_u64(bytes).map(|value| Token::Bool(value != 0)),
+            ParamType::U16 => peek_u16(bytes).map(Token::U16),
+            ParamType::U32 => peek_u32(bytes).map(Token::U32),
+            ParamType::U64 => peek_u64(bytes).map(Token::U64),
+            ParamType::U128 => peek_u128(bytes).map(Token::U128),
+            ParamType::U256 => peek_u256(bytes).map(Token::U256),
+            ParamType::B256 => peek_fixed::<32>(bytes).map(|bytes| Token::B256(*bytes)),
+            _ => return None,
+        };
+
+        Some(token)
     }
 
     pub(crate) fn decode_multiple(
@@ -87,6 +131,7 @@ impl BoundedDecoder {
 
     fn decode_param(&mut self, param_type: &ParamType, bytes: &[u8]) -> Result<Decoded> {
         self.token_tracker.increase()?;
+        self.deadline.check()?;
         match param_type {
             ParamType::Unit => Self::decode_unit(bytes),
             ParamType::U8 => Self::decode_u8(bytes),
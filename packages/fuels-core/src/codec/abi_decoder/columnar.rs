@@ -0,0 +1,427 @@
+use std::str;
+
+use crate::{
+    checked_round_up_to_word_alignment,
+    codec::DecoderConfig,
+    constants::WORD_SIZE,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        U256,
+    },
+};
+
+/// The values of one leaf of a type across all decoded records, see
+/// [`ABIDecoder::decode_columns`](crate::codec::ABIDecoder::decode_columns).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    /// Units carry no data, only the number of records is kept.
+    Unit(usize),
+    Bool(Vec<bool>),
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+    U128(Vec<u128>),
+    U256(Vec<U256>),
+    B256(Vec<[u8; 32]>),
+    String(Vec<String>),
+}
+
+impl Column {
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Unit(len) => *len,
+            Column::Bool(values) => values.len(),
+            Column::U8(values) => values.len(),
+            Column::U16(values) => values.len(),
+            Column::U32(values) => values.len(),
+            Column::U64(values) => values.len(),
+            Column::U128(values) => values.len(),
+            Column::U256(values) => values.len(),
+            Column::B256(values) => values.len(),
+            Column::String(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A leaf of the type being decoded, found at a fixed offset in every record.
+struct Leaf {
+    path: String,
+    offset: usize,
+    kind: LeafKind,
+}
+
+#[derive(Clone, Copy)]
+enum LeafKind {
+    Unit,
+    Bool,
+    U8,
+    /// A top-level `bool` or `u8`, which is returned as a whole word.
+    WordBool,
+    WordU8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    B256,
+    StringArray(usize),
+}
+
+impl LeafKind {
+    fn empty_column(self) -> Column {
+        match self {
+            LeafKind::Unit => Column::Unit(0),
+            LeafKind::Bool | LeafKind::WordBool => Column::Bool(vec![]),
+            LeafKind::U8 | LeafKind::WordU8 => Column::U8(vec![]),
+            LeafKind::U16 => Column::U16(vec![]),
+            LeafKind::U32 => Column::U32(vec![]),
+            LeafKind::U64 => Column::U64(vec![]),
+            LeafKind::U128 => Column::U128(vec![]),
+            LeafKind::U256 => Column::U256(vec![]),
+            LeafKind::B256 => Column::B256(vec![]),
+            LeafKind::StringArray(_) => Column::String(vec![]),
+        }
+    }
+
+    /// The bytes read for the leaf, checked against the record length before reading.
+    fn width(self) -> usize {
+        match self {
+            LeafKind::Unit | LeafKind::Bool | LeafKind::U8 => 1,
+            LeafKind::WordBool | LeafKind::WordU8 | LeafKind::U16 | LeafKind::U32 => WORD_SIZE,
+            LeafKind::U64 => WORD_SIZE,
+            LeafKind::U128 => 2 * WORD_SIZE,
+            LeafKind::U256 | LeafKind::B256 => 4 * WORD_SIZE,
+            LeafKind::StringArray(len) => len,
+        }
+    }
+}
+
+/// Decodes records sharing the same `ParamType` straight into columns, without building a
+/// `Token` per record. The type is walked once to find the offset of every leaf, which is
+/// possible because only types of a fixed layout are supported.
+pub(crate) struct ColumnarDecoder {
+    leaves: Vec<Leaf>,
+}
+
+impl ColumnarDecoder {
+    pub(crate) fn new(param_type: &ParamType, config: DecoderConfig) -> Result<Self> {
+        let mut leaves = vec![];
+        match param_type {
+            ParamType::Bool => leaves.push(Leaf::top_level(LeafKind::WordBool)),
+            ParamType::U8 => leaves.push(Leaf::top_level(LeafKind::WordU8)),
+            // Like `ABIDecoder::decode`, a top-level unit doesn't read any bytes.
+            ParamType::Unit => {}
+            _ => {
+                collect_leaves(param_type, String::new(), 0, 0, &config, &mut leaves)?;
+            }
+        }
+
+        Ok(Self { leaves })
+    }
+
+    pub(crate) fn decode<R: AsRef<[u8]>>(
+        &self,
+        records: impl IntoIterator<Item = R>,
+    ) -> Result<Vec<(String, Column)>> {
+        let mut columns: Vec<_> = self
+            .leaves
+            .iter()
+            .map(|leaf| leaf.kind.empty_column())
+            .collect();
+
+        for record in records {
+            let record = record.as_ref();
+            for (leaf, column) in self.leaves.iter().zip(columns.iter_mut()) {
+                leaf.read_into(record, column)?;
+            }
+        }
+
+        Ok(self
+            .leaves
+            .iter()
+            .map(|leaf| leaf.path.clone())
+            .zip(columns)
+            .collect())
+    }
+}
+
+impl Leaf {
+    fn top_level(kind: LeafKind) -> Self {
+        Self {
+            path: String::new(),
+            offset: 0,
+            kind,
+        }
+    }
+
+    fn read_into(&self, record: &[u8], column: &mut Column) -> Result<()> {
+        let end = self.offset + self.kind.width();
+        let bytes = record.get(self.offset..end).ok_or_else(|| {
+            error!(
+                Codec,
+                "record of {} bytes is too short for `{}`, which ends at byte {end}",
+                record.len(),
+                self.path
+            )
+        })?;
+
+        match (self.kind, column) {
+            (LeafKind::Unit, Column::Unit(len)) => *len += 1,
+            (LeafKind::Bool, Column::Bool(values)) => values.push(bytes[0] != 0),
+            (LeafKind::WordBool, Column::Bool(values)) => values.push(be_u64(bytes) != 0),
+            (LeafKind::U8, Column::U8(values)) => values.push(bytes[0]),
+            (LeafKind::WordU8, Column::U8(values)) => values.push(be_u64(bytes) as u8),
+            (LeafKind::U16, Column::U16(values)) => values.push(be_u64(bytes) as u16),
+            (LeafKind::U32, Column::U32(values)) => values.push(be_u64(bytes) as u32),
+            (LeafKind::U64, Column::U64(values)) => values.push(be_u64(bytes)),
+            (LeafKind::U128, Column::U128(values)) => values.push(u128::from_be_bytes(
+                bytes.try_into().expect("leaf is 16 bytes wide"),
+            )),
+            (LeafKind::U256, Column::U256(values)) => values.push(U256::from_big_endian(bytes)),
+            (LeafKind::B256, Column::B256(values)) => {
+                values.push(bytes.try_into().expect("leaf is 32 bytes wide"))
+            }
+            (LeafKind::StringArray(_), Column::String(values)) => {
+                values.push(str::from_utf8(bytes)?.to_string())
+            }
+            _ => unreachable!("columns are created from the kind of their leaf"),
+        }
+
+        Ok(())
+    }
+}
+
+fn be_u64(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes(bytes.try_into().expect("leaf is a word wide"))
+}
+
+/// Walks `param_type` following the layout read by `ABIDecoder::decode`: struct and tuple
+/// fields start on a word boundary, array elements follow each other without padding and
+/// `u16`s and `u32`s take a whole word. Returns the number of bytes the type takes.
+fn collect_leaves(
+    param_type: &ParamType,
+    path: String,
+    offset: usize,
+    depth: usize,
+    config: &DecoderConfig,
+    leaves: &mut Vec<Leaf>,
+) -> Result<usize> {
+    if depth > config.max_depth {
+        return Err(error!(
+            Codec,
+            "depth limit `{}` reached while decoding. Try increasing it", config.max_depth
+        ));
+    }
+
+    let mut leaf = |kind: LeafKind, width: usize| -> Result<usize> {
+        if leaves.len() == config.max_tokens {
+            return Err(error!(
+                Codec,
+                "token limit `{}` reached while decoding. Try increasing it", config.max_tokens
+            ));
+        }
+        leaves.push(Leaf {
+            path: path.clone(),
+            offset,
+            kind,
+        });
+
+        Ok(width)
+    };
+
+    match param_type {
+        ParamType::Unit => leaf(LeafKind::Unit, 1),
+        ParamType::Bool => leaf(LeafKind::Bool, 1),
+        ParamType::U8 => leaf(LeafKind::U8, 1),
+        ParamType::U16 => leaf(LeafKind::U16, WORD_SIZE),
+        ParamType::U32 => leaf(LeafKind::U32, WORD_SIZE),
+        ParamType::U64 => leaf(LeafKind::U64, WORD_SIZE),
+        ParamType::U128 => leaf(LeafKind::U128, 2 * WORD_SIZE),
+        ParamType::U256 => leaf(LeafKind::U256, 4 * WORD_SIZE),
+        ParamType::B256 => leaf(LeafKind::B256, 4 * WORD_SIZE),
+        ParamType::StringArray(len) => leaf(
+            LeafKind::StringArray(*len),
+            checked_round_up_to_word_alignment(*len)?,
+        ),
+        ParamType::Array(element, len) => {
+            let mut width = 0;
+            for index in 0..*len {
+                width += collect_leaves(
+                    element,
+                    join(&path, &index.to_string()),
+                    offset + width,
+                    depth + 1,
+                    config,
+                    leaves,
+                )?;
+            }
+
+            Ok(width)
+        }
+        ParamType::Tuple(elements) => {
+            let named = elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| (index.to_string(), element));
+
+            collect_aligned_leaves(named, &path, offset, depth, config, leaves)
+        }
+        ParamType::Struct { fields, .. } => {
+            let named = fields.iter().map(|(name, field)| (name.clone(), field));
+
+            collect_aligned_leaves(named, &path, offset, depth, config, leaves)
+        }
+        _ => Err(error!(
+            Codec,
+            "columnar decoding needs a type of a fixed layout, but `{}` holds `{param_type:?}`",
+            if path.is_empty() {
+                "the record"
+            } else {
+                path.as_str()
+            }
+        )),
+    }
+}
+
+fn collect_aligned_leaves<'a>(
+    fields: impl Iterator<Item = (String, &'a ParamType)>,
+    path: &str,
+    offset: usize,
+    depth: usize,
+    config: &DecoderConfig,
+    leaves: &mut Vec<Leaf>,
+) -> Result<usize> {
+    let mut width = 0;
+    for (name, field) in fields {
+        width = checked_round_up_to_word_alignment(width)?;
+        width += collect_leaves(
+            field,
+            join(path, &name),
+            offset + width,
+            depth + 1,
+            config,
+            leaves,
+        )?;
+    }
+
+    Ok(width)
+}
+
+fn join(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        codec::{ABIDecoder, ABIEncoder},
+        types::{param_types::EnumVariants, StaticStringToken, Token},
+    };
+
+    fn order_type() -> ParamType {
+        ParamType::Struct {
+            name: "Order".to_string(),
+            fields: vec![
+                ("id".to_string(), ParamType::U32),
+                ("filled".to_string(), ParamType::Bool),
+                (
+                    "legs".to_string(),
+                    ParamType::Array(Box::new(ParamType::U8), 2),
+                ),
+                (
+                    "pair".to_string(),
+                    ParamType::Tuple(vec![ParamType::StringArray(3), ParamType::U128]),
+                ),
+            ],
+            generics: vec![],
+        }
+    }
+
+    fn order(id: u32) -> Token {
+        Token::Struct(vec![
+            Token::U32(id),
+            Token::Bool(id % 2 == 0),
+            Token::Array(vec![Token::U8(id as u8), Token::U8(id as u8 + 1)]),
+            Token::Tuple(vec![
+                Token::StringArray(StaticStringToken::new("eth".to_string(), Some(3))),
+                Token::U128(u128::from(id) << 64),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn records_are_decoded_into_columns() -> Result<()> {
+        let param_type = order_type();
+        let records = (0..3)
+            .map(|id| Ok(ABIEncoder::default().encode(&[order(id)])?.resolve(0)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let columns = ABIDecoder::default().decode_columns(&param_type, &records)?;
+
+        let expected = vec![
+            ("id".to_string(), Column::U32(vec![0, 1, 2])),
+            ("filled".to_string(), Column::Bool(vec![true, false, true])),
+            ("legs.0".to_string(), Column::U8(vec![0, 1, 2])),
+            ("legs.1".to_string(), Column::U8(vec![1, 2, 3])),
+            ("pair.0".to_string(), Column::String(vec!["eth".into(); 3])),
+            (
+                "pair.1".to_string(),
+                Column::U128(vec![0, 1 << 64, 2 << 64]),
+            ),
+        ];
+        assert_eq!(columns, expected);
+
+        for (record, id) in records.iter().zip(0..) {
+            assert_eq!(
+                ABIDecoder::default().decode(&param_type, record)?,
+                order(id)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn types_without_a_fixed_layout_are_rejected() -> Result<()> {
+        let param_type = ParamType::Struct {
+            name: "Wrapper".to_string(),
+            fields: vec![(
+                "choice".to_string(),
+                ParamType::Enum {
+                    name: "Choice".to_string(),
+                    enum_variants: EnumVariants::new(vec![("A".to_string(), ParamType::U64)])?,
+                    generics: vec![],
+                },
+            )],
+            generics: vec![],
+        };
+
+        let err = ABIDecoder::default()
+            .decode_columns(&param_type, Vec::<Vec<u8>>::new())
+            .expect_err("should fail because enums have no fixed layout");
+
+        assert!(err.to_string().contains("`choice` holds"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn short_records_are_reported() {
+        let err = ABIDecoder::default()
+            .decode_columns(&ParamType::U64, [[0u8; 4]])
+            .expect_err("should fail because a u64 needs 8 bytes");
+
+        assert!(err.to_string().contains("record of 4 bytes is too short"));
+    }
+}
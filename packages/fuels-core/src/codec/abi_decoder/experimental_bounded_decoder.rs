@@ -1,7 +1,7 @@
 use std::{iter::repeat, str};
 
 use crate::{
-    codec::DecoderConfig,
+    codec::{utils::Deadline, DecoderConfig},
     constants::WORD_SIZE,
     types::{
         errors::{error, Result},
@@ -15,6 +15,7 @@ use crate::{
 pub(crate) struct ExperimentalBoundedDecoder {
     depth_tracker: CounterWithLimit,
     token_tracker: CounterWithLimit,
+    deadline: Deadline,
 }
 
 const U8_BYTES_SIZE: usize = 1;
@@ -34,6 +35,7 @@ impl ExperimentalBoundedDecoder {
         Self {
             depth_tracker,
             token_tracker,
+            deadline: Deadline::new(config.deadline),
         }
     }
 
@@ -64,6 +66,7 @@ impl ExperimentalBoundedDecoder {
 
     fn decode_param(&mut self, param_type: &ParamType, bytes: &[u8]) -> Result<Decoded> {
         self.token_tracker.increase()?;
+        self.deadline.check()?;
         match param_type {
             ParamType::Unit => Self::decode_unit(),
             ParamType::Bool => Self::decode_bool(bytes),
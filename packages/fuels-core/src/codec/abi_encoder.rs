@@ -1,8 +1,22 @@
 mod bounded_encoder;
-use std::default::Default;
+mod buffer_pool;
+mod experimental_encoder;
+#[cfg(feature = "codec-telemetry")]
+mod telemetry;
+mod trace;
+
+use std::{default::Default, ops::Range, slice, sync::Arc};
+
+#[cfg(feature = "codec-telemetry")]
+pub use telemetry::*;
+pub use trace::*;
 
 use crate::{
-    codec::abi_encoder::bounded_encoder::BoundedEncoder,
+    codec::abi_encoder::{
+        bounded_encoder::BoundedEncoder, buffer_pool::BufferPool,
+        experimental_encoder::ExperimentalEncoder,
+    },
+    error,
     types::{errors::Result, unresolved_bytes::UnresolvedBytes, Token},
 };
 
@@ -17,6 +31,9 @@ pub struct EncoderConfig {
     /// The total memory size of the top-level token must fit in the available memory of the
     /// system.
     pub max_total_enum_width: usize,
+    /// Pads the data of every vector and string slice to a multiple of 8 bytes so that all
+    /// dynamic data, which is placed after the inline data, starts on a word boundary.
+    pub align_dynamic_data: bool,
 }
 
 // ANCHOR: default_encoder_config
@@ -26,55 +43,244 @@ impl Default for EncoderConfig {
             max_depth: 45,
             max_tokens: 10_000,
             max_total_enum_width: 10_000,
+            align_dynamic_data: false,
         }
     }
 }
 // ANCHOR_END: default_encoder_config
 
+/// The layout tokens are encoded into, see [`ABIEncoder::encode_versioned`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EncodingVersion {
+    /// Word-aligned values, padded enums and heap data placed after the inline data. Used by
+    /// contracts built without experimental encoding.
+    ///
+    /// This is the default even with the `experimental` cfg, which so far only switches how
+    /// logs are decoded. Contract calls are still encoded in this layout.
+    #[default]
+    Stable,
+    /// Values of their natural width without padding and heap data inline, as read by
+    /// `ABIDecoder::experimental_decode`.
+    Experimental,
+}
+
+/// The memory available to the VM. Nothing larger can be encoded for it.
+const VM_MEMORY_SIZE: usize = 64 * 1024 * 1024;
+/// Tokens are encoded recursively, so a much deeper nesting risks overflowing the stack.
+const MAX_SENSIBLE_DEPTH: usize = 1_000;
+
+impl EncoderConfig {
+    /// Fails if a limit is zero, since the encoder could then only encode trivial tokens, if
+    /// anything. Otherwise returns notes about limits that are most likely misconfigured,
+    /// e.g. a depth that can never be reached because of a lower token limit.
+    pub fn validate(&self) -> Result<Vec<String>> {
+        for (name, value) in [
+            ("max_depth", self.max_depth),
+            ("max_tokens", self.max_tokens),
+            ("max_total_enum_width", self.max_total_enum_width),
+        ] {
+            if value == 0 {
+                return Err(error!(Codec, "`{name}` must be greater than zero"));
+            }
+        }
+
+        let mut notes = vec![];
+
+        if self.max_tokens < self.max_depth {
+            notes.push(format!(
+                "`max_depth` ({}) can never be reached since every level of nesting counts \
+                towards `max_tokens` ({})",
+                self.max_depth, self.max_tokens
+            ));
+        }
+        if self.max_depth > MAX_SENSIBLE_DEPTH {
+            notes.push(format!(
+                "`max_depth` ({}) is above {MAX_SENSIBLE_DEPTH}. Encoding such deeply nested \
+                tokens may overflow the stack",
+                self.max_depth
+            ));
+        }
+        if self.max_tokens > VM_MEMORY_SIZE {
+            notes.push(format!(
+                "`max_tokens` ({}) allows more tokens than the VM memory ({VM_MEMORY_SIZE} \
+                bytes) can hold",
+                self.max_tokens
+            ));
+        }
+        if self.max_total_enum_width > VM_MEMORY_SIZE {
+            notes.push(format!(
+                "`max_total_enum_width` ({}) is larger than the VM memory ({VM_MEMORY_SIZE} \
+                bytes)",
+                self.max_total_enum_width
+            ));
+        }
+
+        Ok(notes)
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct ABIEncoder {
     pub config: EncoderConfig,
+    buffer_pool: Option<Arc<BufferPool>>,
+    #[cfg(feature = "codec-telemetry")]
+    telemetry: Option<Arc<EncoderTelemetry>>,
 }
 
 impl ABIEncoder {
     pub fn new(config: EncoderConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            buffer_pool: None,
+            #[cfg(feature = "codec-telemetry")]
+            telemetry: None,
+        }
+    }
+
+    /// Reuses the scratch buffers the encoder gathers inline bytes into across calls to
+    /// [`encode`](Self::encode), instead of growing new ones every time. Clones of the encoder
+    /// share the pool, so keep one around when encoding many calls, possibly from several
+    /// threads. Only the stable layout is affected.
+    pub fn with_buffer_pool(mut self) -> Self {
+        self.buffer_pool = Some(Arc::default());
+        self
+    }
+
+    /// Records every limit hit while encoding into `telemetry`.
+    #[cfg(feature = "codec-telemetry")]
+    pub fn with_telemetry(mut self, telemetry: Arc<EncoderTelemetry>) -> Self {
+        self.telemetry = Some(telemetry);
+        self
     }
 
     /// Encodes `Token`s in `args` following the ABI specs defined
     /// [here](https://github.com/FuelLabs/fuel-specs/blob/master/specs/protocol/abi.md)
+    /// using the default [`EncodingVersion`].
     pub fn encode(&self, args: &[Token]) -> Result<UnresolvedBytes> {
-        BoundedEncoder::new(self.config, false).encode(args)
+        self.encode_versioned(args, EncodingVersion::default())
+    }
+
+    /// Same as [`encode`](Self::encode) but in the layout given by `version`, e.g. to call
+    /// contracts built by both older and newer toolchains from the same binary.
+    pub fn encode_versioned(
+        &self,
+        args: &[Token],
+        version: EncodingVersion,
+    ) -> Result<UnresolvedBytes> {
+        match version {
+            EncodingVersion::Stable => {
+                let mut encoder = BoundedEncoder::new(self.config, false);
+                encoder.set_buffer_pool(self.buffer_pool.clone());
+                #[cfg(feature = "codec-telemetry")]
+                encoder.set_telemetry(self.telemetry.clone());
+
+                encoder.encode(args)
+            }
+            EncodingVersion::Experimental => {
+                let mut encoder = ExperimentalEncoder::new(self.config);
+                #[cfg(feature = "codec-telemetry")]
+                encoder.set_telemetry(self.telemetry.clone());
+
+                encoder.encode(args)
+            }
+        }
+    }
+
+    /// Same as [`encode`](Self::encode) but also returns where each argument ends up in the
+    /// resolved bytes, as `(argument index, byte range)` pairs ordered by offset. An argument
+    /// holding dynamic data, e.g. a vector, has a second range covering its share of the
+    /// dynamic data placed after all of the inline data. The ranges don't depend on the
+    /// address the bytes are resolved at.
+    pub fn encode_with_layout(
+        &self,
+        args: &[Token],
+    ) -> Result<(UnresolvedBytes, Vec<(usize, Range<usize>)>)> {
+        let encoded = self.encode(args)?;
+
+        // Every argument starts word-aligned, so encoding it on its own yields the same chunks
+        // as it has within `encoded`.
+        let sizes = args
+            .iter()
+            .map(|arg| {
+                let encoded_arg =
+                    self.encode_versioned(slice::from_ref(arg), EncodingVersion::default())?;
+                let inline_len = encoded_arg.inline_len();
+
+                Ok((inline_len, encoded_arg.resolve(0).len() - inline_len))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut inline_offset = 0;
+        let mut dynamic_offset = sizes.iter().map(|(inline_len, _)| inline_len).sum();
+        let mut inline_ranges = vec![];
+        let mut dynamic_ranges = vec![];
+        for (index, (inline_len, dynamic_len)) in sizes.into_iter().enumerate() {
+            inline_ranges.push((index, inline_offset..inline_offset + inline_len));
+            inline_offset += inline_len;
+
+            if dynamic_len > 0 {
+                dynamic_ranges.push((index, dynamic_offset..dynamic_offset + dynamic_len));
+                dynamic_offset += dynamic_len;
+            }
+        }
+        inline_ranges.extend(dynamic_ranges);
+
+        Ok((encoded, inline_ranges))
+    }
+
+    /// Same as [`encode`](Self::encode) but also returns an [`EncodeTrace`] recording the bytes
+    /// each argument produced and at which offset, including its dynamic data. Print it to
+    /// see how the arguments were laid out, e.g. when a contract rejects a call.
+    pub fn encode_with_trace(&self, args: &[Token]) -> Result<(UnresolvedBytes, EncodeTrace)> {
+        let (encoded, layout) = self.encode_with_layout(args)?;
+        let trace = EncodeTrace::new(&encoded.resolve(0), layout);
+
+        Ok((encoded, trace))
     }
 }
 
 #[derive(Default, Clone, Debug)]
 pub struct ConfigurablesEncoder {
     pub config: EncoderConfig,
+    #[cfg(feature = "codec-telemetry")]
+    telemetry: Option<Arc<EncoderTelemetry>>,
 }
 
 impl ConfigurablesEncoder {
     pub fn new(config: EncoderConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            #[cfg(feature = "codec-telemetry")]
+            telemetry: None,
+        }
+    }
+
+    /// Records every limit hit while encoding into `telemetry`.
+    #[cfg(feature = "codec-telemetry")]
+    pub fn with_telemetry(mut self, telemetry: Arc<EncoderTelemetry>) -> Self {
+        self.telemetry = Some(telemetry);
+        self
     }
 
     /// Encodes `Token`s in `args` following the ABI specs defined
     /// [here](https://github.com/FuelLabs/fuel-specs/blob/master/specs/protocol/abi.md)
     pub fn encode(&self, args: &[Token]) -> Result<UnresolvedBytes> {
-        BoundedEncoder::new(self.config, true).encode(args)
+        let mut encoder = BoundedEncoder::new(self.config, true);
+        #[cfg(feature = "codec-telemetry")]
+        encoder.set_telemetry(self.telemetry.clone());
+
+        encoder.encode(args)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::slice;
-
     use itertools::chain;
     use sha2::{Digest, Sha256};
 
     use super::*;
     use crate::{
-        codec::first_four_bytes_of_sha256_hash,
+        codec::{first_four_bytes_of_sha256_hash, token_trees},
         constants::WORD_SIZE,
         to_named,
         types::{
@@ -87,6 +293,40 @@ mod tests {
     const VEC_METADATA_SIZE: usize = 3 * WORD_SIZE;
     const DISCRIMINANT_SIZE: usize = WORD_SIZE;
 
+    #[test]
+    fn encoder_config_rejects_zero_limits() {
+        let config = EncoderConfig {
+            max_tokens: 0,
+            ..Default::default()
+        };
+
+        let err = config.validate().expect_err("should fail on a zero limit");
+
+        assert_eq!(
+            err.to_string(),
+            "codec: `max_tokens` must be greater than zero"
+        );
+    }
+
+    #[test]
+    fn encoder_config_notes_unreachable_and_extreme_limits() -> Result<()> {
+        assert!(EncoderConfig::default().validate()?.is_empty());
+
+        let config = EncoderConfig {
+            max_depth: 2_000,
+            max_tokens: 100,
+            ..Default::default()
+        };
+
+        let notes = config.validate()?;
+
+        assert_eq!(notes.len(), 2);
+        assert!(notes[0].contains("can never be reached"));
+        assert!(notes[1].contains("may overflow the stack"));
+
+        Ok(())
+    }
+
     #[test]
     fn encode_function_signature() {
         let fn_signature = "entry_one(u64)";
@@ -893,6 +1133,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn dynamic_data_can_be_word_aligned() -> Result<()> {
+        // arrange
+        let offset: u8 = 40;
+        let vec_1 = Token::Vector(vec![Token::U8(1), Token::U8(2), Token::U8(3)]);
+        let vec_2 = Token::Vector(vec![Token::U8(4)]);
+        let encoder = ABIEncoder::new(EncoderConfig {
+            align_dynamic_data: true,
+            ..Default::default()
+        });
+
+        // act
+        let result = encoder.encode(&[vec_1, vec_2])?.resolve(offset as u64);
+
+        // assert
+        let vec1_data_offset = 6 * WORD_SIZE as u8 + offset;
+        let vec1_ptr = [0, 0, 0, 0, 0, 0, 0, vec1_data_offset];
+        let vec1_cap = [0, 0, 0, 0, 0, 0, 0, 3];
+        let vec1_len = [0, 0, 0, 0, 0, 0, 0, 3];
+        let vec1_data = [1, 2, 3, 0, 0, 0, 0, 0];
+
+        let vec2_data_offset = vec1_data_offset + vec1_data.len() as u8;
+        let vec2_ptr = [0, 0, 0, 0, 0, 0, 0, vec2_data_offset];
+        let vec2_cap = [0, 0, 0, 0, 0, 0, 0, 1];
+        let vec2_len = [0, 0, 0, 0, 0, 0, 0, 1];
+        let vec2_data = [4, 0, 0, 0, 0, 0, 0, 0];
+
+        let expected = chain!(
+            vec1_ptr, vec1_cap, vec1_len, vec2_ptr, vec2_cap, vec2_len, vec1_data, vec2_data,
+        )
+        .collect::<Vec<_>>();
+
+        assert_eq!(result, expected);
+        assert_eq!(vec2_data_offset as usize % WORD_SIZE, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_data_is_not_aligned_by_default() -> Result<()> {
+        let vec_1 = Token::Vector(vec![Token::U8(1), Token::U8(2), Token::U8(3)]);
+        let vec_2 = Token::Vector(vec![Token::U8(4)]);
+
+        let result = ABIEncoder::default().encode(&[vec_1, vec_2])?.resolve(0);
+
+        let vec2_ptr = &result[3 * WORD_SIZE..4 * WORD_SIZE];
+        assert_eq!(vec2_ptr, [0, 0, 0, 0, 0, 0, 0, 6 * WORD_SIZE as u8 + 3]);
+        assert_eq!(result[6 * WORD_SIZE..], [1, 2, 3, 4]);
+
+        Ok(())
+    }
+
     #[test]
     fn a_vec_in_an_enum() -> Result<()> {
         // arrange
@@ -1205,6 +1497,91 @@ mod tests {
         Token::Enum(Box::new(selector))
     }
 
+    #[test]
+    fn layout_covers_inline_and_dynamic_data_of_each_argument() -> Result<()> {
+        let args = [
+            Token::U8(1),
+            Token::Vector(vec![Token::U64(2), Token::U64(3)]),
+            Token::U64(4),
+        ];
+
+        let (encoded, layout) = ABIEncoder::default().encode_with_layout(&args)?;
+        let resolved = encoded.resolve(100);
+
+        let inline_len = 2 * WORD_SIZE + VEC_METADATA_SIZE;
+        let expected = vec![
+            (0, 0..WORD_SIZE),
+            (1, WORD_SIZE..WORD_SIZE + VEC_METADATA_SIZE),
+            (2, WORD_SIZE + VEC_METADATA_SIZE..inline_len),
+            (1, inline_len..inline_len + 2 * WORD_SIZE),
+        ];
+        assert_eq!(layout, expected);
+        assert_eq!(resolved.len(), inline_len + 2 * WORD_SIZE);
+        assert_eq!(resolved[layout[2].1.clone()], 4u64.to_be_bytes());
+        assert_eq!(
+            resolved[layout[3].1.clone()],
+            [2u64.to_be_bytes(), 3u64.to_be_bytes()].concat()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn trace_maps_the_bytes_of_each_argument() -> Result<()> {
+        let args = [
+            Token::U8(1),
+            Token::Vector(vec![Token::U64(2), Token::U64(3)]),
+        ];
+
+        let (encoded, trace) = ABIEncoder::default().encode_with_trace(&args)?;
+
+        let inline_len = WORD_SIZE + VEC_METADATA_SIZE;
+        assert_eq!(encoded.resolve(0).len(), inline_len + 2 * WORD_SIZE);
+
+        let vector: Vec<_> = trace.of_argument(1).collect();
+        assert_eq!(vector.len(), 2);
+        assert_eq!(vector[0].placement, Placement::Inline);
+        assert_eq!(vector[0].offset, WORD_SIZE);
+        // The pointer to the elements, resolved at address 0
+        assert_eq!(
+            vector[0].bytes[..WORD_SIZE],
+            (inline_len as u64).to_be_bytes()
+        );
+        assert_eq!(vector[1].placement, Placement::Dynamic);
+        assert_eq!(vector[1].offset, inline_len);
+
+        assert_eq!(
+            trace.to_string(),
+            "arg 0 inline [0..8]: 0100000000000000\n\
+             arg 1 inline [8..32]: 0000000000000020 0000000000000002 0000000000000002\n\
+             arg 1 dynamic [32..48]: 0000000000000002 0000000000000003\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn pooled_encoder_encodes_like_the_default_one() -> Result<()> {
+        let pooled = ABIEncoder::default().with_buffer_pool();
+
+        for tokens in [
+            token_trees::flat_scalars(100),
+            vec![token_trees::nested_structs(10)],
+            vec![token_trees::large_vector(100)],
+            token_trees::many_dynamic_segments(100),
+            vec![token_trees::mixed_struct()],
+        ] {
+            let expected = ABIEncoder::default().encode(&tokens)?.resolve(0);
+
+            // the second round draws the buffers returned by the first one
+            for _ in 0..2 {
+                assert_eq!(pooled.encode(&tokens)?.resolve(0), expected);
+            }
+        }
+
+        Ok(())
+    }
+
     fn nested_array(depth: usize) -> Token {
         if depth == 1 {
             Token::Array(vec![Token::U8(255)])
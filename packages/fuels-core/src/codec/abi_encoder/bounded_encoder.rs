@@ -1,8 +1,13 @@
+use std::sync::Arc;
+
 use fuel_types::bytes::padded_len_usize;
 
+#[cfg(feature = "codec-telemetry")]
+use crate::codec::{EncoderLimit, EncoderTelemetry};
 use crate::{
     checked_round_up_to_word_alignment,
     codec::{
+        abi_encoder::buffer_pool::BufferPool,
         utils::{CodecDirection, CounterWithLimit},
         EncoderConfig,
     },
@@ -20,6 +25,10 @@ pub(crate) struct BoundedEncoder {
     depth_tracker: CounterWithLimit,
     token_tracker: CounterWithLimit,
     max_total_enum_width: usize,
+    align_dynamic_data: bool,
+    buffer_pool: Option<Arc<BufferPool>>,
+    #[cfg(feature = "codec-telemetry")]
+    telemetry: Option<Arc<EncoderTelemetry>>,
 }
 
 impl BoundedEncoder {
@@ -32,7 +41,53 @@ impl BoundedEncoder {
             depth_tracker,
             token_tracker,
             max_total_enum_width: config.max_total_enum_width,
+            align_dynamic_data: config.align_dynamic_data,
             used_for_configurables,
+            buffer_pool: None,
+            #[cfg(feature = "codec-telemetry")]
+            telemetry: None,
+        }
+    }
+
+    pub(crate) fn set_buffer_pool(&mut self, buffer_pool: Option<Arc<BufferPool>>) {
+        self.buffer_pool = buffer_pool;
+    }
+
+    /// A writer for the data of a token list. Its inline bytes grow in a scratch buffer drawn
+    /// from the pool, if any, so that scalars and padding don't allocate.
+    fn chunks(&self) -> Chunks {
+        let inline = self
+            .buffer_pool
+            .as_ref()
+            .map(|pool| pool.take())
+            .unwrap_or_default();
+
+        Chunks {
+            data: vec![],
+            inline,
+            len: 0,
+        }
+    }
+
+    /// Flushes the pending inline bytes and returns the scratch buffer to the pool.
+    fn finish(&self, mut chunks: Chunks) -> Vec<Data> {
+        chunks.flush_inline();
+        if let Some(pool) = &self.buffer_pool {
+            pool.give_back(chunks.inline);
+        }
+
+        chunks.data
+    }
+
+    #[cfg(feature = "codec-telemetry")]
+    pub(crate) fn set_telemetry(&mut self, telemetry: Option<Arc<EncoderTelemetry>>) {
+        self.telemetry = telemetry;
+    }
+
+    #[cfg(feature = "codec-telemetry")]
+    fn record_limit_hit(&self, limit: EncoderLimit) {
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.record(limit);
         }
     }
 
@@ -44,56 +99,70 @@ impl BoundedEncoder {
         let data = if args.len() == 1 {
             match args[0] {
                 Token::U8(arg_u8) if self.used_for_configurables => {
-                    vec![Self::encode_u8_as_byte(arg_u8)]
+                    vec![Data::Inline(vec![arg_u8])]
                 }
-                Token::U8(arg_u8) => vec![Self::encode_u8_as_u64(arg_u8)],
+                Token::U8(arg_u8) => vec![Data::Inline(Self::encode_u8_as_u64(arg_u8).to_vec())],
                 Token::Bool(arg_bool) if self.used_for_configurables => {
-                    vec![Self::encode_bool_as_byte(arg_bool)]
+                    vec![Data::Inline(vec![u8::from(arg_bool)])]
                 }
                 Token::Bool(arg_bool) => {
-                    vec![Self::encode_bool_as_u64(arg_bool)]
+                    vec![Data::Inline(Self::encode_bool_as_u64(arg_bool).to_vec())]
                 }
-                _ => self.encode_tokens(args, true)?,
+                _ => self.encode_list(args, true)?,
             }
         } else {
-            self.encode_tokens(args, true)?
+            self.encode_list(args, true)?
         };
 
         Ok(UnresolvedBytes::new(data))
     }
 
-    fn encode_tokens(&mut self, tokens: &[Token], word_aligned: bool) -> Result<Vec<Data>> {
-        let mut offset_in_bytes = 0;
-        let mut data = vec![];
+    fn encode_list(&mut self, tokens: &[Token], word_aligned: bool) -> Result<Vec<Data>> {
+        let mut chunks = self.chunks();
+        self.encode_tokens(tokens, word_aligned, &mut chunks)?;
+
+        Ok(self.finish(chunks))
+    }
+
+    fn encode_tokens(
+        &mut self,
+        tokens: &[Token],
+        word_aligned: bool,
+        out: &mut Chunks,
+    ) -> Result<()> {
+        let start = out.len;
 
         for token in tokens {
-            self.token_tracker.increase()?;
-            let mut new_data = self.encode_token(token)?;
-            offset_in_bytes += new_data.iter().map(Data::size_in_bytes).sum::<usize>();
+            let token_count = self.token_tracker.increase();
+            #[cfg(feature = "codec-telemetry")]
+            if token_count.is_err() {
+                self.record_limit_hit(EncoderLimit::Tokens);
+            }
+            token_count?;
 
-            data.append(&mut new_data);
+            self.encode_token(token, out)?;
 
             if word_aligned {
-                let padding = vec![
-                    0u8;
-                    checked_round_up_to_word_alignment(offset_in_bytes)?
-                        - offset_in_bytes
-                ];
-                if !padding.is_empty() {
-                    offset_in_bytes += padding.len();
-                    data.push(Data::Inline(padding));
-                }
+                let offset_in_bytes = out.len - start;
+                out.push_zeros(
+                    checked_round_up_to_word_alignment(offset_in_bytes)? - offset_in_bytes,
+                );
             }
         }
 
-        Ok(data)
+        Ok(())
     }
 
     fn run_w_depth_tracking(
         &mut self,
-        encoder: impl FnOnce(&mut Self) -> Result<Vec<Data>>,
-    ) -> Result<Vec<Data>> {
-        self.depth_tracker.increase()?;
+        encoder: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<()> {
+        let depth = self.depth_tracker.increase();
+        #[cfg(feature = "codec-telemetry")]
+        if depth.is_err() {
+            self.record_limit_hit(EncoderLimit::Depth);
+        }
+        depth?;
 
         let res = encoder(self);
 
@@ -101,101 +170,59 @@ impl BoundedEncoder {
         res
     }
 
-    fn encode_token(&mut self, arg: &Token) -> Result<Vec<Data>> {
-        let encoded_token = match arg {
-            Token::Unit => vec![Self::encode_unit()],
-            Token::U8(arg_u8) => vec![Self::encode_u8_as_byte(*arg_u8)],
-            Token::U16(arg_u16) => vec![Self::encode_u16(*arg_u16)],
-            Token::U32(arg_u32) => vec![Self::encode_u32(*arg_u32)],
-            Token::U64(arg_u64) => vec![Self::encode_u64(*arg_u64)],
-            Token::U128(arg_u128) => vec![Self::encode_u128(*arg_u128)],
-            Token::U256(arg_u256) => vec![Self::encode_u256(*arg_u256)],
-            Token::Bool(arg_bool) => vec![Self::encode_bool_as_byte(*arg_bool)],
-            Token::B256(arg_bits256) => vec![Self::encode_b256(arg_bits256)],
-            Token::RawSlice(data) => Self::encode_raw_slice(data.clone())?,
-            Token::StringSlice(arg_string) => Self::encode_string_slice(arg_string)?,
-            Token::StringArray(arg_string) => vec![Self::encode_string_array(arg_string)?],
+    fn encode_token(&mut self, arg: &Token, out: &mut Chunks) -> Result<()> {
+        match arg {
+            Token::Unit => out.push_zeros(1),
+            Token::U8(arg_u8) => out.push_inline(&[*arg_u8]),
+            Token::U16(arg_u16) => out.push_inline(&pad_u16(*arg_u16)),
+            Token::U32(arg_u32) => out.push_inline(&pad_u32(*arg_u32)),
+            Token::U64(arg_u64) => out.push_inline(&arg_u64.to_be_bytes()),
+            Token::U128(arg_u128) => out.push_inline(&arg_u128.to_be_bytes()),
+            Token::U256(arg_u256) => out.push_inline(&Self::encode_u256(*arg_u256)),
+            Token::Bool(arg_bool) => out.push_inline(&[u8::from(*arg_bool)]),
+            Token::B256(arg_bits256) => out.push_inline(arg_bits256),
+            Token::RawSlice(data) => {
+                out.push_dynamic(Self::padded_segment(data));
+                out.push_inline(&(data.len() as u64).to_be_bytes());
+            }
+            Token::StringSlice(arg_string) => self.encode_string_slice(arg_string, out)?,
+            Token::StringArray(arg_string) => Self::encode_string_array(arg_string, out)?,
             Token::Array(arg_array) => {
-                self.run_w_depth_tracking(|ctx| ctx.encode_array(arg_array))?
+                self.run_w_depth_tracking(|ctx| ctx.encode_tokens(arg_array, false, out))?
             }
-            Token::Struct(arg_struct) => {
-                self.run_w_depth_tracking(|ctx| ctx.encode_struct(arg_struct))?
+            Token::Struct(arg_struct) | Token::Tuple(arg_struct) => {
+                self.run_w_depth_tracking(|ctx| ctx.encode_tokens(arg_struct, true, out))?
             }
-            Token::Enum(arg_enum) => self.run_w_depth_tracking(|ctx| ctx.encode_enum(arg_enum))?,
-            Token::Tuple(arg_tuple) => {
-                self.run_w_depth_tracking(|ctx| ctx.encode_tuple(arg_tuple))?
+            Token::Enum(arg_enum) => {
+                self.run_w_depth_tracking(|ctx| ctx.encode_enum(arg_enum, out))?
             }
-            Token::Vector(data) => self.run_w_depth_tracking(|ctx| ctx.encode_vector(data))?,
-            Token::Bytes(data) => Self::encode_bytes(data.to_vec())?,
+            Token::Vector(data) => self.run_w_depth_tracking(|ctx| ctx.encode_vector(data, out))?,
+            Token::Bytes(data) => Self::encode_bytes(data, out),
             // `String` in Sway has the same memory layout as the bytes type
-            Token::String(string) => Self::encode_bytes(string.clone().into_bytes())?,
+            Token::String(string) => Self::encode_bytes(string.as_bytes(), out),
         };
 
-        Ok(encoded_token)
-    }
-
-    fn encode_unit() -> Data {
-        Data::Inline(vec![0u8])
-    }
-
-    fn encode_tuple(&mut self, arg_tuple: &[Token]) -> Result<Vec<Data>> {
-        self.encode_tokens(arg_tuple, true)
-    }
-
-    fn encode_struct(&mut self, subcomponents: &[Token]) -> Result<Vec<Data>> {
-        self.encode_tokens(subcomponents, true)
-    }
-
-    fn encode_array(&mut self, arg_array: &[Token]) -> Result<Vec<Data>> {
-        self.encode_tokens(arg_array, false)
-    }
-
-    fn encode_b256(arg_bits256: &[u8; 32]) -> Data {
-        Data::Inline(arg_bits256.to_vec())
-    }
-
-    fn encode_bool_as_byte(arg_bool: bool) -> Data {
-        Data::Inline(vec![u8::from(arg_bool)])
-    }
-
-    fn encode_bool_as_u64(arg_bool: bool) -> Data {
-        Data::Inline(vec![0, 0, 0, 0, 0, 0, 0, u8::from(arg_bool)])
+        Ok(())
     }
 
-    fn encode_u128(arg_u128: u128) -> Data {
-        Data::Inline(arg_u128.to_be_bytes().to_vec())
+    fn encode_bool_as_u64(arg_bool: bool) -> [u8; 8] {
+        [0, 0, 0, 0, 0, 0, 0, u8::from(arg_bool)]
     }
 
-    fn encode_u256(arg_u256: U256) -> Data {
+    fn encode_u256(arg_u256: U256) -> [u8; 32] {
         let mut bytes = [0u8; 32];
         arg_u256.to_big_endian(&mut bytes);
-        Data::Inline(bytes.to_vec())
-    }
-
-    fn encode_u64(arg_u64: u64) -> Data {
-        Data::Inline(arg_u64.to_be_bytes().to_vec())
-    }
-
-    fn encode_u32(arg_u32: u32) -> Data {
-        Data::Inline(pad_u32(arg_u32).to_vec())
-    }
-
-    fn encode_u16(arg_u16: u16) -> Data {
-        Data::Inline(pad_u16(arg_u16).to_vec())
+        bytes
     }
 
-    fn encode_u8_as_byte(arg_u8: u8) -> Data {
-        Data::Inline(vec![arg_u8])
+    fn encode_u8_as_u64(arg_u8: u8) -> [u8; 8] {
+        [0, 0, 0, 0, 0, 0, 0, arg_u8]
     }
 
-    fn encode_u8_as_u64(arg_u8: u8) -> Data {
-        Data::Inline(vec![0, 0, 0, 0, 0, 0, 0, arg_u8])
-    }
-
-    fn encode_enum(&mut self, selector: &EnumSelector) -> Result<Vec<Data>> {
+    fn encode_enum(&mut self, selector: &EnumSelector, out: &mut Chunks) -> Result<()> {
         let (discriminant, token_within_enum, variants) = selector;
 
-        let mut encoded_enum = vec![Self::encode_discriminant(*discriminant)];
+        out.push_inline(&discriminant.to_be_bytes());
 
         // Enums that contain only Units as variants have only their discriminant encoded.
         if !variants.only_units_inside() {
@@ -203,6 +230,9 @@ impl BoundedEncoder {
             let enum_width_in_bytes = variants.compute_enum_width_in_bytes()?;
 
             if enum_width_in_bytes > self.max_total_enum_width {
+                #[cfg(feature = "codec-telemetry")]
+                self.record_limit_hit(EncoderLimit::TotalEnumWidth);
+
                 return Err(error!(
                     Codec,
                     "cannot encode enum with variants: {variants:?}. It is `{enum_width_in_bytes}` bytes wide. Try increasing maximum total enum width."
@@ -210,21 +240,16 @@ impl BoundedEncoder {
             }
             let padding_amount = variants.compute_padding_amount_in_bytes(variant_param_type)?;
 
-            encoded_enum.push(Data::Inline(vec![0; padding_amount]));
-
-            let token_data = self.encode_token(token_within_enum)?;
-            encoded_enum.extend(token_data);
+            out.push_zeros(padding_amount);
+            self.encode_token(token_within_enum, out)?;
         }
 
-        Ok(encoded_enum)
+        Ok(())
     }
 
-    fn encode_discriminant(discriminant: u64) -> Data {
-        Self::encode_u64(discriminant)
-    }
-
-    fn encode_vector(&mut self, data: &[Token]) -> Result<Vec<Data>> {
-        let encoded_data = self.encode_tokens(data, false)?;
+    fn encode_vector(&mut self, data: &[Token], out: &mut Chunks) -> Result<()> {
+        let mut elements = self.chunks();
+        self.encode_tokens(data, false, &mut elements)?;
         let cap = data.len() as u64;
         let len = data.len() as u64;
 
@@ -233,54 +258,95 @@ impl BoundedEncoder {
         // somewhere else. Hence the use of Data::Dynamic which will, when
         // resolved, leave behind in its place only a pointer to the actual
         // data.
-        Ok(vec![
-            Data::Dynamic(encoded_data),
-            Self::encode_u64(cap),
-            Self::encode_u64(len),
-        ])
+        let segment = self.dynamic_segment(elements);
+        out.push_dynamic(segment);
+        out.push_inline(&cap.to_be_bytes());
+        out.push_inline(&len.to_be_bytes());
+
+        Ok(())
     }
 
-    fn encode_raw_slice(mut data: Vec<u8>) -> Result<Vec<Data>> {
-        let len = data.len();
+    fn dynamic_segment(&self, mut data: Chunks) -> Data {
+        if self.align_dynamic_data {
+            data.push_zeros(padded_len_usize(data.len) - data.len);
+        }
+
+        Data::Dynamic(self.finish(data))
+    }
 
+    /// Heap data whose size is known upfront gets a single exactly sized allocation.
+    fn padded_segment(bytes: &[u8]) -> Data {
+        let mut data = Vec::with_capacity(padded_len_usize(bytes.len()));
+        data.extend_from_slice(bytes);
         zeropad_to_word_alignment(&mut data);
 
-        let encoded_data = vec![Data::Inline(data)];
+        Data::Dynamic(vec![Data::Inline(data)])
+    }
+
+    fn encode_string_slice(&self, arg_string: &StaticStringToken, out: &mut Chunks) -> Result<()> {
+        let encodable_str = arg_string.get_encodable_str()?;
 
-        Ok(vec![
-            Data::Dynamic(encoded_data),
-            Self::encode_u64(len as u64),
-        ])
+        let mut string = self.chunks();
+        string.push_inline(encodable_str.as_bytes());
+        let segment = self.dynamic_segment(string);
+
+        out.push_dynamic(segment);
+        out.push_inline(&(encodable_str.len() as u64).to_be_bytes());
+
+        Ok(())
     }
 
-    fn encode_string_slice(arg_string: &StaticStringToken) -> Result<Vec<Data>> {
+    fn encode_string_array(arg_string: &StaticStringToken, out: &mut Chunks) -> Result<()> {
         let encodable_str = arg_string.get_encodable_str()?;
 
-        let encoded_data = Data::Inline(encodable_str.as_bytes().to_vec());
-        let len = Self::encode_u64(encodable_str.len() as u64);
+        out.push_inline(encodable_str.as_bytes());
+        out.push_zeros(padded_len_usize(encodable_str.len()) - encodable_str.len());
 
-        Ok(vec![Data::Dynamic(vec![encoded_data]), len])
+        Ok(())
     }
 
-    fn encode_string_array(arg_string: &StaticStringToken) -> Result<Data> {
-        Ok(Data::Inline(crate::types::pad_string(
-            arg_string.get_encodable_str()?,
-        )))
+    fn encode_bytes(data: &[u8], out: &mut Chunks) {
+        let cap = padded_len_usize(data.len()) as u64;
+
+        out.push_dynamic(Self::padded_segment(data));
+        out.push_inline(&cap.to_be_bytes());
+        out.push_inline(&(data.len() as u64).to_be_bytes());
     }
+}
 
-    fn encode_bytes(mut data: Vec<u8>) -> Result<Vec<Data>> {
-        let len = data.len();
+/// The encoded data of a token list being written. Consecutive inline bytes are gathered in
+/// `inline` and copied out as one exactly sized `Data::Inline` when a dynamic segment follows
+/// or the list ends, so the scratch buffer keeps its capacity for the next run.
+struct Chunks {
+    data: Vec<Data>,
+    inline: Vec<u8>,
+    /// The size of everything written so far, pointers to dynamic data included.
+    len: usize,
+}
 
-        zeropad_to_word_alignment(&mut data);
+impl Chunks {
+    fn push_inline(&mut self, bytes: &[u8]) {
+        self.inline.extend_from_slice(bytes);
+        self.len += bytes.len();
+    }
 
-        let cap = data.len() as u64;
-        let encoded_data = vec![Data::Inline(data)];
+    fn push_zeros(&mut self, amount: usize) {
+        self.inline.resize(self.inline.len() + amount, 0);
+        self.len += amount;
+    }
+
+    fn push_dynamic(&mut self, segment: Data) {
+        self.flush_inline();
+        self.len += segment.size_in_bytes();
+        self.data.push(segment);
+    }
 
-        Ok(vec![
-            Data::Dynamic(encoded_data),
-            Self::encode_u64(cap),
-            Self::encode_u64(len as u64),
-        ])
+    fn flush_inline(&mut self) {
+        if !self.inline.is_empty() {
+            self.data
+                .push(Data::Inline(self.inline.as_slice().to_vec()));
+            self.inline.clear();
+        }
     }
 }
 
@@ -0,0 +1,54 @@
+use std::sync::Mutex;
+
+/// Buffers beyond this many are dropped instead of being kept around.
+const MAX_POOLED_BUFFERS: usize = 1_024;
+
+/// Scratch buffers the encoder gathers consecutive inline bytes into before copying them out
+/// in one exactly sized allocation. Returning them once a token list is encoded lets later
+/// encodings reuse their grown capacity. The pool is only locked once per list, never per
+/// scalar.
+#[derive(Debug, Default)]
+pub(crate) struct BufferPool {
+    buffers: Mutex<Vec<Vec<u8>>>,
+}
+
+impl BufferPool {
+    pub(crate) fn take(&self) -> Vec<u8> {
+        self.buffers
+            .lock()
+            .expect("buffer pool lock poisoned")
+            .pop()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn give_back(&self, mut buffer: Vec<u8>) {
+        if buffer.capacity() == 0 {
+            return;
+        }
+        buffer.clear();
+
+        let mut buffers = self.buffers.lock().expect("buffer pool lock poisoned");
+        if buffers.len() < MAX_POOLED_BUFFERS {
+            buffers.push(buffer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffers_are_reused_empty() {
+        let pool = BufferPool::default();
+        let mut buffer = pool.take();
+        buffer.extend([1, 2, 3]);
+        let capacity = buffer.capacity();
+
+        pool.give_back(buffer);
+        let reused = pool.take();
+
+        assert!(reused.is_empty());
+        assert_eq!(reused.capacity(), capacity);
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(feature = "codec-telemetry")]
+use std::sync::Arc;
+
+#[cfg(feature = "codec-telemetry")]
+use crate::codec::{EncoderLimit, EncoderTelemetry};
+use crate::{
+    codec::{
+        utils::{CodecDirection, CounterWithLimit},
+        EncoderConfig,
+    },
+    types::{
+        errors::Result,
+        unresolved_bytes::{Data, UnresolvedBytes},
+        EnumSelector, Token, U256,
+    },
+};
+
+/// Encodes `Token`s following the experimental layout, the one read by
+/// `ABIDecoder::experimental_decode`: values take their natural width without any padding,
+/// enums are a `u64` discriminant followed by the selected variant, and heap types are a `u64`
+/// length followed by their data, all inline.
+pub(crate) struct ExperimentalEncoder {
+    depth_tracker: CounterWithLimit,
+    token_tracker: CounterWithLimit,
+    #[cfg(feature = "codec-telemetry")]
+    telemetry: Option<Arc<EncoderTelemetry>>,
+}
+
+impl ExperimentalEncoder {
+    pub(crate) fn new(config: EncoderConfig) -> Self {
+        Self {
+            depth_tracker: CounterWithLimit::new(
+                config.max_depth,
+                "depth",
+                CodecDirection::Encoding,
+            ),
+            token_tracker: CounterWithLimit::new(
+                config.max_tokens,
+                "token",
+                CodecDirection::Encoding,
+            ),
+            #[cfg(feature = "codec-telemetry")]
+            telemetry: None,
+        }
+    }
+
+    #[cfg(feature = "codec-telemetry")]
+    pub(crate) fn set_telemetry(&mut self, telemetry: Option<Arc<EncoderTelemetry>>) {
+        self.telemetry = telemetry;
+    }
+
+    #[cfg(feature = "codec-telemetry")]
+    fn record_limit_hit(&self, limit: EncoderLimit) {
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.record(limit);
+        }
+    }
+
+    pub(crate) fn encode(&mut self, args: &[Token]) -> Result<UnresolvedBytes> {
+        let mut bytes = vec![];
+        self.encode_tokens(args, &mut bytes)?;
+
+        Ok(UnresolvedBytes::new(vec![Data::Inline(bytes)]))
+    }
+
+    fn encode_tokens(&mut self, tokens: &[Token], bytes: &mut Vec<u8>) -> Result<()> {
+        for token in tokens {
+            let token_count = self.token_tracker.increase();
+            #[cfg(feature = "codec-telemetry")]
+            if token_count.is_err() {
+                self.record_limit_hit(EncoderLimit::Tokens);
+            }
+            token_count?;
+
+            self.encode_token(token, bytes)?;
+        }
+
+        Ok(())
+    }
+
+    fn run_w_depth_tracking(
+        &mut self,
+        encoder: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<()> {
+        let depth = self.depth_tracker.increase();
+        #[cfg(feature = "codec-telemetry")]
+        if depth.is_err() {
+            self.record_limit_hit(EncoderLimit::Depth);
+        }
+        depth?;
+
+        let res = encoder(self);
+
+        self.depth_tracker.decrease();
+        res
+    }
+
+    fn encode_token(&mut self, token: &Token, bytes: &mut Vec<u8>) -> Result<()> {
+        match token {
+            Token::Unit => {}
+            Token::Bool(value) => bytes.push(u8::from(*value)),
+            Token::U8(value) => bytes.push(*value),
+            Token::U16(value) => bytes.extend(value.to_be_bytes()),
+            Token::U32(value) => bytes.extend(value.to_be_bytes()),
+            Token::U64(value) => bytes.extend(value.to_be_bytes()),
+            Token::U128(value) => bytes.extend(value.to_be_bytes()),
+            Token::U256(value) => bytes.extend(Self::encode_u256(*value)),
+            Token::B256(value) => bytes.extend(value),
+            Token::StringArray(value) => bytes.extend(value.get_encodable_str()?.as_bytes()),
+            Token::StringSlice(value) => {
+                Self::encode_length_prefixed(value.get_encodable_str()?.as_bytes(), bytes)
+            }
+            Token::String(value) => Self::encode_length_prefixed(value.as_bytes(), bytes),
+            Token::Bytes(data) | Token::RawSlice(data) => Self::encode_length_prefixed(data, bytes),
+            Token::Array(tokens) | Token::Tuple(tokens) | Token::Struct(tokens) => {
+                self.run_w_depth_tracking(|ctx| ctx.encode_tokens(tokens, bytes))?
+            }
+            Token::Vector(tokens) => self.run_w_depth_tracking(|ctx| {
+                bytes.extend((tokens.len() as u64).to_be_bytes());
+                ctx.encode_tokens(tokens, bytes)
+            })?,
+            Token::Enum(selector) => {
+                self.run_w_depth_tracking(|ctx| ctx.encode_enum(selector, bytes))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_enum(&mut self, selector: &EnumSelector, bytes: &mut Vec<u8>) -> Result<()> {
+        let (discriminant, token_within_enum, variants) = selector;
+        variants.select_variant(*discriminant)?;
+
+        bytes.extend(discriminant.to_be_bytes());
+
+        self.encode_token(token_within_enum, bytes)
+    }
+
+    fn encode_u256(value: U256) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+
+        bytes
+    }
+
+    fn encode_length_prefixed(data: &[u8], bytes: &mut Vec<u8>) {
+        bytes.extend((data.len() as u64).to_be_bytes());
+        bytes.extend(data);
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// The `EncoderConfig` limit that caused encoding to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncoderLimit {
+    Depth,
+    Tokens,
+    TotalEnumWidth,
+}
+
+impl EncoderLimit {
+    /// The name of the `EncoderConfig` field backing this limit.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EncoderLimit::Depth => "max_depth",
+            EncoderLimit::Tokens => "max_tokens",
+            EncoderLimit::TotalEnumWidth => "max_total_enum_width",
+        }
+    }
+}
+
+type LimitHook = Box<dyn Fn(&str) + Send + Sync>;
+
+/// Records how often each `EncoderConfig` limit was hit. Share it between encoders via an `Arc`
+/// and read the counters periodically, or register a hook to get notified on every hit.
+#[derive(Default)]
+pub struct EncoderTelemetry {
+    depth_hits: AtomicU64,
+    token_hits: AtomicU64,
+    enum_width_hits: AtomicU64,
+    on_limit_hit: Option<LimitHook>,
+}
+
+impl EncoderTelemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `hook` with the name of the limit (see [`EncoderLimit::as_str`]) every time one is
+    /// hit.
+    pub fn with_hook(mut self, hook: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.on_limit_hit = Some(Box::new(hook));
+        self
+    }
+
+    /// Number of times `limit` was hit since creation or the last [`reset`](Self::reset).
+    pub fn hits(&self, limit: EncoderLimit) -> u64 {
+        self.counter(limit).load(Ordering::Relaxed)
+    }
+
+    pub fn total_hits(&self) -> u64 {
+        [
+            EncoderLimit::Depth,
+            EncoderLimit::Tokens,
+            EncoderLimit::TotalEnumWidth,
+        ]
+        .into_iter()
+        .map(|limit| self.hits(limit))
+        .sum()
+    }
+
+    pub fn reset(&self) {
+        self.depth_hits.store(0, Ordering::Relaxed);
+        self.token_hits.store(0, Ordering::Relaxed);
+        self.enum_width_hits.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record(&self, limit: EncoderLimit) {
+        self.counter(limit).fetch_add(1, Ordering::Relaxed);
+
+        if let Some(hook) = &self.on_limit_hit {
+            hook(limit.as_str());
+        }
+    }
+
+    fn counter(&self, limit: EncoderLimit) -> &AtomicU64 {
+        match limit {
+            EncoderLimit::Depth => &self.depth_hits,
+            EncoderLimit::Tokens => &self.token_hits,
+            EncoderLimit::TotalEnumWidth => &self.enum_width_hits,
+        }
+    }
+}
+
+impl Debug for EncoderTelemetry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncoderTelemetry")
+            .field("depth_hits", &self.depth_hits)
+            .field("token_hits", &self.token_hits)
+            .field("enum_width_hits", &self.enum_width_hits)
+            .field("on_limit_hit", &self.on_limit_hit.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{
+        codec::{ABIEncoder, EncoderConfig},
+        types::Token,
+    };
+
+    #[test]
+    fn limit_hits_are_counted_per_limit() {
+        let telemetry = Arc::new(EncoderTelemetry::new());
+        let encoder = ABIEncoder::new(EncoderConfig {
+            max_depth: 1,
+            max_tokens: 3,
+            ..Default::default()
+        })
+        .with_telemetry(telemetry.clone());
+
+        let too_deep = Token::Struct(vec![Token::Struct(vec![Token::U8(1)])]);
+        let too_many = Token::Tuple(vec![Token::U8(1); 3]);
+
+        encoder
+            .encode(&[too_deep])
+            .expect_err("should hit depth limit");
+        encoder
+            .encode(&[too_many])
+            .expect_err("should hit token limit");
+        encoder.encode(&[Token::U64(1)]).expect("should encode");
+
+        assert_eq!(telemetry.hits(EncoderLimit::Depth), 1);
+        assert_eq!(telemetry.hits(EncoderLimit::Tokens), 1);
+        assert_eq!(telemetry.hits(EncoderLimit::TotalEnumWidth), 0);
+        assert_eq!(telemetry.total_hits(), 2);
+
+        telemetry.reset();
+        assert_eq!(telemetry.total_hits(), 0);
+    }
+
+    #[test]
+    fn hook_is_called_with_limit_name() {
+        let hit_limits = Arc::new(Mutex::new(vec![]));
+        let telemetry = {
+            let hit_limits = hit_limits.clone();
+            EncoderTelemetry::new()
+                .with_hook(move |limit| hit_limits.lock().unwrap().push(limit.to_string()))
+        };
+        let encoder = ABIEncoder::new(EncoderConfig {
+            max_depth: 0,
+            ..Default::default()
+        })
+        .with_telemetry(Arc::new(telemetry));
+
+        encoder
+            .encode(&[Token::Tuple(vec![Token::U8(1)])])
+            .expect_err("should hit depth limit");
+
+        assert_eq!(*hit_limits.lock().unwrap(), vec!["max_depth".to_string()]);
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    ops::Range,
+};
+
+/// Where the bytes of a [`TraceEntry`] are placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// In the inline data, which holds every argument in order.
+    Inline,
+    /// In the dynamic data placed after all of the inline data, e.g. the elements of a vector.
+    Dynamic,
+}
+
+/// Bytes an argument contributed to the encoding, see [`EncodeTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// The index of the argument.
+    pub argument: usize,
+    pub placement: Placement,
+    /// The offset of `bytes` from the start of the resolved bytes.
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+/// A map of the bytes each argument produced and where they were placed, as returned by
+/// [`ABIEncoder::encode_with_trace`](crate::codec::ABIEncoder::encode_with_trace). The bytes are
+/// resolved at address 0, so the pointers to dynamic data differ from the ones of bytes
+/// resolved elsewhere, while everything else is the same.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EncodeTrace {
+    entries: Vec<TraceEntry>,
+}
+
+impl EncodeTrace {
+    /// `layout` holds the ranges of `resolved` each argument occupies, see
+    /// `ABIEncoder::encode_with_layout`. The first range of an argument is its inline data.
+    pub(crate) fn new(resolved: &[u8], layout: Vec<(usize, Range<usize>)>) -> Self {
+        let mut seen = HashSet::new();
+        let entries = layout
+            .into_iter()
+            .map(|(argument, range)| TraceEntry {
+                argument,
+                placement: if seen.insert(argument) {
+                    Placement::Inline
+                } else {
+                    Placement::Dynamic
+                },
+                offset: range.start,
+                bytes: resolved[range].to_vec(),
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// The entries, ordered by offset.
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// The entries of the argument at index `argument`.
+    pub fn of_argument(&self, argument: usize) -> impl Iterator<Item = &TraceEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.argument == argument)
+    }
+}
+
+impl Display for EncodeTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            let placement = match entry.placement {
+                Placement::Inline => "inline",
+                Placement::Dynamic => "dynamic",
+            };
+            let end = entry.offset + entry.bytes.len();
+            let words = entry
+                .bytes
+                .chunks(8)
+                .map(hex::encode)
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            writeln!(
+                f,
+                "arg {} {placement} [{}..{end}]: {words}",
+                entry.argument, entry.offset
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,320 @@
+//! Random `ParamType`s and matching `Token`s for property testing the codec. Heap types
+//! (vectors, bytes, strings and slices) only round-trip in some positions, so
+//! [`random_param_type`] leaves them out and [`random_param_type_for`] places them wherever
+//! the given layout supports them.
+
+use rand::Rng;
+
+use crate::{
+    codec::EncodingVersion,
+    types::{
+        param_types::{EnumVariants, ParamType},
+        StaticStringToken, Token, U256,
+    },
+};
+
+const MAX_CHILDREN: usize = 3;
+const MAX_STRING_LEN: usize = 8;
+
+/// A random enum nested at most `depth` levels deep, along with a token holding a randomly
+/// selected variant.
+pub fn random_enum(rng: &mut impl Rng, depth: usize) -> (ParamType, Token) {
+    let param_type = ParamType::Enum {
+        name: "RandomEnum".to_string(),
+        enum_variants: random_enum_variants(rng, depth),
+        generics: vec![],
+    };
+    let token = random_token(rng, &param_type);
+
+    (param_type, token)
+}
+
+/// Between one and three variants named `V0`, `V1`, ..., each of a random type nested at
+/// most `depth` levels deep.
+pub fn random_enum_variants(rng: &mut impl Rng, depth: usize) -> EnumVariants {
+    random_variants(rng, depth, false)
+}
+
+/// A random type nested at most `depth` levels deep, without heap types. A `depth` of zero
+/// gives a scalar type.
+pub fn random_param_type(rng: &mut impl Rng, depth: usize) -> ParamType {
+    random_type(rng, depth, false)
+}
+
+/// A random type nested at most `depth` levels deep, holding heap types where `version` can
+/// round-trip them. The stable layout only decodes them as returned values, so they appear at
+/// the top level, while the experimental layout encodes them inline in any position.
+pub fn random_param_type_for(
+    rng: &mut impl Rng,
+    depth: usize,
+    version: EncodingVersion,
+) -> ParamType {
+    match version {
+        EncodingVersion::Stable if rng.gen_bool(0.5) => random_heap_type(rng, depth, false),
+        EncodingVersion::Stable => random_param_type(rng, depth),
+        EncodingVersion::Experimental => random_type(rng, depth, true),
+    }
+}
+
+fn random_type(rng: &mut impl Rng, depth: usize, nested_heap_types: bool) -> ParamType {
+    let kinds = match (depth, nested_heap_types) {
+        (0, _) => 10,
+        (_, false) => 14,
+        (_, true) => 15,
+    };
+
+    match rng.gen_range(0..kinds) {
+        0 => ParamType::Unit,
+        1 => ParamType::Bool,
+        2 => ParamType::U8,
+        3 => ParamType::U16,
+        4 => ParamType::U32,
+        5 => ParamType::U64,
+        6 => ParamType::U128,
+        7 => ParamType::U256,
+        8 => ParamType::B256,
+        9 => ParamType::StringArray(rng.gen_range(0..=MAX_STRING_LEN)),
+        10 => ParamType::Array(
+            Box::new(random_type(rng, depth - 1, nested_heap_types)),
+            rng.gen_range(1..=MAX_CHILDREN),
+        ),
+        11 => ParamType::Tuple(random_children(rng, depth - 1, nested_heap_types)),
+        12 => ParamType::Struct {
+            name: "RandomStruct".to_string(),
+            fields: random_children(rng, depth - 1, nested_heap_types)
+                .into_iter()
+                .enumerate()
+                .map(|(index, field)| (format!("f{index}"), field))
+                .collect(),
+            generics: vec![],
+        },
+        13 => ParamType::Enum {
+            name: "RandomEnum".to_string(),
+            enum_variants: random_variants(rng, depth - 1, nested_heap_types),
+            generics: vec![],
+        },
+        _ => random_heap_type(rng, depth, true),
+    }
+}
+
+/// A vector, bytes, string or raw slice. Elements of vectors that aren't inline, as in the
+/// stable layout, need a size to be counted by, so zero-sized ones are skipped.
+fn random_heap_type(rng: &mut impl Rng, depth: usize, nested_heap_types: bool) -> ParamType {
+    let kinds = if depth == 0 { 3 } else { 4 };
+
+    match rng.gen_range(0..kinds) {
+        0 => ParamType::Bytes,
+        1 => ParamType::String,
+        2 => ParamType::RawSlice,
+        _ => loop {
+            let element = random_type(rng, depth - 1, nested_heap_types);
+            let is_sized = nested_heap_types
+                || element
+                    .compute_encoding_in_bytes()
+                    .is_ok_and(|size| size > 0);
+
+            if is_sized {
+                break ParamType::Vector(Box::new(element));
+            }
+        },
+    }
+}
+
+/// A random token of the given type. Enums hold a randomly selected variant and heap types
+/// hold up to three elements or eight bytes.
+///
+/// # Panics
+///
+/// If `param_type` contains a string slice, which the generators don't produce.
+pub fn random_token(rng: &mut impl Rng, param_type: &ParamType) -> Token {
+    match param_type {
+        ParamType::Unit => Token::Unit,
+        ParamType::Bool => Token::Bool(rng.gen()),
+        ParamType::U8 => Token::U8(rng.gen()),
+        ParamType::U16 => Token::U16(rng.gen()),
+        ParamType::U32 => Token::U32(rng.gen()),
+        ParamType::U64 => Token::U64(rng.gen()),
+        ParamType::U128 => Token::U128(rng.gen()),
+        ParamType::U256 => Token::U256(U256(rng.gen())),
+        ParamType::B256 => Token::B256(rng.gen()),
+        ParamType::StringArray(len) => {
+            Token::StringArray(StaticStringToken::new(random_string(rng, *len), Some(*len)))
+        }
+        ParamType::String => {
+            let len = rng.gen_range(0..=MAX_STRING_LEN);
+
+            Token::String(random_string(rng, len))
+        }
+        ParamType::Bytes => Token::Bytes(random_bytes(rng)),
+        ParamType::RawSlice => Token::RawSlice(random_bytes(rng)),
+        ParamType::Vector(element) => Token::Vector(
+            (0..rng.gen_range(0..=MAX_CHILDREN))
+                .map(|_| random_token(rng, element))
+                .collect(),
+        ),
+        ParamType::Array(element, len) => {
+            Token::Array((0..*len).map(|_| random_token(rng, element)).collect())
+        }
+        ParamType::Tuple(elements) => Token::Tuple(random_tokens(rng, elements)),
+        ParamType::Struct { fields, .. } => {
+            let field_types: Vec<_> = fields.iter().map(|(_, field)| field.clone()).collect();
+
+            Token::Struct(random_tokens(rng, &field_types))
+        }
+        ParamType::Enum { enum_variants, .. } => {
+            let variants = enum_variants.variants();
+            let discriminant = rng.gen_range(0..variants.len());
+            let (_, variant_type) = &variants[discriminant];
+            let token = random_token(rng, variant_type);
+
+            Token::Enum(Box::new((
+                discriminant as u64,
+                token,
+                enum_variants.clone(),
+            )))
+        }
+        _ => panic!("no random tokens are generated for `{param_type:?}`"),
+    }
+}
+
+fn random_children(rng: &mut impl Rng, depth: usize, nested_heap_types: bool) -> Vec<ParamType> {
+    (0..rng.gen_range(1..=MAX_CHILDREN))
+        .map(|_| random_type(rng, depth, nested_heap_types))
+        .collect()
+}
+
+fn random_variants(rng: &mut impl Rng, depth: usize, nested_heap_types: bool) -> EnumVariants {
+    let variants = random_children(rng, depth, nested_heap_types)
+        .into_iter()
+        .enumerate()
+        .map(|(index, variant)| (format!("V{index}"), variant))
+        .collect();
+
+    EnumVariants::new(variants).expect("there is at least one variant")
+}
+
+fn random_string(rng: &mut impl Rng, len: usize) -> String {
+    (0..len)
+        .map(|_| rng.gen_range(b'a'..=b'z') as char)
+        .collect()
+}
+
+fn random_bytes(rng: &mut impl Rng) -> Vec<u8> {
+    (0..rng.gen_range(0..=MAX_STRING_LEN))
+        .map(|_| rng.gen())
+        .collect()
+}
+
+fn random_tokens(rng: &mut impl Rng, param_types: &[ParamType]) -> Vec<Token> {
+    param_types
+        .iter()
+        .map(|param_type| random_token(rng, param_type))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{
+        codec::{ABIDecoder, ABIEncoder, EncodingVersion},
+        types::errors::Result,
+    };
+
+    const ITERATIONS: u64 = 500;
+    const MAX_DEPTH: usize = 3;
+
+    #[test]
+    fn random_enums_survive_an_encode_decode_round_trip() -> Result<()> {
+        for seed in 0..ITERATIONS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (param_type, token) = random_enum(&mut rng, MAX_DEPTH);
+
+            let bytes = ABIEncoder::default()
+                .encode_versioned(&[token.clone()], EncodingVersion::Stable)?
+                .resolve(0);
+            let decoded = ABIDecoder::default().decode(&param_type, &bytes)?;
+
+            assert_eq!(decoded, token, "seed {seed}, type {param_type:?}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn random_returned_values_survive_a_stable_round_trip() -> Result<()> {
+        let mut heap_types = 0;
+        for seed in 0..ITERATIONS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let param_type = random_param_type_for(&mut rng, MAX_DEPTH, EncodingVersion::Stable);
+            let token = random_token(&mut rng, &param_type);
+
+            let bytes = stable_return_data(&token)?;
+            let decoded = ABIDecoder::default().decode(&param_type, &bytes)?;
+
+            assert_eq!(decoded, token, "seed {seed}, type {param_type:?}");
+            heap_types += usize::from(param_type.is_extra_receipt_needed(false));
+        }
+        assert!(heap_types > 0);
+
+        Ok(())
+    }
+
+    /// Heap types are returned as their bare contents, which is what the stable decoder reads.
+    fn stable_return_data(token: &Token) -> Result<Vec<u8>> {
+        let bytes = match token {
+            Token::Bytes(data) | Token::RawSlice(data) => data.clone(),
+            Token::String(value) => value.as_bytes().to_vec(),
+            Token::Vector(elements) => ABIEncoder::default()
+                .encode_versioned(elements, EncodingVersion::Stable)?
+                .resolve(0),
+            _ => ABIEncoder::default()
+                .encode_versioned(&[token.clone()], EncodingVersion::Stable)?
+                .resolve(0),
+        };
+
+        Ok(bytes)
+    }
+
+    #[cfg(experimental)]
+    #[test]
+    fn random_types_survive_an_experimental_round_trip() -> Result<()> {
+        let mut nested_heap_types = 0;
+        for seed in 0..ITERATIONS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let param_type =
+                random_param_type_for(&mut rng, MAX_DEPTH, EncodingVersion::Experimental);
+            let token = random_token(&mut rng, &param_type);
+
+            let bytes = ABIEncoder::default()
+                .encode_versioned(&[token.clone()], EncodingVersion::Experimental)?
+                .resolve(0);
+            let decoded = ABIDecoder::default().experimental_decode(&param_type, &bytes)?;
+
+            assert_eq!(decoded, token, "seed {seed}, type {param_type:?}");
+            nested_heap_types += usize::from(param_type.children_need_extra_receipts());
+        }
+        assert!(nested_heap_types > 0);
+
+        Ok(())
+    }
+
+    #[cfg(experimental)]
+    #[test]
+    fn random_enums_survive_an_experimental_round_trip() -> Result<()> {
+        for seed in 0..ITERATIONS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (param_type, token) = random_enum(&mut rng, MAX_DEPTH);
+
+            let bytes = ABIEncoder::default()
+                .encode_versioned(&[token.clone()], EncodingVersion::Experimental)?
+                .resolve(0);
+            let decoded = ABIDecoder::default().experimental_decode(&param_type, &bytes)?;
+
+            assert_eq!(decoded, token, "seed {seed}, type {param_type:?}");
+        }
+
+        Ok(())
+    }
+}
@@ -7,12 +7,14 @@ use std::{
 
 use fuel_tx::{ContractId, Receipt};
 
-#[cfg(not(experimental))]
-use crate::types::param_types::ParamType;
 use crate::{
     codec::{ABIDecoder, DecoderConfig},
     traits::{Parameterize, Tokenizable},
-    types::errors::{error, Error, Result},
+    types::{
+        errors::{error, Error, Result},
+        param_types::ParamType,
+        Token,
+    },
 };
 
 #[derive(Clone)]
@@ -79,6 +81,19 @@ impl Debug for LogFormatter {
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct LogId(ContractId, u64);
 
+impl LogId {
+    pub fn new(contract_id: ContractId, log_id: u64) -> Self {
+        Self(contract_id, log_id)
+    }
+}
+
+/// A log decoded into a `Token` using the `ParamType` registered for its `LogId`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedLog {
+    pub log_id: LogId,
+    pub token: Token,
+}
+
 /// Struct used to pass the log mappings from the Abigen
 #[derive(Debug, Clone, Default)]
 pub struct LogDecoder {
@@ -214,6 +229,30 @@ impl LogDecoder {
     }
 }
 
+/// Decodes the logs found in `receipts` into `Token`s using the types in `log_types`. Logs
+/// without a registered type are skipped.
+pub fn decode_logs_as_tokens(
+    receipts: &[Receipt],
+    log_types: &HashMap<LogId, ParamType>,
+    decoder_config: DecoderConfig,
+) -> Result<Vec<DecodedLog>> {
+    receipts
+        .iter()
+        .extract_log_id_and_data()
+        .filter_map(|(log_id, bytes)| {
+            let param_type = log_types.get(&log_id)?;
+
+            #[cfg(experimental)]
+            let token = ABIDecoder::new(decoder_config).experimental_decode(param_type, &bytes);
+
+            #[cfg(not(experimental))]
+            let token = ABIDecoder::new(decoder_config).decode(param_type, &bytes);
+
+            Some(token.map(|token| DecodedLog { log_id, token }))
+        })
+        .collect()
+}
+
 trait ExtractLogIdData {
     type Output: Iterator<Item = (LogId, Vec<u8>)>;
     fn extract_log_id_and_data(self) -> Self::Output;
@@ -244,3 +283,40 @@ pub fn log_formatters_lookup(
         .map(|(id, log_formatter)| (LogId(contract_id, id), log_formatter))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logs_are_decoded_into_tokens_by_their_registered_type() -> Result<()> {
+        let contract_id = ContractId::from([1; 32]);
+        let receipts = vec![
+            Receipt::log(contract_id, 7, 0, 0, 0, 0, 0),
+            Receipt::log(contract_id, 8, 1, 0, 0, 0, 0),
+            Receipt::log(contract_id, 9, 2, 0, 0, 0, 0),
+        ];
+        let log_types = HashMap::from([
+            (LogId::new(contract_id, 0), ParamType::U64),
+            (LogId::new(contract_id, 2), ParamType::U64),
+        ]);
+
+        let decoded = decode_logs_as_tokens(&receipts, &log_types, DecoderConfig::default())?;
+
+        assert_eq!(
+            decoded,
+            vec![
+                DecodedLog {
+                    log_id: LogId::new(contract_id, 0),
+                    token: Token::U64(7),
+                },
+                DecodedLog {
+                    log_id: LogId::new(contract_id, 2),
+                    token: Token::U64(9),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+//! Golden-file snapshots of the token encoding. Every case below is checked against a stored
+//! hex string so that an accidental change of the layout fails the build instead of silently
+//! breaking compatibility with deployed contracts.
+use std::collections::BTreeMap;
+
+use crate::{
+    codec::{ABIEncoder, EncoderConfig, EncodingVersion},
+    types::{
+        errors::Result,
+        param_types::{EnumVariants, ParamType},
+        StaticStringToken, Token, U256,
+    },
+};
+
+const GOLDENS_PATH: &str = "src/codec/goldens/encoding.txt";
+const GOLDENS: &str = include_str!("goldens/encoding.txt");
+const EXPERIMENTAL_GOLDENS: &str = include_str!("goldens/experimental_encoding.txt");
+
+struct Case {
+    name: &'static str,
+    param_type: ParamType,
+    token: Token,
+}
+
+impl Case {
+    fn new(name: &'static str, param_type: ParamType, token: Token) -> Self {
+        Self {
+            name,
+            param_type,
+            token,
+        }
+    }
+}
+
+fn cases() -> Result<Vec<Case>> {
+    let variants = EnumVariants::new(vec![
+        ("Small".to_string(), ParamType::U8),
+        ("Wide".to_string(), ParamType::U64),
+    ])?;
+    let enum_type = ParamType::Enum {
+        name: "Size".to_string(),
+        enum_variants: variants.clone(),
+        generics: vec![],
+    };
+    let struct_type = ParamType::Struct {
+        name: "Mixed".to_string(),
+        fields: vec![
+            ("small".to_string(), ParamType::U8),
+            ("flag".to_string(), ParamType::Bool),
+            ("wide".to_string(), ParamType::U64),
+        ],
+        generics: vec![],
+    };
+
+    Ok(vec![
+        Case::new("u8", ParamType::U8, Token::U8(255)),
+        Case::new("bool", ParamType::Bool, Token::Bool(true)),
+        Case::new("u16", ParamType::U16, Token::U16(0x1234)),
+        Case::new("u32", ParamType::U32, Token::U32(0xdeadbeef)),
+        Case::new("u64", ParamType::U64, Token::U64(1)),
+        Case::new("u128", ParamType::U128, Token::U128(1)),
+        Case::new("u256", ParamType::U256, Token::U256(U256::from(1))),
+        Case::new("b256", ParamType::B256, Token::B256([0x11; 32])),
+        Case::new(
+            "string_array",
+            ParamType::StringArray(4),
+            Token::StringArray(StaticStringToken::new("fuel".to_string(), Some(4))),
+        ),
+        Case::new(
+            "struct",
+            struct_type,
+            Token::Struct(vec![Token::U8(1), Token::Bool(true), Token::U64(2)]),
+        ),
+        Case::new(
+            "array",
+            ParamType::Array(Box::new(ParamType::U8), 3),
+            Token::Array(vec![Token::U8(1), Token::U8(2), Token::U8(3)]),
+        ),
+        Case::new(
+            "tuple",
+            ParamType::Tuple(vec![ParamType::U16, ParamType::U32]),
+            Token::Tuple(vec![Token::U16(7), Token::U32(8)]),
+        ),
+        Case::new(
+            "enum_widest_variant",
+            enum_type.clone(),
+            Token::Enum(Box::new((1, Token::U64(42), variants.clone()))),
+        ),
+        Case::new(
+            "enum_padded_variant",
+            enum_type,
+            Token::Enum(Box::new((0, Token::U8(7), variants))),
+        ),
+        Case::new(
+            "vector",
+            ParamType::Vector(Box::new(ParamType::U8)),
+            Token::Vector(vec![Token::U8(1), Token::U8(2)]),
+        ),
+        Case::new("bytes", ParamType::Bytes, Token::Bytes(vec![1, 2, 3])),
+        Case::new(
+            "string",
+            ParamType::String,
+            Token::String("fuel".to_string()),
+        ),
+        Case::new(
+            "string_slice",
+            ParamType::StringSlice,
+            Token::StringSlice(StaticStringToken::new("fuel".to_string(), None)),
+        ),
+        Case::new(
+            "raw_slice",
+            ParamType::RawSlice,
+            Token::RawSlice(vec![1, 2]),
+        ),
+    ])
+}
+
+/// Parses `name hex` lines, skipping blank lines and `#` comments.
+fn parse_goldens(contents: &str) -> BTreeMap<&str, &str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split_once(' ')
+                .unwrap_or_else(|| panic!("malformed golden line: `{line}`"))
+        })
+        .collect()
+}
+
+fn assert_same_cases(goldens: &BTreeMap<&str, &str>, cases: &[Case]) {
+    let mut case_names: Vec<_> = cases.iter().map(|case| case.name).collect();
+    case_names.sort_unstable();
+    let golden_names: Vec<_> = goldens.keys().copied().collect();
+
+    assert_eq!(
+        golden_names, case_names,
+        "goldens and cases are out of sync"
+    );
+}
+
+/// Set `UPDATE_GOLDENS` to rewrite the stored goldens with the current encoding instead of
+/// comparing against them.
+#[test]
+fn encoding_matches_goldens() -> Result<()> {
+    let cases = cases()?;
+    let encoded = cases
+        .iter()
+        .map(|case| {
+            case.param_type
+                .validate_token(&case.token, EncoderConfig::default().max_depth)?;
+            let bytes = ABIEncoder::default()
+                .encode(&[case.token.clone()])?
+                .resolve(0);
+
+            Ok((case.name, hex::encode(bytes)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if std::env::var_os("UPDATE_GOLDENS").is_some() {
+        let header: String = GOLDENS
+            .lines()
+            .take_while(|line| line.starts_with('#'))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        let body: String = encoded
+            .iter()
+            .map(|(name, hex)| format!("{name} {hex}\n"))
+            .collect();
+
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(GOLDENS_PATH);
+        std::fs::write(path, header + &body)?;
+
+        return Ok(());
+    }
+
+    let goldens = parse_goldens(GOLDENS);
+    assert_same_cases(&goldens, &cases);
+
+    let mismatches: Vec<_> = encoded
+        .iter()
+        .filter(|(name, hex)| goldens[name] != hex.as_str())
+        .map(|(name, hex)| format!("{name}: expected {}, got {hex}", goldens[name]))
+        .collect();
+
+    assert!(
+        mismatches.is_empty(),
+        "encoding changed:\n{}",
+        mismatches.join("\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn experimental_encoding_matches_goldens() -> Result<()> {
+    let cases = cases()?;
+    let goldens = parse_goldens(EXPERIMENTAL_GOLDENS);
+    assert_same_cases(&goldens, &cases);
+
+    for case in cases {
+        let bytes = ABIEncoder::default()
+            .encode_versioned(&[case.token.clone()], EncodingVersion::Experimental)?
+            .resolve(0);
+
+        assert_eq!(
+            hex::encode(bytes),
+            goldens[case.name],
+            "experimental encoding of `{}` changed",
+            case.name
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(experimental)]
+#[test]
+fn experimental_goldens_decode_into_cases() -> Result<()> {
+    use crate::codec::ABIDecoder;
+
+    let cases = cases()?;
+    let goldens = parse_goldens(EXPERIMENTAL_GOLDENS);
+    assert_same_cases(&goldens, &cases);
+
+    for case in cases {
+        let bytes = hex::decode(goldens[case.name])?;
+
+        let decoded = ABIDecoder::default().experimental_decode(&case.param_type, &bytes)?;
+
+        assert_eq!(
+            decoded, case.token,
+            "golden `{}` no longer decodes",
+            case.name
+        );
+    }
+
+    Ok(())
+}
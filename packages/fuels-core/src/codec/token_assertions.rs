@@ -0,0 +1,202 @@
+//! Assertions comparing `Token`s that, on failure, point at the differing leaves by the names
+//! of their struct fields and enum variants instead of dumping both token trees.
+
+use crate::types::{param_types::ParamType, ScalarValue, Token};
+
+/// Panics if `actual` and `expected` differ, listing every differing leaf with its path built
+/// from the names in `param_type`, e.g. `order.items[2].price: expected 100 got 99`. The path
+/// starts with the name of the root struct or enum, in snake case, and indexes arrays and vectors
+/// with brackets.
+#[track_caller]
+pub fn assert_token_eq_named(actual: &Token, expected: &Token, param_type: &ParamType) {
+    if actual == expected {
+        return;
+    }
+
+    let changes = expected.diff(actual, Some(param_type));
+    if changes.is_empty() {
+        // Only the enum variants or the kinds of empty collections differ, there is no leaf
+        // to point at.
+        panic!("tokens differ:\n  expected: {expected:?}\n    actual: {actual:?}");
+    }
+
+    let lines: Vec<_> = changes
+        .iter()
+        .map(|change| {
+            // Leaves only found in `actual` can't be looked up in `expected`
+            let token = if change.old.is_some() {
+                expected
+            } else {
+                actual
+            };
+            let path = display_path(&change.path, token, param_type);
+
+            format!(
+                "  {path}: expected {} got {}",
+                describe(change.old.as_ref()),
+                describe(change.new.as_ref())
+            )
+        })
+        .collect();
+
+    panic!("tokens differ:\n{}", lines.join("\n"));
+}
+
+/// Rewrites a path of [`Token::flatten`] by walking `token` along it, so that the indices of
+/// arrays and vectors are put in brackets.
+fn display_path(path: &str, token: &Token, param_type: &ParamType) -> String {
+    let mut display = match param_type {
+        ParamType::Struct { name, .. } | ParamType::Enum { name, .. } => to_snake_case(name),
+        _ => String::new(),
+    };
+
+    let mut current = Some((token, Some(param_type)));
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        if let Some((Token::Array(_) | Token::Vector(_), _)) = current {
+            display.push_str(&format!("[{segment}]"));
+        } else {
+            if !display.is_empty() {
+                display.push('.');
+            }
+            display.push_str(segment);
+        }
+
+        current = current.and_then(|(token, param_type)| child(token, param_type, segment));
+    }
+
+    if display.is_empty() {
+        "<root>".to_string()
+    } else {
+        display
+    }
+}
+
+/// The token, and its type if known, reached from `token` by the path `segment`. Names are
+/// resolved the same way as by [`Token::flatten`].
+fn child<'a>(
+    token: &'a Token,
+    param_type: Option<&'a ParamType>,
+    segment: &str,
+) -> Option<(&'a Token, Option<&'a ParamType>)> {
+    match token {
+        Token::Array(tokens) | Token::Vector(tokens) => {
+            let element = match param_type {
+                Some(ParamType::Array(element, _) | ParamType::Vector(element)) => {
+                    Some(element.as_ref())
+                }
+                _ => None,
+            };
+
+            Some((tokens.get(segment.parse::<usize>().ok()?)?, element))
+        }
+        Token::Tuple(tokens) => {
+            let index = segment.parse::<usize>().ok()?;
+            let element = match param_type {
+                Some(ParamType::Tuple(elements)) if elements.len() == tokens.len() => {
+                    elements.get(index)
+                }
+                _ => None,
+            };
+
+            Some((tokens.get(index)?, element))
+        }
+        Token::Struct(tokens) => match param_type {
+            Some(ParamType::Struct { fields, .. }) if fields.len() == tokens.len() => {
+                let index = fields.iter().position(|(name, _)| name == segment)?;
+
+                Some((&tokens[index], Some(&fields[index].1)))
+            }
+            _ => Some((tokens.get(segment.parse::<usize>().ok()?)?, None)),
+        },
+        Token::Enum(selector) => {
+            let (discriminant, token, variants) = selector.as_ref();
+            let variant_type = variants
+                .select_variant(*discriminant)
+                .ok()
+                .map(|(_, variant_type)| variant_type);
+
+            Some((token, variant_type))
+        }
+        _ => None,
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::with_capacity(name.len());
+    for (index, letter) in name.chars().enumerate() {
+        if letter.is_uppercase() && index > 0 {
+            snake_case.push('_');
+        }
+        snake_case.extend(letter.to_lowercase());
+    }
+
+    snake_case
+}
+
+fn describe(value: Option<&ScalarValue>) -> String {
+    let Some(value) = value else {
+        return "nothing".to_string();
+    };
+
+    match value {
+        ScalarValue::Unit => "()".to_string(),
+        ScalarValue::Bool(value) => value.to_string(),
+        ScalarValue::U8(value) => value.to_string(),
+        ScalarValue::U16(value) => value.to_string(),
+        ScalarValue::U32(value) => value.to_string(),
+        ScalarValue::U64(value) => value.to_string(),
+        ScalarValue::U128(value) => value.to_string(),
+        ScalarValue::U256(value) => value.to_string(),
+        ScalarValue::B256(value) => format!("0x{}", hex::encode(value)),
+        ScalarValue::String(value) => format!("{value:?}"),
+        ScalarValue::Bytes(value) => format!("0x{}", hex::encode(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_type() -> ParamType {
+        let item = ParamType::Struct {
+            name: "Item".to_string(),
+            fields: vec![("price".to_string(), ParamType::U64)],
+            generics: vec![],
+        };
+
+        ParamType::Struct {
+            name: "Order".to_string(),
+            fields: vec![
+                ("id".to_string(), ParamType::U32),
+                ("items".to_string(), ParamType::Vector(Box::new(item))),
+            ],
+            generics: vec![],
+        }
+    }
+
+    fn order(prices: &[u64]) -> Token {
+        let items = prices
+            .iter()
+            .map(|price| Token::Struct(vec![Token::U64(*price)]))
+            .collect();
+
+        Token::Struct(vec![Token::U32(1), Token::Vector(items)])
+    }
+
+    #[test]
+    fn equal_tokens_pass() {
+        assert_token_eq_named(&order(&[1, 2]), &order(&[1, 2]), &order_type());
+    }
+
+    #[test]
+    #[should_panic(expected = "order.items[1].price: expected 100 got 99")]
+    fn differing_leaves_are_named() {
+        assert_token_eq_named(&order(&[1, 99]), &order(&[1, 100]), &order_type());
+    }
+
+    #[test]
+    #[should_panic(expected = "order.items[1].price: expected nothing got 3")]
+    fn extra_leaves_are_reported() {
+        assert_token_eq_named(&order(&[1, 3]), &order(&[1]), &order_type());
+    }
+}
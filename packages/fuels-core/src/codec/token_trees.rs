@@ -0,0 +1,103 @@
+//! Deterministic `Token` trees representative of what the encoder sees in practice. Used by the
+//! encoder benchmarks and reusable in tests.
+
+use crate::types::{StaticStringToken, Token, U256};
+
+/// `count` top-level scalar tokens cycling through every scalar type.
+pub fn flat_scalars(count: usize) -> Vec<Token> {
+    (0..count)
+        .map(|i| match i % 8 {
+            0 => Token::U8(i as u8),
+            1 => Token::U16(i as u16),
+            2 => Token::U32(i as u32),
+            3 => Token::U64(i as u64),
+            4 => Token::U128(i as u128),
+            5 => Token::U256(U256::from(i)),
+            6 => Token::Bool(i % 2 == 0),
+            _ => Token::B256([i as u8; 32]),
+        })
+        .collect()
+}
+
+/// Structs nested `depth` levels deep. Each level increases the encoding depth by one, so
+/// `depth` can be set up to `EncoderConfig::max_depth`.
+pub fn nested_structs(depth: usize) -> Token {
+    (0..depth).fold(Token::U64(depth as u64), |inner, level| {
+        Token::Struct(vec![Token::U64(level as u64), inner])
+    })
+}
+
+/// A single vector holding `len` `u64`s.
+pub fn large_vector(len: usize) -> Token {
+    Token::Vector((0..len as u64).map(Token::U64).collect())
+}
+
+/// `count` top-level heap types, each becoming its own dynamic segment when encoded.
+pub fn many_dynamic_segments(count: usize) -> Vec<Token> {
+    (0..count)
+        .map(|i| match i % 4 {
+            0 => Token::Bytes(vec![i as u8; i % 17]),
+            1 => Token::String("a".repeat(i % 13)),
+            2 => Token::RawSlice(vec![i as u8; i % 9]),
+            _ => Token::Vector(vec![Token::U32(i as u32); i % 5]),
+        })
+        .collect()
+}
+
+/// A struct mixing inline and dynamic data, similar to typical contract call arguments.
+pub fn mixed_struct() -> Token {
+    Token::Struct(vec![
+        Token::U16(10),
+        Token::Struct(vec![
+            Token::Bool(true),
+            Token::Array(vec![Token::U8(1), Token::U8(2)]),
+        ]),
+        Token::B256([7; 32]),
+        Token::StringArray(StaticStringToken::new(
+            "This is a full sentence".into(),
+            Some(23),
+        )),
+        Token::Vector(vec![Token::U64(1), Token::U64(2), Token::U64(3)]),
+        Token::Bytes(vec![1, 2, 3, 4, 5]),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        codec::{ABIEncoder, EncoderConfig},
+        types::errors::Result,
+    };
+
+    #[test]
+    fn all_trees_encode_with_default_config() -> Result<()> {
+        let encoder = ABIEncoder::default();
+
+        encoder.encode(&flat_scalars(100))?;
+        encoder.encode(&[nested_structs(EncoderConfig::default().max_depth)])?;
+        encoder.encode(&[large_vector(1000)])?;
+        encoder.encode(&many_dynamic_segments(100))?;
+        encoder.encode(&[mixed_struct()])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_structs_depth_matches_encoding_depth() {
+        let max_depth = 10;
+        let encoder = ABIEncoder::new(EncoderConfig {
+            max_depth,
+            ..Default::default()
+        });
+
+        assert!(encoder.encode(&[nested_structs(max_depth)]).is_ok());
+        assert!(encoder.encode(&[nested_structs(max_depth + 1)]).is_err());
+    }
+
+    #[test]
+    fn trees_are_deterministic() {
+        assert_eq!(flat_scalars(50), flat_scalars(50));
+        assert_eq!(many_dynamic_segments(50), many_dynamic_segments(50));
+    }
+}
@@ -1,3 +1,7 @@
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
 use crate::types::errors::{error, Result};
 
 pub(crate) struct CounterWithLimit {
@@ -52,3 +56,62 @@ impl CounterWithLimit {
         }
     }
 }
+
+/// Fails once `budget` has elapsed since its creation, if a budget is set. The clock is only
+/// read every `CHECK_INTERVAL` checks to keep the overhead low. Time can't be read on `wasm32`,
+/// so there a set budget fails the first check instead of panicking.
+pub(crate) struct Deadline {
+    #[cfg(not(target_arch = "wasm32"))]
+    expires_at: Option<(Instant, Duration)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    checks: usize,
+    #[cfg(target_arch = "wasm32")]
+    budget: Option<Duration>,
+}
+
+impl Deadline {
+    #[cfg(not(target_arch = "wasm32"))]
+    const CHECK_INTERVAL: usize = 64;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn new(budget: Option<Duration>) -> Self {
+        Self {
+            expires_at: budget.map(|budget| (Instant::now() + budget, budget)),
+            checks: 0,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn new(budget: Option<Duration>) -> Self {
+        Self { budget }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn check(&mut self) -> Result<()> {
+        let Some((expires_at, budget)) = self.expires_at else {
+            return Ok(());
+        };
+
+        let should_read_clock = self.checks % Self::CHECK_INTERVAL == 0;
+        self.checks += 1;
+        if should_read_clock && Instant::now() >= expires_at {
+            return Err(error!(
+                Codec,
+                "deadline of `{budget:?}` reached while decoding. The input may be malicious"
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn check(&mut self) -> Result<()> {
+        match self.budget {
+            Some(budget) => Err(error!(
+                Codec,
+                "deadline of `{budget:?}` can't be enforced on wasm32. Set it to `None`"
+            )),
+            None => Ok(()),
+        }
+    }
+}
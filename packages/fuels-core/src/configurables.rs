@@ -0,0 +1,136 @@
+use std::{collections::HashMap, fs};
+
+use fuel_abi_types::abi::program::ProgramABI;
+
+use crate::{
+    codec::ConfigurablesEncoder,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        Token,
+    },
+    Configurables,
+};
+
+/// The offsets and types of a program's configurable constants, keyed by name. Used to set
+/// configurables from `Token`s when the typed `abigen!` bindings are not available.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigurableSlots {
+    slots: HashMap<String, (u64, ParamType)>,
+}
+
+impl ConfigurableSlots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_slot(
+        mut self,
+        name: impl Into<String>,
+        offset: u64,
+        param_type: ParamType,
+    ) -> Self {
+        self.slots.insert(name.into(), (offset, param_type));
+        self
+    }
+
+    /// Reads the slots from the `configurables` section of a program's ABI.
+    pub fn from_abi(abi: &ProgramABI) -> Result<Self> {
+        let type_lookup = abi
+            .types
+            .iter()
+            .map(|decl| (decl.type_id, decl.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let slots = abi
+            .configurables
+            .iter()
+            .flatten()
+            .map(|configurable| {
+                let param_type = ParamType::try_from_type_application(
+                    &configurable.configurable_type,
+                    &type_lookup,
+                )?;
+
+                Ok((configurable.name.clone(), (configurable.offset, param_type)))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { slots })
+    }
+
+    /// Same as [`from_abi`](Self::from_abi) but reads the ABI from a JSON file.
+    pub fn load_from(abi_path: &str) -> Result<Self> {
+        let json = fs::read_to_string(abi_path)?;
+        let abi: ProgramABI = serde_json::from_str(&json)?;
+
+        Self::from_abi(&abi)
+    }
+
+    /// Encodes every `(name, token)` pair in `values` with `encoder`, checking the token against
+    /// the type of the named configurable, and places it at that configurable's offset.
+    pub fn encode<'a>(
+        &self,
+        values: impl IntoIterator<Item = (&'a str, Token)>,
+        encoder: &ConfigurablesEncoder,
+    ) -> Result<Configurables> {
+        let offsets_with_data = values
+            .into_iter()
+            .map(|(name, token)| {
+                let (offset, param_type) = self
+                    .slots
+                    .get(name)
+                    .ok_or_else(|| error!(Other, "program has no configurable named `{name}`"))?;
+
+                param_type
+                    .validate_token(&token, encoder.config.max_depth)
+                    .map_err(|err| error!(Other, "configurable `{name}`: {err}"))?;
+                let data = encoder.encode(&[token])?.resolve(0);
+
+                Ok((*offset, data))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Configurables::new(offsets_with_data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_encoded_at_their_offsets() -> Result<()> {
+        let slots = ConfigurableSlots::new()
+            .with_slot("FLAG", 2, ParamType::Bool)
+            .with_slot("AMOUNT", 4, ParamType::U64);
+        let mut binary = vec![0u8; 12];
+
+        slots
+            .encode(
+                [("AMOUNT", Token::U64(258)), ("FLAG", Token::Bool(true))],
+                &ConfigurablesEncoder::default(),
+            )?
+            .update_constants_in(&mut binary);
+
+        assert_eq!(binary, [0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_names_and_mistyped_tokens_are_rejected() {
+        let slots = ConfigurableSlots::new().with_slot("AMOUNT", 0, ParamType::U64);
+        let encoder = ConfigurablesEncoder::default();
+
+        let err = slots
+            .encode([("LIMIT", Token::U64(1))], &encoder)
+            .expect_err("should fail since there is no such configurable");
+        assert!(err.to_string().contains("no configurable named `LIMIT`"));
+
+        let err = slots
+            .encode([("AMOUNT", Token::Bool(true))], &encoder)
+            .expect_err("should fail since the token doesn't match the type");
+        assert!(err.to_string().contains("configurable `AMOUNT`"));
+    }
+}
@@ -1,8 +1,10 @@
 pub mod codec;
+mod configurables;
 pub mod traits;
 pub mod types;
 mod utils;
 
+pub use configurables::*;
 pub use utils::*;
 
 #[derive(Debug, Clone, Default)]
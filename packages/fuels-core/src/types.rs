@@ -6,16 +6,22 @@ pub use fuel_types::{
     MessageId, Nonce, Salt, Word,
 };
 
-pub use crate::types::{core::*, wrappers::*};
+pub use crate::types::{
+    core::*,
+    token::{ScalarValue, TokenChange, TAGGED_FORMAT_VERSION},
+    wrappers::*,
+};
 use crate::types::{
     errors::{error, Error, Result},
     param_types::EnumVariants,
 };
+pub use crate::{token_array, token_enum, token_struct, token_tuple, token_vec};
 
 pub mod bech32;
 mod core;
 pub mod errors;
 pub mod param_types;
+mod token;
 pub mod transaction_builders;
 pub mod tx_status;
 pub mod unresolved_bytes;
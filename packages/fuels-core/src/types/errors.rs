@@ -1,6 +1,6 @@
-use std::{array::TryFromSliceError, str::Utf8Error};
+use std::{array::TryFromSliceError, str::Utf8Error, time::Duration};
 
-use fuel_tx::{Receipt, ValidityError};
+use fuel_tx::{Receipt, TxId, ValidityError};
 use fuel_vm::checked_transaction::CheckError;
 use hex::FromHexError;
 use thiserror::Error;
@@ -22,12 +22,38 @@ pub mod transaction {
             revert_id: u64,
             receipts: Vec<Receipt>,
         },
+        /// The transaction was submitted but its outcome is unknown. Its status can still be
+        /// queried with `tx_id`.
+        #[error("`{tx_id}` was not committed before the deadline")]
+        Timeout { tx_id: TxId },
+        /// The chain state checked before submitting the transaction didn't hold, so it was
+        /// never sent.
+        #[error("precondition failed: {0}")]
+        PreconditionFailed(String),
         #[error(": {0}")]
         Other(String),
     }
 }
 use transaction::Reason;
 
+pub mod request {
+    use super::*;
+
+    /// Why a request sent to the node failed.
+    #[derive(Error, Debug)]
+    pub enum Failure {
+        #[error("request timed out after {0:?}")]
+        Timeout(Duration),
+        #[error("connection error: {0}")]
+        Connection(String),
+        /// The node doesn't support the request, e.g. because it lacks an optional index.
+        #[error("not supported by the node: {0}")]
+        Unsupported(String),
+        #[error("{0}")]
+        NodeError(String),
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("io: {0}")]
@@ -38,6 +64,9 @@ pub enum Error {
     Transaction(Reason),
     #[error("provider: {0}")]
     Provider(String),
+    /// A request sent to the node failed. These failures used to be reported as `Provider`.
+    #[error("request: {0}")]
+    Request(request::Failure),
     #[error("{0}")]
     Other(String),
 }
@@ -55,7 +84,7 @@ macro_rules! error {
 pub use error;
 
 /// This macro can only be used for `Error::Transaction` variants that have a `String` field.
-/// Those are: `Builder`, `Validation`, `SqueezedOut`, `Other`.
+/// Those are: `Builder`, `Validation`, `SqueezedOut`, `PreconditionFailed`, `Other`.
 #[macro_export]
 macro_rules! error_transaction {
    ($err_variant:ident, $fmt_str: literal $(,$arg: expr)*) => {
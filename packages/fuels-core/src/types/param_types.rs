@@ -1,7 +1,11 @@
 mod debug_with_depth;
 mod enum_variants;
 mod from_type_application;
+mod infer;
 mod param_type;
+mod structural_eq;
+mod token_validator;
 
 pub use enum_variants::*;
 pub use param_type::*;
+pub use token_validator::*;
@@ -1,4 +1,5 @@
 use crate::{
+    codec::EncoderConfig,
     constants::ENUM_DISCRIMINANT_BYTE_WIDTH,
     types::{
         errors::{error, Result},
@@ -67,6 +68,22 @@ impl EnumVariants {
             .ok_or_else(|| error!(Other, "enum variants are too wide"))
     }
 
+    /// Returns how many bytes the encoder will produce for this enum, failing the same way the
+    /// encoder would if the width exceeds `config.max_total_enum_width`. Enums containing only
+    /// units are encoded as just their discriminant and are not subject to the limit.
+    pub fn encoded_width(&self, config: &EncoderConfig) -> Result<usize> {
+        let width = self.compute_enum_width_in_bytes()?;
+
+        if !self.only_units_inside() && width > config.max_total_enum_width {
+            return Err(error!(
+                Codec,
+                "cannot encode enum with variants: {self:?}. It is `{width}` bytes wide. Try increasing maximum total enum width."
+            ));
+        }
+
+        Ok(width)
+    }
+
     /// Determines the padding needed for the provided enum variant (based on the width of the
     /// biggest variant) and returns it.
     pub fn compute_padding_amount_in_bytes(&self, variant_param_type: &ParamType) -> Result<usize> {
@@ -81,7 +98,11 @@ impl EnumVariants {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::to_named;
+    use crate::{
+        codec::ABIEncoder,
+        to_named,
+        types::{errors::Error, Token},
+    };
 
     #[test]
     fn test_get_heap_type_variant_discriminant() -> Result<()> {
@@ -114,4 +135,53 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn encoded_width_matches_encoder_output() -> Result<()> {
+        let variants = EnumVariants::new(to_named(&[
+            ParamType::U8,
+            ParamType::Array(Box::new(ParamType::U64), 3),
+        ]))?;
+        let token = Token::Enum(Box::new((0, Token::U8(1), variants.clone())));
+
+        let encoded = ABIEncoder::default().encode(&[token])?.resolve(0);
+
+        assert_eq!(variants.encoded_width(&EncoderConfig::default())?, 32);
+        assert_eq!(encoded.len(), 32);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unit_only_enums_are_a_single_word_wide() -> Result<()> {
+        let variants = EnumVariants::new(to_named(&[ParamType::Unit, ParamType::Unit]))?;
+        let config = EncoderConfig {
+            max_total_enum_width: 0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            variants.encoded_width(&config)?,
+            ENUM_DISCRIMINANT_BYTE_WIDTH
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn encoded_width_respects_max_total_enum_width() -> Result<()> {
+        let variants = EnumVariants::new(to_named(&[ParamType::U64, ParamType::B256]))?;
+        let config = EncoderConfig {
+            max_total_enum_width: 39,
+            ..Default::default()
+        };
+
+        let err = variants
+            .encoded_width(&config)
+            .expect_err("should fail because the enum is 40 bytes wide");
+
+        assert!(matches!(err, Error::Codec(_)));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,150 @@
+use crate::types::{
+    errors::{error, Result},
+    param_types::ParamType,
+    Token,
+};
+
+impl ParamType {
+    /// Reconstructs, on a best-effort basis, the type of `token`. Struct names aren't part of a
+    /// token, so inferred structs are unnamed and their fields are named after their position.
+    /// Enums use the variants embedded in their token. String tokens are inferred as slices or
+    /// arrays depending on their variant, and arrays take the length of the string.
+    ///
+    /// Fails if the type is under-determined: the element type of empty arrays and vectors is
+    /// unknown, as is the type of collections whose elements have different types.
+    pub fn infer_from(token: &Token) -> Result<ParamType> {
+        let param_type = match token {
+            Token::Unit => ParamType::Unit,
+            Token::Bool(_) => ParamType::Bool,
+            Token::U8(_) => ParamType::U8,
+            Token::U16(_) => ParamType::U16,
+            Token::U32(_) => ParamType::U32,
+            Token::U64(_) => ParamType::U64,
+            Token::U128(_) => ParamType::U128,
+            Token::U256(_) => ParamType::U256,
+            Token::B256(_) => ParamType::B256,
+            Token::Bytes(_) => ParamType::Bytes,
+            Token::String(_) => ParamType::String,
+            Token::RawSlice(_) => ParamType::RawSlice,
+            Token::StringSlice(_) => ParamType::StringSlice,
+            Token::StringArray(string) => ParamType::StringArray(string.data.len()),
+            Token::Array(tokens) => {
+                ParamType::Array(Box::new(infer_element_type(tokens, "array")?), tokens.len())
+            }
+            Token::Vector(tokens) => {
+                ParamType::Vector(Box::new(infer_element_type(tokens, "vector")?))
+            }
+            Token::Tuple(tokens) => ParamType::Tuple(
+                tokens
+                    .iter()
+                    .map(ParamType::infer_from)
+                    .collect::<Result<_>>()?,
+            ),
+            Token::Struct(tokens) => ParamType::Struct {
+                name: String::new(),
+                fields: tokens
+                    .iter()
+                    .enumerate()
+                    .map(|(index, token)| Ok((index.to_string(), ParamType::infer_from(token)?)))
+                    .collect::<Result<_>>()?,
+                generics: vec![],
+            },
+            Token::Enum(selector) => {
+                let (_, _, enum_variants) = selector.as_ref();
+
+                ParamType::Enum {
+                    name: String::new(),
+                    enum_variants: enum_variants.clone(),
+                    generics: vec![],
+                }
+            }
+        };
+
+        Ok(param_type)
+    }
+}
+
+fn infer_element_type(tokens: &[Token], collection: &str) -> Result<ParamType> {
+    let Some((first, rest)) = tokens.split_first() else {
+        return Err(error!(
+            Codec,
+            "cannot infer the element type of an empty {collection}"
+        ));
+    };
+
+    let element_type = ParamType::infer_from(first)?;
+    for (index, token) in rest.iter().enumerate() {
+        let other = ParamType::infer_from(token)?;
+
+        if other != element_type {
+            return Err(error!(
+                Codec,
+                "cannot infer the element type of the {collection}: element {} is `{other:?}` \
+                    but the first one is `{element_type:?}`",
+                index + 1
+            ));
+        }
+    }
+
+    Ok(element_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        to_named,
+        types::{param_types::EnumVariants, StaticStringToken},
+    };
+
+    #[test]
+    fn type_is_inferred_from_token() -> Result<()> {
+        let enum_variants = EnumVariants::new(to_named(&[ParamType::Unit, ParamType::U64]))?;
+        let token = Token::Struct(vec![
+            Token::Array(vec![Token::U8(1), Token::U8(2)]),
+            Token::StringArray(StaticStringToken::new("fuel".to_string(), Some(4))),
+            Token::Enum(Box::new((0, Token::Unit, enum_variants.clone()))),
+        ]);
+
+        let param_type = ParamType::infer_from(&token)?;
+
+        let expected = ParamType::Struct {
+            name: String::new(),
+            fields: vec![
+                (
+                    "0".to_string(),
+                    ParamType::Array(Box::new(ParamType::U8), 2),
+                ),
+                ("1".to_string(), ParamType::StringArray(4)),
+                (
+                    "2".to_string(),
+                    ParamType::Enum {
+                        name: String::new(),
+                        enum_variants,
+                        generics: vec![],
+                    },
+                ),
+            ],
+            generics: vec![],
+        };
+        assert_eq!(param_type, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn under_determined_collections_are_rejected() {
+        let err = ParamType::infer_from(&Token::Vector(vec![]))
+            .expect_err("should fail because the vector is empty");
+        assert_eq!(
+            err.to_string(),
+            "codec: cannot infer the element type of an empty vector"
+        );
+
+        let err = ParamType::infer_from(&Token::Array(vec![Token::U8(1), Token::U16(2)]))
+            .expect_err("should fail because the elements have different types");
+        assert!(err
+            .to_string()
+            .contains("element 1 is `U16` but the first one is `U8`"));
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     types::{
         errors::{error, Result},
         param_types::{debug_with_depth::DebugWithDepth, EnumVariants},
+        Token,
     },
 };
 
@@ -47,6 +48,49 @@ pub enum ReturnLocation {
 }
 
 impl ParamType {
+    /// The dynamically sized `str`, encoded as a pointer and a length. Matches
+    /// `Token::StringSlice`.
+    pub fn string_slice() -> Self {
+        ParamType::StringSlice
+    }
+
+    /// The fixed size `str[len]`, encoded inline. Matches `Token::StringArray`.
+    pub fn string_array(len: usize) -> Self {
+        ParamType::StringArray(len)
+    }
+
+    /// The `Option<String>` read from a Sway `StorageString`. Matches
+    /// [`Token::storage_string`].
+    pub fn storage_string() -> Self {
+        Self::option_of(ParamType::String)
+    }
+
+    /// The `Option<Bytes>` read from a Sway `StorageBytes`. Matches
+    /// [`Token::storage_bytes`].
+    pub fn storage_bytes() -> Self {
+        Self::option_of(ParamType::Bytes)
+    }
+
+    pub(crate) fn option_of(param_type: ParamType) -> Self {
+        ParamType::Enum {
+            name: "Option".to_string(),
+            enum_variants: EnumVariants::new(vec![
+                ("None".to_string(), ParamType::Unit),
+                ("Some".to_string(), param_type.clone()),
+            ])
+            .expect("two variants are valid"),
+            generics: vec![param_type],
+        }
+    }
+
+    pub fn is_string_slice(&self) -> bool {
+        matches!(self, ParamType::StringSlice)
+    }
+
+    pub fn is_string_array(&self) -> bool {
+        matches!(self, ParamType::StringArray(_))
+    }
+
     // Depending on the type, the returned value will be stored
     // either in `Return` or `ReturnData`.
     pub fn get_return_location(&self) -> ReturnLocation {
@@ -141,6 +185,110 @@ impl ParamType {
         Ok(())
     }
 
+    /// Checks that `token` has the shape described by this type. Tokens nested deeper than
+    /// `max_depth` are rejected, so that arbitrarily deep types, such as unrolled recursive
+    /// ones, fail with an error instead of exhausting the stack.
+    pub fn validate_token(&self, token: &Token, max_depth: usize) -> Result<()> {
+        self.validate_token_at_depth(token, max_depth, 0)
+    }
+
+    fn validate_token_at_depth(&self, token: &Token, max_depth: usize, depth: usize) -> Result<()> {
+        let mismatch = || {
+            error!(
+                Codec,
+                "token `{token}` doesn't match type `{:?}`",
+                DebugWithDepth::new(self, max_depth)
+            )
+        };
+        let descend = || {
+            if depth >= max_depth {
+                return Err(error!(
+                    Codec,
+                    "depth limit `{max_depth}` reached while validating token. Try increasing it"
+                ));
+            }
+            Ok(depth + 1)
+        };
+        match (self, token) {
+            (ParamType::Unit, Token::Unit)
+            | (ParamType::Bool, Token::Bool(_))
+            | (ParamType::U8, Token::U8(_))
+            | (ParamType::U16, Token::U16(_))
+            | (ParamType::U32, Token::U32(_))
+            | (ParamType::U64, Token::U64(_))
+            | (ParamType::U128, Token::U128(_))
+            | (ParamType::U256, Token::U256(_))
+            | (ParamType::B256, Token::B256(_))
+            | (ParamType::Bytes, Token::Bytes(_))
+            | (ParamType::String, Token::String(_))
+            | (ParamType::RawSlice, Token::RawSlice(_))
+            | (ParamType::StringSlice, Token::StringSlice(_)) => Ok(()),
+            (ParamType::StringArray(len), Token::StringArray(string))
+                if string.data.len() == *len =>
+            {
+                Ok(())
+            }
+            (ParamType::StringArray(len), Token::StringSlice(_)) => Err(error!(
+                Codec,
+                "a `Token::StringSlice` can't be used for the string array `str[{len}]`. \
+                    Use `Token::StringArray` instead"
+            )),
+            (ParamType::StringSlice, Token::StringArray(_)) => Err(error!(
+                Codec,
+                "a `Token::StringArray` can't be used for a string slice `str`. \
+                    Use `Token::StringSlice` instead"
+            )),
+            (ParamType::Array(inner, len), Token::Array(tokens)) if tokens.len() == *len => {
+                Self::validate_tokens(
+                    std::iter::repeat(inner.as_ref()),
+                    tokens,
+                    max_depth,
+                    descend()?,
+                )
+            }
+            (ParamType::Vector(inner), Token::Vector(tokens)) => Self::validate_tokens(
+                std::iter::repeat(inner.as_ref()),
+                tokens,
+                max_depth,
+                descend()?,
+            ),
+            (ParamType::Tuple(param_types), Token::Tuple(tokens))
+                if tokens.len() == param_types.len() =>
+            {
+                Self::validate_tokens(param_types.iter(), tokens, max_depth, descend()?)
+            }
+            (ParamType::Struct { fields, .. }, Token::Struct(tokens))
+                if tokens.len() == fields.len() =>
+            {
+                Self::validate_tokens(
+                    fields.iter().map(|(_, param_type)| param_type),
+                    tokens,
+                    max_depth,
+                    descend()?,
+                )
+            }
+            (ParamType::Enum { enum_variants, .. }, Token::Enum(selector)) => {
+                let depth = descend()?;
+                let (discriminant, token_within_enum, _) = selector.as_ref();
+                let (_, variant_param_type) = enum_variants.select_variant(*discriminant)?;
+
+                variant_param_type.validate_token_at_depth(token_within_enum, max_depth, depth)
+            }
+            _ => Err(mismatch()),
+        }
+    }
+
+    fn validate_tokens<'a>(
+        param_types: impl Iterator<Item = &'a ParamType>,
+        tokens: &[Token],
+        max_depth: usize,
+        depth: usize,
+    ) -> Result<()> {
+        param_types.zip(tokens).try_for_each(|(param_type, token)| {
+            param_type.validate_token_at_depth(token, max_depth, depth)
+        })
+    }
+
     pub fn is_extra_receipt_needed(&self, top_level_type: bool) -> bool {
         match self {
             ParamType::Vector(_) | ParamType::Bytes | ParamType::String => true,
@@ -228,8 +376,11 @@ impl ParamType {
 mod tests {
     use super::*;
     use crate::{
-        checked_round_up_to_word_alignment, codec::DecoderConfig, constants::WORD_SIZE, to_named,
-        types::param_types::ParamType,
+        checked_round_up_to_word_alignment,
+        codec::{ABIEncoder, DecoderConfig, EncoderConfig},
+        constants::WORD_SIZE,
+        to_named,
+        types::{param_types::ParamType, StaticStringToken},
     };
 
     const WIDTH_OF_B256: usize = 32;
@@ -605,4 +756,115 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn validate_token_accepts_matching_tokens() -> Result<()> {
+        let param_type = ParamType::Struct {
+            name: "".to_string(),
+            fields: to_named(&[
+                ParamType::Array(Box::new(ParamType::U8), 2),
+                ParamType::Vector(Box::new(ParamType::Bool)),
+            ]),
+            generics: vec![],
+        };
+        let token = Token::Struct(vec![
+            Token::Array(vec![Token::U8(1), Token::U8(2)]),
+            Token::Vector(vec![Token::Bool(true)]),
+        ]);
+
+        param_type.validate_token(&token, 10)
+    }
+
+    #[test]
+    fn validate_token_rejects_mismatches() {
+        let array = ParamType::Array(Box::new(ParamType::U8), 2);
+
+        for token in [
+            Token::Array(vec![Token::U8(1)]),
+            Token::Array(vec![Token::U8(1), Token::U16(2)]),
+            Token::Vector(vec![Token::U8(1), Token::U8(2)]),
+        ] {
+            array
+                .validate_token(&token, 10)
+                .expect_err("should not match");
+        }
+    }
+
+    #[test]
+    fn string_slices_and_arrays_are_not_interchangeable() -> Result<()> {
+        let slice = ParamType::string_slice();
+        let array = ParamType::string_array(4);
+        let slice_token = Token::StringSlice(StaticStringToken::new("fuel".to_string(), None));
+        let array_token = Token::StringArray(StaticStringToken::new("fuel".to_string(), Some(4)));
+
+        assert!(slice.is_string_slice() && !slice.is_string_array());
+        assert!(array.is_string_array() && !array.is_string_slice());
+
+        slice.validate_token(&slice_token, 10)?;
+        array.validate_token(&array_token, 10)?;
+
+        let err = array
+            .validate_token(&slice_token, 10)
+            .expect_err("should fail because a slice token was given for an array");
+        assert!(err.to_string().contains("Use `Token::StringArray` instead"));
+
+        let err = slice
+            .validate_token(&array_token, 10)
+            .expect_err("should fail because an array token was given for a slice");
+        assert!(err.to_string().contains("Use `Token::StringSlice` instead"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn deeply_nested_recursive_type_fails_cleanly() {
+        // A linked list `Node { next: Option<Node> }` unrolled far beyond the limit
+        const NESTING: usize = 100;
+        let max_depth = EncoderConfig::default().max_depth;
+
+        let option_of = |inner: ParamType| ParamType::Enum {
+            name: "Option".to_string(),
+            enum_variants: EnumVariants::new(to_named(&[ParamType::Unit, inner])).unwrap(),
+            generics: vec![],
+        };
+        let node_of = |next: ParamType| ParamType::Struct {
+            name: "Node".to_string(),
+            fields: to_named(&[option_of(next)]),
+            generics: vec![],
+        };
+
+        let (param_type, token) =
+            (0..NESTING).fold((ParamType::Unit, Token::Unit), |(param_type, token), _| {
+                let param_type = node_of(param_type);
+                let ParamType::Struct { fields, .. } = &param_type else {
+                    unreachable!()
+                };
+                let ParamType::Enum { enum_variants, .. } = &fields[0].1 else {
+                    unreachable!()
+                };
+                let token = Token::Struct(vec![Token::Enum(Box::new((
+                    1,
+                    token,
+                    enum_variants.clone(),
+                )))]);
+
+                (param_type, token)
+            });
+
+        let error = param_type
+            .validate_token(&token, max_depth)
+            .expect_err("should exceed the depth limit");
+
+        assert!(error
+            .to_string()
+            .contains("depth limit `45` reached while validating token"));
+
+        let error = ABIEncoder::default()
+            .encode(&[token])
+            .expect_err("should exceed the depth limit");
+
+        assert!(error
+            .to_string()
+            .contains("depth limit `45` reached while encoding"));
+    }
 }
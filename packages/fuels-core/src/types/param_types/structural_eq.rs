@@ -0,0 +1,113 @@
+use crate::types::param_types::ParamType;
+
+impl ParamType {
+    /// Compares two types ignoring the names of structs, enums, fields and variants, as well
+    /// as generic parameters, none of which are part of the encoding. The order of the fields
+    /// and variants, the primitive widths and the array lengths must match.
+    pub fn structurally_eq(&self, other: &ParamType) -> bool {
+        match (self, other) {
+            (ParamType::Tuple(types), ParamType::Tuple(other_types)) => {
+                all_structurally_eq(types.iter(), other_types.iter())
+            }
+            (ParamType::Array(element, len), ParamType::Array(other_element, other_len)) => {
+                len == other_len && element.structurally_eq(other_element)
+            }
+            (ParamType::Vector(element), ParamType::Vector(other_element)) => {
+                element.structurally_eq(other_element)
+            }
+            (
+                ParamType::Struct { fields, .. },
+                ParamType::Struct {
+                    fields: other_fields,
+                    ..
+                },
+            ) => all_structurally_eq(
+                fields.iter().map(|(_, param_type)| param_type),
+                other_fields.iter().map(|(_, param_type)| param_type),
+            ),
+            (
+                ParamType::Enum { enum_variants, .. },
+                ParamType::Enum {
+                    enum_variants: other_variants,
+                    ..
+                },
+            ) => all_structurally_eq(enum_variants.param_types(), other_variants.param_types()),
+            (
+                ParamType::Tuple(_)
+                | ParamType::Array(..)
+                | ParamType::Vector(_)
+                | ParamType::Struct { .. }
+                | ParamType::Enum { .. },
+                _,
+            ) => false,
+            _ => self == other,
+        }
+    }
+}
+
+fn all_structurally_eq<'a>(
+    types: impl ExactSizeIterator<Item = &'a ParamType>,
+    other_types: impl ExactSizeIterator<Item = &'a ParamType>,
+) -> bool {
+    types.len() == other_types.len()
+        && types
+            .zip(other_types)
+            .all(|(param_type, other)| param_type.structurally_eq(other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{errors::Result, param_types::EnumVariants};
+
+    fn position(names: [&str; 4], len: usize, amount_type: ParamType) -> Result<ParamType> {
+        let [name, first_field, second_field, variant] = names;
+        let enum_variants = EnumVariants::new(vec![
+            (variant.to_string(), ParamType::Unit),
+            ("Some".to_string(), amount_type),
+        ])?;
+
+        Ok(ParamType::Struct {
+            name: name.to_string(),
+            fields: vec![
+                (
+                    first_field.to_string(),
+                    ParamType::Array(Box::new(ParamType::U8), len),
+                ),
+                (
+                    second_field.to_string(),
+                    ParamType::Enum {
+                        name: format!("{name}Limit"),
+                        enum_variants,
+                        generics: vec![],
+                    },
+                ),
+            ],
+            generics: vec![],
+        })
+    }
+
+    #[test]
+    fn names_are_ignored() -> Result<()> {
+        let position_type = position(["Position", "fills", "limit", "None"], 2, ParamType::U64)?;
+        let renamed_type = position(["", "0", "1", "Nothing"], 2, ParamType::U64)?;
+
+        assert!(position_type.structurally_eq(&renamed_type));
+        assert_ne!(position_type, renamed_type);
+
+        Ok(())
+    }
+
+    #[test]
+    fn widths_and_lengths_are_respected() -> Result<()> {
+        let names = ["Position", "fills", "limit", "None"];
+        let position_type = position(names, 2, ParamType::U64)?;
+
+        assert!(!position_type.structurally_eq(&position(names, 3, ParamType::U64)?));
+        assert!(!position_type.structurally_eq(&position(names, 2, ParamType::U32)?));
+        assert!(!ParamType::Tuple(vec![ParamType::U8])
+            .structurally_eq(&ParamType::Vector(Box::new(ParamType::U8))));
+
+        Ok(())
+    }
+}
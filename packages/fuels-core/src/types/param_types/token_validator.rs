@@ -0,0 +1,316 @@
+use std::ops::Range;
+
+use crate::{
+    codec::EncoderConfig,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        Token,
+    },
+};
+
+/// Compiles `ParamType`s into [`CompiledValidator`]s, for hot paths validating many tokens
+/// against the same type.
+pub struct TokenValidator;
+
+impl TokenValidator {
+    /// Flattens `param_type` into a validation plan that can be reused across many
+    /// [`CompiledValidator::validate`] calls, instead of walking the type every time as
+    /// [`ParamType::validate_token`] does.
+    pub fn compile(param_type: &ParamType) -> CompiledValidator {
+        let mut validator = CompiledValidator {
+            param_type: param_type.clone(),
+            max_depth: EncoderConfig::default().max_depth,
+            steps: vec![],
+            children: vec![],
+        };
+        validator.push_step(param_type);
+
+        validator
+    }
+}
+
+/// A `ParamType` flattened into a validation plan, see [`TokenValidator::compile`].
+#[derive(Debug, Clone)]
+pub struct CompiledValidator {
+    param_type: ParamType,
+    max_depth: usize,
+    /// The root step is the last one, since children are compiled before their parent.
+    steps: Vec<Step>,
+    /// The steps of the fields of tuples and structs, and of the variants of enums.
+    children: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Step {
+    Unit,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    B256,
+    Bytes,
+    String,
+    RawSlice,
+    StringSlice,
+    StringArray(usize),
+    Array { element: usize, len: usize },
+    Vector { element: usize },
+    Tuple(Range<usize>),
+    Struct(Range<usize>),
+    Enum(Range<usize>),
+}
+
+impl Step {
+    fn is_nested(&self) -> bool {
+        matches!(
+            self,
+            Step::Array { .. }
+                | Step::Vector { .. }
+                | Step::Tuple(_)
+                | Step::Struct(_)
+                | Step::Enum(_)
+        )
+    }
+}
+
+impl CompiledValidator {
+    /// Checks that `token` has the shape of the compiled type. Accepts the same tokens and
+    /// reports the same errors as [`ParamType::validate_token`] with the default depth limit.
+    pub fn validate(&self, token: &Token) -> Result<()> {
+        if self.matches(token) {
+            return Ok(());
+        }
+
+        // The plan doesn't know where the mismatch lies, the recursive validation does
+        Err(self
+            .param_type
+            .validate_token(token, self.max_depth)
+            .err()
+            .unwrap_or_else(|| error!(Codec, "token `{token}` doesn't match the compiled type")))
+    }
+
+    fn push_step(&mut self, param_type: &ParamType) -> usize {
+        let step = match param_type {
+            ParamType::Unit => Step::Unit,
+            ParamType::Bool => Step::Bool,
+            ParamType::U8 => Step::U8,
+            ParamType::U16 => Step::U16,
+            ParamType::U32 => Step::U32,
+            ParamType::U64 => Step::U64,
+            ParamType::U128 => Step::U128,
+            ParamType::U256 => Step::U256,
+            ParamType::B256 => Step::B256,
+            ParamType::Bytes => Step::Bytes,
+            ParamType::String => Step::String,
+            ParamType::RawSlice => Step::RawSlice,
+            ParamType::StringSlice => Step::StringSlice,
+            ParamType::StringArray(len) => Step::StringArray(*len),
+            ParamType::Array(element, len) => Step::Array {
+                element: self.push_step(element),
+                len: *len,
+            },
+            ParamType::Vector(element) => Step::Vector {
+                element: self.push_step(element),
+            },
+            ParamType::Tuple(param_types) => Step::Tuple(self.compile_children(param_types.iter())),
+            ParamType::Struct { fields, .. } => {
+                Step::Struct(self.compile_children(fields.iter().map(|(_, param_type)| param_type)))
+            }
+            ParamType::Enum { enum_variants, .. } => {
+                Step::Enum(self.compile_children(enum_variants.param_types()))
+            }
+        };
+        self.steps.push(step);
+
+        self.steps.len() - 1
+    }
+
+    fn compile_children<'a>(
+        &mut self,
+        param_types: impl Iterator<Item = &'a ParamType>,
+    ) -> Range<usize> {
+        let steps: Vec<_> = param_types
+            .map(|param_type| self.push_step(param_type))
+            .collect();
+
+        let start = self.children.len();
+        self.children.extend(steps);
+
+        start..self.children.len()
+    }
+
+    // Iterative, so that deep tokens can't exhaust the stack. Still bounded by `max_depth`, as
+    // the recursive validation is
+    fn matches(&self, token: &Token) -> bool {
+        let mut pending = vec![(self.steps.len() - 1, token, 0)];
+
+        while let Some((step, token, depth)) = pending.pop() {
+            let step = &self.steps[step];
+            if step.is_nested() && depth >= self.max_depth {
+                return false;
+            }
+            let depth = depth + 1;
+
+            let matches = match (step, token) {
+                (Step::Unit, Token::Unit)
+                | (Step::Bool, Token::Bool(_))
+                | (Step::U8, Token::U8(_))
+                | (Step::U16, Token::U16(_))
+                | (Step::U32, Token::U32(_))
+                | (Step::U64, Token::U64(_))
+                | (Step::U128, Token::U128(_))
+                | (Step::U256, Token::U256(_))
+                | (Step::B256, Token::B256(_))
+                | (Step::Bytes, Token::Bytes(_))
+                | (Step::String, Token::String(_))
+                | (Step::RawSlice, Token::RawSlice(_))
+                | (Step::StringSlice, Token::StringSlice(_)) => true,
+                (Step::StringArray(len), Token::StringArray(string)) => string.data.len() == *len,
+                (Step::Array { element, len }, Token::Array(tokens)) if tokens.len() == *len => {
+                    pending.extend(tokens.iter().map(|token| (*element, token, depth)));
+                    true
+                }
+                (Step::Vector { element }, Token::Vector(tokens)) => {
+                    pending.extend(tokens.iter().map(|token| (*element, token, depth)));
+                    true
+                }
+                (Step::Tuple(fields), Token::Tuple(tokens))
+                | (Step::Struct(fields), Token::Struct(tokens))
+                    if tokens.len() == fields.len() =>
+                {
+                    pending.extend(
+                        self.children[fields.clone()]
+                            .iter()
+                            .zip(tokens)
+                            .map(|(field, token)| (*field, token, depth)),
+                    );
+                    true
+                }
+                (Step::Enum(variants), Token::Enum(selector)) => {
+                    let (discriminant, token_within_enum, _) = selector.as_ref();
+
+                    match usize::try_from(*discriminant)
+                        .ok()
+                        .and_then(|discriminant| self.children[variants.clone()].get(discriminant))
+                    {
+                        Some(variant) => {
+                            pending.push((*variant, token_within_enum, depth));
+                            true
+                        }
+                        None => false,
+                    }
+                }
+                _ => false,
+            };
+
+            if !matches {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codec::token_trees, types::StaticStringToken};
+
+    #[test]
+    fn accepts_what_the_recursive_validation_accepts() -> Result<()> {
+        let token = token_trees::mixed_struct();
+        let param_type = ParamType::infer_from(&token)?;
+
+        let validator = TokenValidator::compile(&param_type);
+
+        validator.validate(&token)?;
+
+        let token = token_trees::large_vector(100);
+        TokenValidator::compile(&ParamType::infer_from(&token)?).validate(&token)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_with_the_recursive_validation_error() -> Result<()> {
+        let param_type = ParamType::Struct {
+            name: "Item".to_string(),
+            fields: vec![
+                ("id".to_string(), ParamType::U64),
+                ("name".to_string(), ParamType::StringArray(4)),
+                ("price".to_string(), ParamType::option_of(ParamType::U32)),
+            ],
+            generics: vec![],
+        };
+        let validator = TokenValidator::compile(&param_type);
+        let max_depth = EncoderConfig::default().max_depth;
+
+        let ParamType::Enum { enum_variants, .. } = ParamType::option_of(ParamType::U32) else {
+            unreachable!()
+        };
+        let price = |discriminant, token| {
+            Token::Enum(Box::new((discriminant, token, enum_variants.clone())))
+        };
+        let item = |name: &str, price| {
+            Token::Struct(vec![
+                Token::U64(1),
+                Token::StringArray(StaticStringToken::new(name.to_string(), Some(4))),
+                price,
+            ])
+        };
+
+        validator.validate(&item("fuel", price(1, Token::U32(10))))?;
+
+        for invalid in [
+            item("fuels", price(1, Token::U32(10))),
+            item("fuel", price(1, Token::U64(10))),
+            item("fuel", price(2, Token::Unit)),
+            Token::Tuple(vec![]),
+        ] {
+            let expected = param_type
+                .validate_token(&invalid, max_depth)
+                .expect_err("should be invalid")
+                .to_string();
+
+            let err = validator.validate(&invalid).expect_err("should be invalid");
+
+            assert_eq!(err.to_string(), expected);
+        }
+
+        Ok(())
+    }
+    #[test]
+    fn rejects_tokens_nested_beyond_the_depth_limit() -> Result<()> {
+        let max_depth = EncoderConfig::default().max_depth;
+        let nested = |levels| {
+            (0..levels).fold((ParamType::U8, Token::U8(1)), |(param_type, token), _| {
+                (
+                    ParamType::Vector(Box::new(param_type)),
+                    Token::Vector(vec![token]),
+                )
+            })
+        };
+
+        let (param_type, token) = nested(max_depth);
+        TokenValidator::compile(&param_type).validate(&token)?;
+
+        let (param_type, token) = nested(max_depth + 1);
+        let expected = param_type
+            .validate_token(&token, max_depth)
+            .expect_err("should be too deep")
+            .to_string();
+
+        let err = TokenValidator::compile(&param_type)
+            .validate(&token)
+            .expect_err("should be too deep");
+
+        assert_eq!(err.to_string(), expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+mod array;
+mod b256;
+#[cfg(feature = "cbor")]
+mod cbor;
+mod default;
+mod diff;
+mod flatten;
+mod json;
+mod macros;
+mod numeric;
+mod path;
+mod raw_slice;
+mod result;
+mod stable_hash;
+mod storage;
+mod string;
+mod tagged;
+mod vector;
+
+pub use diff::TokenChange;
+pub use flatten::ScalarValue;
+pub use tagged::TAGGED_FORMAT_VERSION;
@@ -0,0 +1,83 @@
+use crate::{
+    codec::EncoderConfig,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        Token,
+    },
+};
+
+impl Token {
+    /// Creates a `Token::Array` after checking that `elements` match the element type and the
+    /// length of `param_type`, which must be a `ParamType::Array`.
+    pub fn array_of(param_type: &ParamType, elements: Vec<Token>) -> Result<Token> {
+        let ParamType::Array(element_type, len) = param_type else {
+            return Err(error!(
+                Codec,
+                "expected an array type, got `{param_type:?}`"
+            ));
+        };
+
+        if elements.len() != *len {
+            return Err(error!(
+                Codec,
+                "array of `{element_type:?}` expects {len} elements, got {}",
+                elements.len()
+            ));
+        }
+
+        let max_depth = EncoderConfig::default().max_depth;
+        for (index, element) in elements.iter().enumerate() {
+            element_type
+                .validate_token(element, max_depth)
+                .map_err(|e| error!(Codec, "invalid array element at index {index}: {e}"))?;
+        }
+
+        Ok(Token::Array(elements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_is_created_when_elements_match() -> Result<()> {
+        let param_type = ParamType::Array(Box::new(ParamType::U8), 2);
+
+        let token = Token::array_of(&param_type, vec![Token::U8(1), Token::U8(2)])?;
+
+        assert_eq!(token, Token::Array(vec![Token::U8(1), Token::U8(2)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn wrong_length_is_rejected() {
+        let param_type = ParamType::Array(Box::new(ParamType::U8), 2);
+
+        let err = Token::array_of(&param_type, vec![Token::U8(1)]).expect_err("should fail");
+
+        assert_eq!(
+            err.to_string(),
+            "codec: array of `U8` expects 2 elements, got 1"
+        );
+    }
+
+    #[test]
+    fn heterogeneous_elements_are_rejected() {
+        let param_type = ParamType::Array(Box::new(ParamType::U8), 2);
+
+        let err = Token::array_of(&param_type, vec![Token::U8(1), Token::Bool(true)])
+            .expect_err("should fail");
+
+        assert!(err.to_string().contains("invalid array element at index 1"));
+    }
+
+    #[test]
+    fn non_array_types_are_rejected() {
+        let err = Token::array_of(&ParamType::U8, vec![]).expect_err("should fail");
+
+        assert_eq!(err.to_string(), "codec: expected an array type, got `U8`");
+    }
+}
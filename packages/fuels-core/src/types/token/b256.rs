@@ -0,0 +1,152 @@
+use crate::{
+    traits::Tokenizable,
+    types::{
+        errors::{error, Error, Result},
+        Bits256, Token, B512,
+    },
+};
+
+const B256_HEX_LEN: usize = 64;
+
+impl Token {
+    /// Creates a `Token::B256` from its canonical hex representation: 64 hex characters,
+    /// optionally prefixed with `0x`.
+    pub fn b256_from_hex(hex: &str) -> Result<Token> {
+        let stripped = hex.strip_prefix("0x").unwrap_or(hex);
+
+        if stripped.len() != B256_HEX_LEN {
+            return Err(error!(
+                Codec,
+                "expected {B256_HEX_LEN} hex characters for a `b256`, got {}",
+                stripped.len()
+            ));
+        }
+
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(stripped, &mut bytes)
+            .map_err(|e| error!(Codec, "invalid `b256` hex `{hex}`: {e}"))?;
+
+        Ok(Token::B256(bytes))
+    }
+
+    /// Returns the `0x` prefixed hex representation of a `Token::B256`.
+    pub fn b256_to_hex(&self) -> Result<String> {
+        match self {
+            Token::B256(bytes) => Ok(format!("0x{}", hex::encode(bytes))),
+            _ => Err(error!(Codec, "expected a `Token::B256`, got `{self}`")),
+        }
+    }
+}
+
+impl From<[u8; 32]> for Token {
+    fn from(bytes: [u8; 32]) -> Self {
+        Token::B256(bytes)
+    }
+}
+
+/// Produces the token of a `B512`, i.e. a struct holding two `b256` halves.
+impl From<[u8; 64]> for Token {
+    fn from(bytes: [u8; 64]) -> Self {
+        let (high, low) = bytes.split_at(32);
+        let halves = (
+            Bits256(high.try_into().expect("slice is 32 bytes long")),
+            Bits256(low.try_into().expect("slice is 32 bytes long")),
+        );
+
+        B512::from(halves).into_token()
+    }
+}
+
+/// Produces a `B256` token from 32 bytes and a `B512` token from 64 bytes. Fails on any other
+/// length.
+impl TryFrom<&[u8]> for Token {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        if let Ok(bytes) = <[u8; 32]>::try_from(bytes) {
+            return Ok(bytes.into());
+        }
+
+        if let Ok(bytes) = <[u8; 64]>::try_from(bytes) {
+            return Ok(bytes.into());
+        }
+
+        Err(error!(
+            Codec,
+            "expected 32 bytes for a `b256` or 64 bytes for a `B512`, got {}",
+            bytes.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+    #[test]
+    fn b256_hex_roundtrip() -> Result<()> {
+        let token = Token::b256_from_hex(HEX)?;
+
+        assert_eq!(token, Token::B256([1; 32]));
+        assert_eq!(token.b256_to_hex()?, HEX);
+
+        Ok(())
+    }
+
+    #[test]
+    fn b256_from_hex_accepts_unprefixed_hex() -> Result<()> {
+        let token = Token::b256_from_hex(HEX.strip_prefix("0x").unwrap())?;
+
+        assert_eq!(token, Token::B256([1; 32]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn b256_from_hex_validates_input() {
+        let err = Token::b256_from_hex("0x0101").expect_err("should fail on short input");
+        assert_eq!(
+            err.to_string(),
+            "codec: expected 64 hex characters for a `b256`, got 4"
+        );
+
+        let invalid_char = format!("0x{}", "g".repeat(64));
+        let err = Token::b256_from_hex(&invalid_char).expect_err("should fail on non-hex input");
+        assert!(err.to_string().contains("invalid `b256` hex"));
+    }
+
+    #[test]
+    fn b256_to_hex_rejects_other_tokens() {
+        let err = Token::U64(1)
+            .b256_to_hex()
+            .expect_err("should fail on non-b256 token");
+
+        assert!(err.to_string().contains("expected a `Token::B256`"));
+    }
+
+    #[test]
+    fn fixed_size_bytes_are_converted_by_length() -> Result<()> {
+        let bytes: Vec<u8> = (0..64).collect();
+
+        assert_eq!(
+            Token::try_from(&bytes[..32])?,
+            Token::B256(bytes[..32].try_into().unwrap())
+        );
+
+        let b512 = B512::from((
+            Bits256(bytes[..32].try_into().unwrap()),
+            Bits256(bytes[32..].try_into().unwrap()),
+        ));
+        assert_eq!(Token::try_from(bytes.as_slice())?, b512.into_token());
+
+        let err = Token::try_from(&bytes[..33]).expect_err("should fail on 33 bytes");
+        assert_eq!(
+            err.to_string(),
+            "codec: expected 32 bytes for a `b256` or 64 bytes for a `B512`, got 33"
+        );
+
+        Ok(())
+    }
+}
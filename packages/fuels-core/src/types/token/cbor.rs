@@ -0,0 +1,74 @@
+use crate::{
+    codec::EncoderConfig,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        Token,
+    },
+};
+
+impl Token {
+    /// Serializes the token into CBOR after checking it against `param_type`. Every value is
+    /// tagged with its `Token` variant and enums carry their variants, so [`Token::from_cbor`]
+    /// restores the exact same token.
+    pub fn to_cbor(&self, param_type: &ParamType) -> Result<Vec<u8>> {
+        param_type.validate_token(self, EncoderConfig::default().max_depth)?;
+
+        let mut bytes = vec![];
+        ciborium::into_writer(self, &mut bytes)
+            .map_err(|e| error!(Codec, "failed to serialize token to CBOR: {e}"))?;
+
+        Ok(bytes)
+    }
+
+    /// Deserializes a token produced by [`Token::to_cbor`] and checks it against `param_type`.
+    pub fn from_cbor(bytes: &[u8], param_type: &ParamType) -> Result<Token> {
+        let token: Token = ciborium::from_reader(bytes)
+            .map_err(|e| error!(Codec, "failed to deserialize token from CBOR: {e}"))?;
+
+        param_type.validate_token(&token, EncoderConfig::default().max_depth)?;
+
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_named, types::param_types::EnumVariants};
+
+    #[test]
+    fn token_roundtrips_through_cbor() -> Result<()> {
+        let enum_variants = EnumVariants::new(to_named(&[ParamType::Unit, ParamType::U64]))?;
+        let param_type = ParamType::Tuple(vec![
+            ParamType::Enum {
+                name: "Option".to_string(),
+                enum_variants: enum_variants.clone(),
+                generics: vec![],
+            },
+            ParamType::Vector(Box::new(ParamType::B256)),
+        ]);
+        let token = Token::Tuple(vec![
+            Token::Enum(Box::new((1, Token::U64(42), enum_variants))),
+            Token::Vector(vec![Token::B256([7; 32])]),
+        ]);
+
+        let bytes = token.to_cbor(&param_type)?;
+
+        assert_eq!(Token::from_cbor(&bytes, &param_type)?, token);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cbor_is_checked_against_the_type() -> Result<()> {
+        let bytes = Token::U64(1).to_cbor(&ParamType::U64)?;
+
+        Token::from_cbor(&bytes, &ParamType::Bool).expect_err("should fail on type mismatch");
+        Token::U8(1)
+            .to_cbor(&ParamType::U64)
+            .expect_err("should fail on type mismatch");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    codec::EncoderConfig,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        StaticStringToken, Token, U256,
+    },
+};
+
+impl Token {
+    /// Creates the "zero value" of `param_type`: zero for numbers, `false`, a zeroed `b256`,
+    /// empty dynamic collections and strings, NUL-filled string arrays and the first variant of
+    /// enums. Types nested deeper than the encoder's default `max_depth` are rejected.
+    pub fn default_for(param_type: &ParamType) -> Result<Token> {
+        default_at_depth(param_type, EncoderConfig::default().max_depth, 0)
+    }
+}
+
+fn default_at_depth(param_type: &ParamType, max_depth: usize, depth: usize) -> Result<Token> {
+    if depth > max_depth {
+        return Err(error!(
+            Codec,
+            "depth limit `{max_depth}` reached while creating a default token"
+        ));
+    }
+    let default_of = |param_type| default_at_depth(param_type, max_depth, depth + 1);
+
+    let token = match param_type {
+        ParamType::Unit => Token::Unit,
+        ParamType::Bool => Token::Bool(false),
+        ParamType::U8 => Token::U8(0),
+        ParamType::U16 => Token::U16(0),
+        ParamType::U32 => Token::U32(0),
+        ParamType::U64 => Token::U64(0),
+        ParamType::U128 => Token::U128(0),
+        ParamType::U256 => Token::U256(U256::zero()),
+        ParamType::B256 => Token::B256([0; 32]),
+        ParamType::Bytes => Token::Bytes(vec![]),
+        ParamType::String => Token::String(String::new()),
+        ParamType::RawSlice => Token::RawSlice(vec![]),
+        ParamType::StringSlice => Token::StringSlice(StaticStringToken::new(String::new(), None)),
+        ParamType::StringArray(len) => {
+            Token::StringArray(StaticStringToken::new("\0".repeat(*len), Some(*len)))
+        }
+        ParamType::Vector(_) => Token::Vector(vec![]),
+        ParamType::Array(element_type, len) => {
+            let element = default_of(element_type)?;
+            Token::Array(vec![element; *len])
+        }
+        ParamType::Tuple(param_types) => {
+            Token::Tuple(param_types.iter().map(default_of).collect::<Result<_>>()?)
+        }
+        ParamType::Struct { fields, .. } => Token::Struct(
+            fields
+                .iter()
+                .map(|(_, param_type)| default_of(param_type))
+                .collect::<Result<_>>()?,
+        ),
+        ParamType::Enum { enum_variants, .. } => {
+            let (_, variant_type) = enum_variants.select_variant(0)?;
+            let token = default_of(variant_type)?;
+
+            Token::Enum(Box::new((0, token, enum_variants.clone())))
+        }
+    };
+
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_named, types::param_types::EnumVariants};
+
+    #[test]
+    fn default_tokens_match_their_types() -> Result<()> {
+        let enum_variants = EnumVariants::new(to_named(&[ParamType::U8, ParamType::Bool]))?;
+        let param_type = ParamType::Struct {
+            name: "Scaffold".to_string(),
+            fields: to_named(&[
+                ParamType::U64,
+                ParamType::B256,
+                ParamType::StringArray(3),
+                ParamType::Vector(Box::new(ParamType::U8)),
+                ParamType::Array(Box::new(ParamType::Bool), 2),
+                ParamType::Enum {
+                    name: "Choice".to_string(),
+                    enum_variants: enum_variants.clone(),
+                    generics: vec![],
+                },
+            ]),
+            generics: vec![],
+        };
+
+        let token = Token::default_for(&param_type)?;
+
+        let expected = Token::Struct(vec![
+            Token::U64(0),
+            Token::B256([0; 32]),
+            Token::StringArray(StaticStringToken::new("\0\0\0".to_string(), Some(3))),
+            Token::Vector(vec![]),
+            Token::Array(vec![Token::Bool(false); 2]),
+            Token::Enum(Box::new((0, Token::U8(0), enum_variants))),
+        ]);
+        assert_eq!(token, expected);
+        param_type.validate_token(&token, EncoderConfig::default().max_depth)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_for_too_deep_type_fails() {
+        let max_depth = EncoderConfig::default().max_depth;
+        let param_type =
+            (0..=max_depth).fold(ParamType::U8, |inner, _| ParamType::Tuple(vec![inner]));
+
+        let err = Token::default_for(&param_type).expect_err("should exceed the depth limit");
+
+        assert!(err.to_string().contains("depth limit"));
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use super::flatten::{flatten, ScalarValue};
+use crate::types::{param_types::ParamType, Token};
+
+/// A leaf that differs between two tokens. See [`Token::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenChange {
+    /// The path of the leaf, built the same way as by [`Token::flatten`].
+    pub path: String,
+    /// `None` if the leaf only exists in the new token, e.g. an element pushed to a vector.
+    pub old: Option<ScalarValue>,
+    /// `None` if the leaf only exists in the old token, e.g. the fields of an enum variant
+    /// that is no longer selected.
+    pub new: Option<ScalarValue>,
+}
+
+impl Token {
+    /// Lists the leaves that differ between `self` and `other`, ordered as they appear in
+    /// `self`, followed by the leaves only found in `other`. The paths use the names found in
+    /// `param_type`, or positional indices if it is `None`.
+    pub fn diff(&self, other: &Token, param_type: Option<&ParamType>) -> Vec<TokenChange> {
+        let old_pairs = flatten(self, param_type);
+        let new_pairs = flatten(other, param_type);
+
+        let mut new_values: HashMap<_, _> = new_pairs
+            .iter()
+            .map(|(path, value)| (path.as_str(), value))
+            .collect();
+
+        let mut changes: Vec<_> = old_pairs
+            .iter()
+            .filter_map(|(path, old)| {
+                let new = new_values.remove(path.as_str());
+
+                (new != Some(old)).then(|| TokenChange {
+                    path: path.clone(),
+                    old: Some(old.clone()),
+                    new: new.cloned(),
+                })
+            })
+            .collect();
+
+        changes.extend(
+            new_pairs
+                .iter()
+                .filter(|(path, _)| new_values.contains_key(path.as_str()))
+                .map(|(path, new)| TokenChange {
+                    path: path.clone(),
+                    old: None,
+                    new: Some(new.clone()),
+                }),
+        );
+
+        changes
+    }
+
+    /// Returns the path to the first place where `self` and `other` differ, or `None` if they
+    /// are equal. Each step is the index of a struct field or of a tuple, array or vector
+    /// element, or the discriminant of an enum whose variant is entered. The path stops at the
+    /// token whose kind, enum variant or length differs; for sequences of different lengths
+    /// sharing a common prefix it points at the first element missing from the shorter one.
+    pub fn first_difference(&self, other: &Token) -> Option<Vec<usize>> {
+        let mut path = vec![];
+
+        first_difference(self, other, &mut path).then_some(path)
+    }
+}
+
+fn first_difference(left: &Token, right: &Token, path: &mut Vec<usize>) -> bool {
+    match (left, right) {
+        (Token::Struct(left), Token::Struct(right))
+        | (Token::Tuple(left), Token::Tuple(right))
+        | (Token::Array(left), Token::Array(right))
+        | (Token::Vector(left), Token::Vector(right)) => {
+            for (index, (left, right)) in left.iter().zip(right).enumerate() {
+                path.push(index);
+                if first_difference(left, right, path) {
+                    return true;
+                }
+                path.pop();
+            }
+
+            if left.len() != right.len() {
+                path.push(left.len().min(right.len()));
+                return true;
+            }
+
+            false
+        }
+        (Token::Enum(left), Token::Enum(right)) => {
+            let (left_discriminant, left, left_variants) = left.as_ref();
+            let (right_discriminant, right, right_variants) = right.as_ref();
+
+            if left_discriminant != right_discriminant || left_variants != right_variants {
+                return true;
+            }
+
+            path.push(*left_discriminant as usize);
+            if first_difference(left, right, path) {
+                return true;
+            }
+            path.pop();
+
+            false
+        }
+        _ => left != right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{errors::Result, param_types::EnumVariants};
+
+    #[test]
+    fn changed_added_and_removed_leaves_are_reported() -> Result<()> {
+        let variants = EnumVariants::new(vec![
+            ("None".to_string(), ParamType::Unit),
+            ("Some".to_string(), ParamType::U64),
+        ])?;
+        let param_type = ParamType::Struct {
+            name: "Position".to_string(),
+            fields: vec![
+                ("size".to_string(), ParamType::U32),
+                (
+                    "fills".to_string(),
+                    ParamType::Vector(Box::new(ParamType::U8)),
+                ),
+                (
+                    "limit".to_string(),
+                    ParamType::Enum {
+                        name: "Option".to_string(),
+                        enum_variants: variants.clone(),
+                        generics: vec![],
+                    },
+                ),
+            ],
+            generics: vec![],
+        };
+        let before = Token::Struct(vec![
+            Token::U32(7),
+            Token::Vector(vec![Token::U8(1)]),
+            Token::Enum(Box::new((1, Token::U64(10), variants.clone()))),
+        ]);
+        let after = Token::Struct(vec![
+            Token::U32(7),
+            Token::Vector(vec![Token::U8(2), Token::U8(3)]),
+            Token::Enum(Box::new((0, Token::Unit, variants))),
+        ]);
+
+        let changes = before.diff(&after, Some(&param_type));
+
+        let expected = vec![
+            TokenChange {
+                path: "fills.0".to_string(),
+                old: Some(ScalarValue::U8(1)),
+                new: Some(ScalarValue::U8(2)),
+            },
+            TokenChange {
+                path: "limit.Some".to_string(),
+                old: Some(ScalarValue::U64(10)),
+                new: None,
+            },
+            TokenChange {
+                path: "fills.1".to_string(),
+                old: None,
+                new: Some(ScalarValue::U8(3)),
+            },
+            TokenChange {
+                path: "limit.None".to_string(),
+                old: None,
+                new: Some(ScalarValue::Unit),
+            },
+        ];
+        assert_eq!(changes, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn equal_tokens_have_no_changes() {
+        let token = Token::Tuple(vec![Token::Bool(true), Token::String("fuel".to_string())]);
+
+        assert!(token.diff(&token.clone(), None).is_empty());
+    }
+
+    #[test]
+    fn first_difference_points_at_the_mismatch() -> Result<()> {
+        let variants = EnumVariants::new(vec![
+            ("None".to_string(), ParamType::Unit),
+            ("Some".to_string(), ParamType::U64),
+        ])?;
+        let token = |limit, fills: Vec<u8>| {
+            Token::Struct(vec![
+                Token::U32(7),
+                Token::Vector(fills.into_iter().map(Token::U8).collect()),
+                Token::Enum(Box::new((1, Token::U64(limit), variants.clone()))),
+            ])
+        };
+
+        assert_eq!(
+            token(10, vec![1]).first_difference(&token(10, vec![1])),
+            None
+        );
+        assert_eq!(
+            token(10, vec![1]).first_difference(&token(11, vec![1])),
+            Some(vec![2, 1])
+        );
+        assert_eq!(
+            token(10, vec![1]).first_difference(&token(10, vec![1, 2])),
+            Some(vec![1, 1])
+        );
+        assert_eq!(Token::U8(1).first_difference(&Token::U16(1)), Some(vec![]));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use crate::types::{param_types::ParamType, Token, U256};
+
+/// A leaf value of a flattened `Token`. See [`Token::flatten`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScalarValue {
+    Unit,
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    U256(U256),
+    B256([u8; 32]),
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+impl Token {
+    /// Flattens the token into `(path, value)` pairs, one per leaf. Paths are made of the
+    /// struct field and enum variant names found in `param_type`, and of the element indices
+    /// of tuples, arrays and vectors, joined with dots, e.g. `owner.Address.0`. Positional
+    /// indices are used wherever `param_type` doesn't describe the token. A scalar token is
+    /// returned under an empty path and empty collections produce no pairs.
+    pub fn flatten(&self, param_type: &ParamType) -> Vec<(String, ScalarValue)> {
+        flatten(self, Some(param_type))
+    }
+}
+
+pub(super) fn flatten(token: &Token, param_type: Option<&ParamType>) -> Vec<(String, ScalarValue)> {
+    let mut pairs = vec![];
+    flatten_into(token, param_type, String::new(), &mut pairs);
+
+    pairs
+}
+
+fn flatten_into(
+    token: &Token,
+    param_type: Option<&ParamType>,
+    path: String,
+    pairs: &mut Vec<(String, ScalarValue)>,
+) {
+    let value = match token {
+        Token::Unit => ScalarValue::Unit,
+        Token::Bool(value) => ScalarValue::Bool(*value),
+        Token::U8(value) => ScalarValue::U8(*value),
+        Token::U16(value) => ScalarValue::U16(*value),
+        Token::U32(value) => ScalarValue::U32(*value),
+        Token::U64(value) => ScalarValue::U64(*value),
+        Token::U128(value) => ScalarValue::U128(*value),
+        Token::U256(value) => ScalarValue::U256(*value),
+        Token::B256(value) => ScalarValue::B256(*value),
+        Token::StringSlice(value) | Token::StringArray(value) => {
+            ScalarValue::String(value.data.clone())
+        }
+        Token::String(value) => ScalarValue::String(value.clone()),
+        Token::RawSlice(value) | Token::Bytes(value) => ScalarValue::Bytes(value.clone()),
+        Token::Struct(tokens) => {
+            let fields = match param_type {
+                Some(ParamType::Struct { fields, .. }) if fields.len() == tokens.len() => {
+                    Some(fields)
+                }
+                _ => None,
+            };
+
+            for (index, token) in tokens.iter().enumerate() {
+                let field = fields.map(|fields| &fields[index]);
+                let name = field.map_or_else(|| index.to_string(), |(name, _)| name.clone());
+
+                flatten_into(
+                    token,
+                    field.map(|(_, param_type)| param_type),
+                    join(&path, &name),
+                    pairs,
+                );
+            }
+
+            return;
+        }
+        Token::Tuple(tokens) => {
+            let elements = match param_type {
+                Some(ParamType::Tuple(elements)) if elements.len() == tokens.len() => {
+                    Some(elements)
+                }
+                _ => None,
+            };
+
+            for (index, token) in tokens.iter().enumerate() {
+                let element = elements.map(|elements| &elements[index]);
+                flatten_into(token, element, join(&path, &index.to_string()), pairs);
+            }
+
+            return;
+        }
+        Token::Array(tokens) | Token::Vector(tokens) => {
+            let element = match param_type {
+                Some(ParamType::Array(element, _) | ParamType::Vector(element)) => {
+                    Some(element.as_ref())
+                }
+                _ => None,
+            };
+
+            for (index, token) in tokens.iter().enumerate() {
+                flatten_into(token, element, join(&path, &index.to_string()), pairs);
+            }
+
+            return;
+        }
+        Token::Enum(selector) => {
+            let (discriminant, token, variants) = selector.as_ref();
+
+            let (name, variant_type) = match variants.select_variant(*discriminant) {
+                Ok((name, variant_type)) => (name.clone(), Some(variant_type)),
+                Err(_) => (discriminant.to_string(), None),
+            };
+
+            flatten_into(token, variant_type, join(&path, &name), pairs);
+
+            return;
+        }
+    };
+
+    pairs.push((path, value));
+}
+
+fn join(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{errors::Result, param_types::EnumVariants};
+
+    #[test]
+    fn nested_token_is_flattened_into_named_paths() -> Result<()> {
+        let variants = EnumVariants::new(vec![
+            ("None".to_string(), ParamType::Unit),
+            ("Some".to_string(), ParamType::U64),
+        ])?;
+        let param_type = ParamType::Struct {
+            name: "Order".to_string(),
+            fields: vec![
+                ("id".to_string(), ParamType::U32),
+                (
+                    "amounts".to_string(),
+                    ParamType::Vector(Box::new(ParamType::U8)),
+                ),
+                (
+                    "limit".to_string(),
+                    ParamType::Enum {
+                        name: "Option".to_string(),
+                        enum_variants: variants.clone(),
+                        generics: vec![],
+                    },
+                ),
+                (
+                    "pair".to_string(),
+                    ParamType::Tuple(vec![ParamType::Bool, ParamType::String]),
+                ),
+            ],
+            generics: vec![],
+        };
+        let token = Token::Struct(vec![
+            Token::U32(7),
+            Token::Vector(vec![Token::U8(1), Token::U8(2)]),
+            Token::Enum(Box::new((1, Token::U64(10), variants))),
+            Token::Tuple(vec![Token::Bool(true), Token::String("fuel".to_string())]),
+        ]);
+
+        let pairs = token.flatten(&param_type);
+
+        let expected = vec![
+            ("id".to_string(), ScalarValue::U32(7)),
+            ("amounts.0".to_string(), ScalarValue::U8(1)),
+            ("amounts.1".to_string(), ScalarValue::U8(2)),
+            ("limit.Some".to_string(), ScalarValue::U64(10)),
+            ("pair.0".to_string(), ScalarValue::Bool(true)),
+            (
+                "pair.1".to_string(),
+                ScalarValue::String("fuel".to_string()),
+            ),
+        ];
+        assert_eq!(pairs, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn indices_are_used_when_param_type_does_not_describe_the_token() {
+        let token = Token::Struct(vec![Token::U8(1), Token::Unit]);
+
+        let pairs = token.flatten(&ParamType::U64);
+
+        let expected = vec![
+            ("0".to_string(), ScalarValue::U8(1)),
+            ("1".to_string(), ScalarValue::Unit),
+        ];
+        assert_eq!(pairs, expected);
+    }
+}
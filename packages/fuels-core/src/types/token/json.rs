@@ -0,0 +1,79 @@
+use serde_json::Value;
+
+use crate::types::{
+    errors::{error, Result},
+    Token,
+};
+
+impl Token {
+    /// Builds a `Token` out of untyped JSON, guessing the type from the shape of the value. Meant
+    /// for debugging only, use the typed bindings when the ABI is known.
+    ///
+    /// The following coercions are applied:
+    /// * `null` becomes `Token::Unit`
+    /// * booleans become `Token::Bool`
+    /// * non-negative integers become `Token::U64`
+    /// * strings become `Token::String`
+    /// * arrays become `Token::Vector`, converting each element with the same rules
+    ///
+    /// Negative or fractional numbers and objects are rejected, since there is no type to
+    /// guide their conversion.
+    pub fn from_json_loose(value: &Value) -> Result<Token> {
+        match value {
+            Value::Null => Ok(Token::Unit),
+            Value::Bool(value) => Ok(Token::Bool(*value)),
+            Value::Number(number) => number.as_u64().map(Token::U64).ok_or_else(|| {
+                error!(
+                    Codec,
+                    "cannot convert `{number}` into a token: only non-negative integers fitting into a `u64` are supported"
+                )
+            }),
+            Value::String(value) => Ok(Token::String(value.clone())),
+            Value::Array(values) => values
+                .iter()
+                .map(Token::from_json_loose)
+                .collect::<Result<Vec<_>>>()
+                .map(Token::Vector),
+            Value::Object(_) => Err(error!(
+                Codec,
+                "cannot convert a JSON object into a token without knowing its type"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn simple_values_are_coerced() -> Result<()> {
+        let value = json!([null, true, 42, "fuel", [1, 2]]);
+
+        let token = Token::from_json_loose(&value)?;
+
+        assert_eq!(
+            token,
+            Token::Vector(vec![
+                Token::Unit,
+                Token::Bool(true),
+                Token::U64(42),
+                Token::String("fuel".to_string()),
+                Token::Vector(vec![Token::U64(1), Token::U64(2)]),
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_values_are_rejected() {
+        for value in [json!(-1), json!(1.5), json!({"a": 1}), json!([{"a": 1}])] {
+            let err = Token::from_json_loose(&value).expect_err("should fail");
+
+            assert!(matches!(err, crate::types::errors::Error::Codec(_)));
+        }
+    }
+}
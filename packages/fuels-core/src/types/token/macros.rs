@@ -0,0 +1,127 @@
+/// Builds a `Token::Struct` from values implementing `Tokenizable`.
+///
+/// ```
+/// use fuels_core::{token_struct, types::Token};
+///
+/// let token = token_struct![1u8, true];
+///
+/// assert_eq!(token, Token::Struct(vec![Token::U8(1), Token::Bool(true)]));
+/// ```
+#[macro_export]
+macro_rules! token_struct {
+    ($($value: expr),* $(,)?) => {
+        $crate::types::Token::Struct(vec![$($crate::traits::Tokenizable::into_token($value)),*])
+    };
+}
+
+/// Builds a `Token::Tuple` from values implementing `Tokenizable`.
+#[macro_export]
+macro_rules! token_tuple {
+    ($($value: expr),* $(,)?) => {
+        $crate::types::Token::Tuple(vec![$($crate::traits::Tokenizable::into_token($value)),*])
+    };
+}
+
+/// Builds a `Token::Array` from values implementing `Tokenizable`.
+#[macro_export]
+macro_rules! token_array {
+    ($($value: expr),* $(,)?) => {
+        $crate::types::Token::Array(vec![$($crate::traits::Tokenizable::into_token($value)),*])
+    };
+}
+
+/// Builds a `Token::Vector` from values implementing `Tokenizable`.
+#[macro_export]
+macro_rules! token_vec {
+    ($($value: expr),* $(,)?) => {
+        $crate::types::Token::Vector(vec![$($crate::traits::Tokenizable::into_token($value)),*])
+    };
+}
+
+/// Builds a `Token::Enum` holding `value` in the variant selected by `discriminant`.
+///
+/// ```
+/// use fuels_core::{
+///     token_enum,
+///     types::{param_types::{EnumVariants, ParamType}, Token},
+/// };
+///
+/// let variants = EnumVariants::new(vec![
+///     ("A".to_string(), ParamType::U64),
+///     ("B".to_string(), ParamType::Bool),
+/// ])?;
+///
+/// let token = token_enum!(variants.clone(), 1 => true);
+///
+/// assert_eq!(token, Token::Enum(Box::new((1, Token::Bool(true), variants))));
+/// # Ok::<(), fuels_core::types::errors::Error>(())
+/// ```
+#[macro_export]
+macro_rules! token_enum {
+    ($variants: expr, $discriminant: expr => $value: expr $(,)?) => {
+        $crate::types::Token::Enum(Box::new((
+            $discriminant,
+            $crate::traits::Tokenizable::into_token($value),
+            $variants,
+        )))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codec::ABIEncoder,
+        to_named,
+        types::{
+            errors::Result,
+            param_types::{EnumVariants, ParamType},
+            Token,
+        },
+    };
+
+    #[test]
+    fn macros_expand_to_the_right_variants() {
+        assert_eq!(token_struct![], Token::Struct(vec![]));
+        assert_eq!(
+            token_tuple![1u64, Token::Unit],
+            Token::Tuple(vec![Token::U64(1), Token::Unit])
+        );
+        assert_eq!(
+            token_array![1u8, 2u8,],
+            Token::Array(vec![Token::U8(1), Token::U8(2)])
+        );
+        assert_eq!(
+            token_vec![true, false],
+            Token::Vector(vec![Token::Bool(true), Token::Bool(false)])
+        );
+    }
+
+    #[test]
+    fn macros_can_be_nested() -> Result<()> {
+        let variants = EnumVariants::new(to_named(&[ParamType::U8, ParamType::Bool]))?;
+
+        let token = token_struct![
+            10u16,
+            token_struct![true, token_array![1u8, 2u8]],
+            token_enum!(variants.clone(), 0 => 7u8),
+        ];
+
+        let expected = Token::Struct(vec![
+            Token::U16(10),
+            Token::Struct(vec![
+                Token::Bool(true),
+                Token::Array(vec![Token::U8(1), Token::U8(2)]),
+            ]),
+            Token::Enum(Box::new((0, Token::U8(7), variants))),
+        ]);
+        assert_eq!(token, expected);
+
+        let encoder = ABIEncoder::default();
+        assert_eq!(
+            encoder.encode(&[token])?.resolve(0),
+            encoder.encode(&[expected])?.resolve(0)
+        );
+
+        Ok(())
+    }
+}
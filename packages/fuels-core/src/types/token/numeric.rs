@@ -0,0 +1,103 @@
+use crate::types::{
+    errors::{error, Result},
+    Token, U256,
+};
+
+macro_rules! impl_try_as_uint {
+    ($($fn_name: ident => $ty: ty),*) => {
+        $(
+            #[doc = concat!(
+                "Reads the value of a numeric `Token` and range-checks it into a `",
+                stringify!($ty),
+                "`."
+            )]
+            pub fn $fn_name(&self) -> Result<$ty> {
+                let value = self.numeric_value()?;
+
+                <$ty>::try_from(value).map_err(|_| {
+                    error!(
+                        Other,
+                        "value `{value}` is out of range for `{}`",
+                        stringify!($ty)
+                    )
+                })
+            }
+        )*
+    };
+}
+
+impl Token {
+    impl_try_as_uint!(
+        try_as_u8 => u8,
+        try_as_u16 => u16,
+        try_as_u32 => u32,
+        try_as_u64 => u64,
+        try_as_u128 => u128
+    );
+
+    /// Compares numeric tokens by value regardless of their width, e.g. `Token::U8(5)` equals
+    /// `Token::U64(5)`. Returns `false` if either token isn't numeric.
+    pub fn numeric_eq(&self, other: &Token) -> bool {
+        matches!(
+            (self.numeric_value(), other.numeric_value()),
+            (Ok(lhs), Ok(rhs)) if lhs == rhs
+        )
+    }
+
+    fn numeric_value(&self) -> Result<U256> {
+        let value = match self {
+            Token::U8(value) => U256::from(*value),
+            Token::U16(value) => U256::from(*value),
+            Token::U32(value) => U256::from(*value),
+            Token::U64(value) => U256::from(*value),
+            Token::U128(value) => U256::from(*value),
+            Token::U256(value) => *value,
+            _ => return Err(error!(Other, "expected a numeric token, got `{self}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_tokens_are_compared_by_value_across_widths() {
+        assert!(Token::U8(5).numeric_eq(&Token::U64(5)));
+        assert!(Token::U256(U256::from(5)).numeric_eq(&Token::U16(5)));
+        assert!(!Token::U8(5).numeric_eq(&Token::U64(6)));
+        assert!(!Token::Bool(true).numeric_eq(&Token::U8(1)));
+        assert!(!Token::Unit.numeric_eq(&Token::Unit));
+    }
+
+    #[test]
+    fn numeric_tokens_are_narrowed_when_in_range() -> Result<()> {
+        assert_eq!(Token::U64(255).try_as_u8()?, 255);
+        assert_eq!(Token::U8(7).try_as_u64()?, 7);
+        assert_eq!(Token::U128(u16::MAX as u128).try_as_u16()?, u16::MAX);
+        assert_eq!(Token::U256(U256::from(u32::MAX)).try_as_u32()?, u32::MAX);
+        assert_eq!(Token::U256(U256::from(u128::MAX)).try_as_u128()?, u128::MAX);
+
+        Ok(())
+    }
+
+    #[test]
+    fn out_of_range_value_is_reported() {
+        let err = Token::U64(256)
+            .try_as_u8()
+            .expect_err("should fail because 256 doesn't fit in `u8`");
+
+        assert_eq!(err.to_string(), "value `256` is out of range for `u8`");
+    }
+
+    #[test]
+    fn non_numeric_token_is_rejected() {
+        let err = Token::Bool(true)
+            .try_as_u64()
+            .expect_err("should fail because `Bool` is not numeric");
+
+        assert!(err.to_string().contains("expected a numeric token"));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::types::{
+    errors::{error, Result},
+    param_types::ParamType,
+    Token,
+};
+
+impl Token {
+    /// Returns the token found at `path`, written like the paths of [`Token::flatten`], e.g.
+    /// `owner.Address.0`. Struct fields are selected by name or position, tuple, array and
+    /// vector elements by index, and enums by the name or discriminant of the selected variant.
+    /// An empty path returns the token itself.
+    pub fn get_by_path(&self, path: &str, param_type: &ParamType) -> Result<&Token> {
+        if path.is_empty() {
+            return Ok(self);
+        }
+
+        let mut token = self;
+        let mut param_type = param_type;
+        for (depth, segment) in path.split('.').enumerate() {
+            let (child, child_type) = child(token, param_type, segment).map_err(|msg| {
+                let parent = path.split('.').take(depth).collect::<Vec<_>>().join(".");
+                error!(Codec, "invalid path `{path}` at `{parent}`: {msg}")
+            })?;
+
+            token = child;
+            param_type = child_type;
+        }
+
+        Ok(token)
+    }
+
+    /// Returns the field called `name` of a `Token::Struct`, resolving its position from the
+    /// field names of `param_type`. Unlike [`Token::get_by_path`], fields can't be selected by
+    /// position, so a field reordered in the ABI is still found.
+    pub fn get_named_field(&self, name: &str, param_type: &ParamType) -> Result<&Token> {
+        let (Token::Struct(tokens), ParamType::Struct { fields, .. }) = (self, param_type) else {
+            return Err(error!(
+                Codec,
+                "cannot get field `{name}` of `{self}`, expected a struct and its type"
+            ));
+        };
+
+        let index = fields
+            .iter()
+            .position(|(field_name, _)| field_name == name)
+            .ok_or_else(|| error!(Codec, "no field named `{name}`"))?;
+
+        element(tokens, index).map_err(|msg| error!(Codec, "cannot get field `{name}`: {msg}"))
+    }
+}
+
+fn child<'a>(
+    token: &'a Token,
+    param_type: &'a ParamType,
+    segment: &str,
+) -> std::result::Result<(&'a Token, &'a ParamType), String> {
+    match (token, param_type) {
+        (Token::Struct(tokens), ParamType::Struct { fields, .. }) => {
+            let index = fields
+                .iter()
+                .position(|(name, _)| name == segment)
+                .map_or_else(|| parse_index(segment), Ok)?;
+
+            let token = element(tokens, index)?;
+            let (_, field_type) = fields
+                .get(index)
+                .ok_or_else(|| format!("the type has no field `{index}`"))?;
+
+            Ok((token, field_type))
+        }
+        (Token::Tuple(tokens), ParamType::Tuple(element_types)) => {
+            let index = parse_index(segment)?;
+            let token = element(tokens, index)?;
+            let element_type = element_types
+                .get(index)
+                .ok_or_else(|| format!("the type has no element `{index}`"))?;
+
+            Ok((token, element_type))
+        }
+        (Token::Array(tokens), ParamType::Array(element_type, _))
+        | (Token::Vector(tokens), ParamType::Vector(element_type)) => {
+            Ok((element(tokens, parse_index(segment)?)?, element_type))
+        }
+        (Token::Enum(selector), ParamType::Enum { enum_variants, .. }) => {
+            let (discriminant, token, _) = selector.as_ref();
+            let (name, variant_type) = enum_variants
+                .select_variant(*discriminant)
+                .map_err(|err| err.to_string())?;
+
+            if segment != name && segment != discriminant.to_string() {
+                return Err(format!(
+                    "variant `{segment}` is not selected, the enum holds `{name}`"
+                ));
+            }
+
+            Ok((token, variant_type))
+        }
+        _ => Err(format!("cannot select `{segment}` in `{token}`")),
+    }
+}
+
+fn parse_index(segment: &str) -> std::result::Result<usize, String> {
+    segment
+        .parse()
+        .map_err(|_| format!("no field named `{segment}`"))
+}
+
+fn element(tokens: &[Token], index: usize) -> std::result::Result<&Token, String> {
+    tokens.get(index).ok_or_else(|| {
+        format!(
+            "index `{index}` is out of bounds for {} elements",
+            tokens.len()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::param_types::EnumVariants;
+
+    fn order() -> Result<(Token, ParamType)> {
+        let variants = EnumVariants::new(vec![
+            ("None".to_string(), ParamType::Unit),
+            ("Some".to_string(), ParamType::U64),
+        ])?;
+        let param_type = ParamType::Struct {
+            name: "Order".to_string(),
+            fields: vec![
+                (
+                    "amounts".to_string(),
+                    ParamType::Vector(Box::new(ParamType::U8)),
+                ),
+                (
+                    "limit".to_string(),
+                    ParamType::Enum {
+                        name: "Option".to_string(),
+                        enum_variants: variants.clone(),
+                        generics: vec![],
+                    },
+                ),
+            ],
+            generics: vec![],
+        };
+        let token = Token::Struct(vec![
+            Token::Vector(vec![Token::U8(1), Token::U8(2)]),
+            Token::Enum(Box::new((1, Token::U64(10), variants))),
+        ]);
+
+        Ok((token, param_type))
+    }
+
+    #[test]
+    fn tokens_are_found_by_names_and_indices() -> Result<()> {
+        let (token, param_type) = order()?;
+
+        assert_eq!(token.get_by_path("amounts.1", &param_type)?, &Token::U8(2));
+        assert_eq!(token.get_by_path("0.0", &param_type)?, &Token::U8(1));
+        assert_eq!(
+            token.get_by_path("limit.Some", &param_type)?,
+            &Token::U64(10)
+        );
+        assert_eq!(token.get_by_path("limit.1", &param_type)?, &Token::U64(10));
+        assert_eq!(token.get_by_path("", &param_type)?, &token);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bad_paths_are_reported() -> Result<()> {
+        let (token, param_type) = order()?;
+
+        let err = token
+            .get_by_path("amounts.2", &param_type)
+            .expect_err("should fail because the vector has 2 elements");
+        assert_eq!(
+            err.to_string(),
+            "codec: invalid path `amounts.2` at `amounts`: index `2` is out of bounds for 2 elements"
+        );
+
+        let err = token
+            .get_by_path("price", &param_type)
+            .expect_err("should fail because there is no such field");
+        assert!(err.to_string().contains("no field named `price`"));
+
+        let err = token
+            .get_by_path("limit.None", &param_type)
+            .expect_err("should fail because another variant is selected");
+        assert!(err.to_string().contains("the enum holds `Some`"));
+
+        token
+            .get_by_path("amounts.0.0", &param_type)
+            .expect_err("should fail because `u8` has no children");
+
+        Ok(())
+    }
+
+    #[test]
+    fn struct_fields_are_found_by_name_only() -> Result<()> {
+        let (token, param_type) = order()?;
+
+        assert_eq!(
+            token.get_named_field("amounts", &param_type)?,
+            &Token::Vector(vec![Token::U8(1), Token::U8(2)])
+        );
+
+        let err = token
+            .get_named_field("0", &param_type)
+            .expect_err("should fail because fields aren't selected by position");
+        assert_eq!(err.to_string(), "codec: no field named `0`");
+
+        let amounts = token.get_named_field("amounts", &param_type)?;
+        amounts
+            .get_named_field("amounts", &param_type)
+            .expect_err("should fail because the token isn't a struct");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::types::Token;
+
+impl Token {
+    /// Creates a `Token::RawSlice` holding the bytes of `iter`. Note that a raw slice is encoded
+    /// differently than `Token::Bytes`, so pick the token matching the ABI type.
+    pub fn raw_slice_from(iter: impl IntoIterator<Item = u8>) -> Token {
+        Token::RawSlice(iter.into_iter().collect())
+    }
+
+    /// Creates a `Token::RawSlice` holding a copy of `bytes`, see
+    /// [`raw_slice_from`](Self::raw_slice_from).
+    pub fn raw_slice_from_slice(bytes: &[u8]) -> Token {
+        Token::RawSlice(bytes.to_vec())
+    }
+
+    /// Returns the bytes of a `Token::RawSlice`, or `None` for any other token, `Token::Bytes`
+    /// included.
+    pub fn into_raw_slice(self) -> Option<Vec<u8>> {
+        match self {
+            Token::RawSlice(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_slices_are_created_from_iterators_and_slices() {
+        let expected = Token::RawSlice(vec![1, 2, 3]);
+
+        assert_eq!(Token::raw_slice_from(1..=3), expected);
+        assert_eq!(Token::raw_slice_from_slice(&[1, 2, 3]), expected);
+        assert_eq!(expected.into_raw_slice(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn bytes_are_not_a_raw_slice() {
+        assert_eq!(Token::Bytes(vec![1, 2, 3]).into_raw_slice(), None);
+    }
+}
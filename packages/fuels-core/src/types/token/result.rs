@@ -0,0 +1,90 @@
+use crate::{
+    codec::EncoderConfig,
+    types::{
+        errors::{error, Result},
+        param_types::{EnumVariants, ParamType},
+        Token,
+    },
+};
+
+impl Token {
+    /// Creates the `Token::Enum` of a Sway `Result<T, E>` holding `value`, where `ok_type` and
+    /// `err_type` are the types of `T` and `E`. Fails if the token in `value` doesn't match
+    /// its type.
+    pub fn result(
+        value: std::result::Result<Token, Token>,
+        ok_type: ParamType,
+        err_type: ParamType,
+    ) -> Result<Token> {
+        let (discriminant, token, variant_type) = match value {
+            Ok(token) => (0, token, &ok_type),
+            Err(token) => (1, token, &err_type),
+        };
+
+        variant_type
+            .validate_token(&token, EncoderConfig::default().max_depth)
+            .map_err(|e| error!(Codec, "invalid `Result` variant: {e}"))?;
+
+        let variants = EnumVariants::new(vec![
+            ("Ok".to_string(), ok_type),
+            ("Err".to_string(), err_type),
+        ])?;
+
+        Ok(Token::Enum(Box::new((discriminant, token, variants))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{ABIDecoder, ABIEncoder};
+
+    fn result_type(ok_type: ParamType, err_type: ParamType) -> Result<ParamType> {
+        Ok(ParamType::Enum {
+            name: "Result".to_string(),
+            enum_variants: EnumVariants::new(vec![
+                ("Ok".to_string(), ok_type.clone()),
+                ("Err".to_string(), err_type.clone()),
+            ])?,
+            generics: vec![ok_type, err_type],
+        })
+    }
+
+    fn round_trip(token: &Token, param_type: &ParamType) -> Result<Token> {
+        let encoded = ABIEncoder::default().encode(&[token.clone()])?.resolve(0);
+
+        ABIDecoder::default().decode(param_type, &encoded)
+    }
+
+    #[test]
+    fn ok_round_trips() -> Result<()> {
+        let token = Token::result(Ok(Token::U64(42)), ParamType::U64, ParamType::Bool)?;
+
+        let decoded = round_trip(&token, &result_type(ParamType::U64, ParamType::Bool)?)?;
+
+        assert_eq!(decoded, token);
+        assert!(matches!(&decoded, Token::Enum(selector) if selector.0 == 0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn err_round_trips() -> Result<()> {
+        let token = Token::result(Err(Token::Bool(true)), ParamType::U64, ParamType::Bool)?;
+
+        let decoded = round_trip(&token, &result_type(ParamType::U64, ParamType::Bool)?)?;
+
+        assert_eq!(decoded, token);
+        assert!(matches!(&decoded, Token::Enum(selector) if selector.0 == 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn mismatched_variant_is_rejected() {
+        let err = Token::result(Err(Token::U64(1)), ParamType::U64, ParamType::Bool)
+            .expect_err("should fail");
+
+        assert!(err.to_string().contains("invalid `Result` variant"));
+    }
+}
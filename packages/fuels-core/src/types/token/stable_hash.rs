@@ -0,0 +1,175 @@
+use sha2::{Digest, Sha256};
+
+use crate::types::Token;
+
+// The tags identifying each kind of token in the hashed data. Changing them changes every hash,
+// breaking the keys already derived from them.
+const UNIT: u8 = 0;
+const U8: u8 = 1;
+const U16: u8 = 2;
+const U32: u8 = 3;
+const U64: u8 = 4;
+const U128: u8 = 5;
+const U256: u8 = 6;
+const BOOL: u8 = 7;
+const B256: u8 = 8;
+const ARRAY: u8 = 9;
+const VECTOR: u8 = 10;
+const STRING_SLICE: u8 = 11;
+const STRING_ARRAY: u8 = 12;
+const STRUCT: u8 = 13;
+const ENUM: u8 = 14;
+const TUPLE: u8 = 15;
+const RAW_SLICE: u8 = 16;
+const BYTES: u8 = 17;
+const STRING: u8 = 18;
+
+impl Token {
+    /// A SHA-256 hash of the value held by the token, suitable for deduplication and
+    /// idempotency keys. Unlike the `Debug` output or the encoding, it is stable across
+    /// releases and encoding versions. Enums are hashed by their discriminant and value only,
+    /// so the variant names and types carried by the token don't affect the hash.
+    pub fn stable_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        self.feed(&mut hasher);
+
+        hasher.finalize().into()
+    }
+
+    /// Like [`stable_hash`](Self::stable_hash), for a sequence of tokens such as the arguments
+    /// of a call.
+    pub fn stable_hash_slice(tokens: &[Token]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        feed_tokens(tokens, &mut hasher);
+
+        hasher.finalize().into()
+    }
+
+    fn feed(&self, hasher: &mut Sha256) {
+        match self {
+            Token::Unit => hasher.update([UNIT]),
+            Token::U8(value) => feed_tagged(U8, &[*value], hasher),
+            Token::U16(value) => feed_tagged(U16, &value.to_be_bytes(), hasher),
+            Token::U32(value) => feed_tagged(U32, &value.to_be_bytes(), hasher),
+            Token::U64(value) => feed_tagged(U64, &value.to_be_bytes(), hasher),
+            Token::U128(value) => feed_tagged(U128, &value.to_be_bytes(), hasher),
+            Token::U256(value) => {
+                let mut bytes = [0u8; 32];
+                value.to_big_endian(&mut bytes);
+                feed_tagged(U256, &bytes, hasher)
+            }
+            Token::Bool(value) => feed_tagged(BOOL, &[u8::from(*value)], hasher),
+            Token::B256(value) => feed_tagged(B256, value, hasher),
+            Token::Array(tokens) => {
+                hasher.update([ARRAY]);
+                feed_tokens(tokens, hasher)
+            }
+            Token::Vector(tokens) => {
+                hasher.update([VECTOR]);
+                feed_tokens(tokens, hasher)
+            }
+            Token::StringSlice(value) => {
+                hasher.update([STRING_SLICE]);
+                feed_bytes(value.data.as_bytes(), hasher)
+            }
+            Token::StringArray(value) => {
+                hasher.update([STRING_ARRAY]);
+                feed_bytes(value.data.as_bytes(), hasher)
+            }
+            Token::Struct(tokens) => {
+                hasher.update([STRUCT]);
+                feed_tokens(tokens, hasher)
+            }
+            Token::Enum(selector) => {
+                let (discriminant, token, _) = selector.as_ref();
+                feed_tagged(ENUM, &discriminant.to_be_bytes(), hasher);
+                token.feed(hasher)
+            }
+            Token::Tuple(tokens) => {
+                hasher.update([TUPLE]);
+                feed_tokens(tokens, hasher)
+            }
+            Token::RawSlice(bytes) => {
+                hasher.update([RAW_SLICE]);
+                feed_bytes(bytes, hasher)
+            }
+            Token::Bytes(bytes) => {
+                hasher.update([BYTES]);
+                feed_bytes(bytes, hasher)
+            }
+            Token::String(value) => {
+                hasher.update([STRING]);
+                feed_bytes(value.as_bytes(), hasher)
+            }
+        }
+    }
+}
+
+fn feed_tagged(tag: u8, bytes: &[u8], hasher: &mut Sha256) {
+    hasher.update([tag]);
+    hasher.update(bytes);
+}
+
+// Lengths are hashed so that adjacent values can't be regrouped into the same data
+fn feed_bytes(bytes: &[u8], hasher: &mut Sha256) {
+    hasher.update((bytes.len() as u64).to_be_bytes());
+    hasher.update(bytes);
+}
+
+fn feed_tokens(tokens: &[Token], hasher: &mut Sha256) {
+    hasher.update((tokens.len() as u64).to_be_bytes());
+    tokens.iter().for_each(|token| token.feed(hasher));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::param_types::ParamType;
+
+    fn option_of(discriminant: u64, token: Token, some_type: ParamType) -> Token {
+        let ParamType::Enum { enum_variants, .. } = ParamType::option_of(some_type) else {
+            unreachable!()
+        };
+
+        Token::Enum(Box::new((discriminant, token, enum_variants)))
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        let token = Token::Struct(vec![
+            Token::U64(42),
+            Token::String("fuel".to_string()),
+            Token::Vector(vec![Token::Bool(true), Token::Unit]),
+        ]);
+
+        // Fixed on purpose, a change here breaks the keys users already derived
+        assert_eq!(
+            hex::encode(token.stable_hash()),
+            "f82076d600e427e7ccbfd518ac0dd00454e2f244bbfdd4b17a6f0cf2899b17f1"
+        );
+    }
+
+    #[test]
+    fn enum_metadata_is_ignored() {
+        let a = option_of(1, Token::U64(1), ParamType::U64);
+        let b = option_of(1, Token::U64(1), ParamType::U32);
+
+        assert_eq!(a.stable_hash(), b.stable_hash());
+        assert_ne!(
+            a.stable_hash(),
+            option_of(0, Token::U64(1), ParamType::U64).stable_hash()
+        );
+    }
+
+    #[test]
+    fn values_are_not_regrouped() {
+        let split = [Token::Bytes(vec![1]), Token::Bytes(vec![2, 3])];
+        let regrouped = [Token::Bytes(vec![1, 2]), Token::Bytes(vec![3])];
+
+        assert_ne!(
+            Token::stable_hash_slice(&split),
+            Token::stable_hash_slice(&regrouped)
+        );
+        assert_ne!(Token::U32(1).stable_hash(), Token::U64(1).stable_hash());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::types::{
+    errors::{error, Result},
+    param_types::ParamType,
+    Token,
+};
+
+impl Token {
+    /// Creates the token of the `Option<String>` read from a Sway `StorageString`, `None`
+    /// standing for an empty slot. Matches [`ParamType::storage_string`].
+    pub fn storage_string(value: Option<String>) -> Token {
+        Self::storage_option(value.map(Token::String), ParamType::storage_string())
+    }
+
+    /// Creates the token of the `Option<Bytes>` read from a Sway `StorageBytes`, `None`
+    /// standing for an empty slot. Matches [`ParamType::storage_bytes`].
+    pub fn storage_bytes(value: Option<Vec<u8>>) -> Token {
+        Self::storage_option(value.map(Token::Bytes), ParamType::storage_bytes())
+    }
+
+    /// Returns the content of a token decoded as [`ParamType::storage_string`]. Fails if the
+    /// token has another shape, e.g. a plain `Token::String` decoded with `ParamType::String`.
+    pub fn into_storage_string(self) -> Result<Option<String>> {
+        match self.into_storage_option("StorageString", "String")? {
+            None => Ok(None),
+            Some(Token::String(value)) => Ok(Some(value)),
+            Some(other) => Err(error!(
+                Codec,
+                "expected the `Option<String>` read from a `StorageString` to hold a `Token::String`, got `{other:?}`"
+            )),
+        }
+    }
+
+    /// Returns the content of a token decoded as [`ParamType::storage_bytes`]. Fails if the
+    /// token has another shape, e.g. a plain `Token::Bytes` decoded with `ParamType::Bytes`.
+    pub fn into_storage_bytes(self) -> Result<Option<Vec<u8>>> {
+        match self.into_storage_option("StorageBytes", "Bytes")? {
+            None => Ok(None),
+            Some(Token::Bytes(value)) => Ok(Some(value)),
+            Some(other) => Err(error!(
+                Codec,
+                "expected the `Option<Bytes>` read from a `StorageBytes` to hold a `Token::Bytes`, got `{other:?}`"
+            )),
+        }
+    }
+
+    fn storage_option(value: Option<Token>, param_type: ParamType) -> Token {
+        let ParamType::Enum { enum_variants, .. } = param_type else {
+            unreachable!("storage types are read as an `Option`")
+        };
+
+        let (discriminant, token) = match value {
+            None => (0, Token::Unit),
+            Some(token) => (1, token),
+        };
+
+        Token::Enum(Box::new((discriminant, token, enum_variants)))
+    }
+
+    fn into_storage_option(self, storage_type: &str, inner_type: &str) -> Result<Option<Token>> {
+        let Token::Enum(selector) = self else {
+            return Err(error!(
+                Codec,
+                "expected the `Option<{inner_type}>` read from a `{storage_type}`, got `{self:?}`. Decode it with `ParamType::storage_{}()`",
+                inner_type.to_lowercase()
+            ));
+        };
+
+        match *selector {
+            (0, Token::Unit, _) => Ok(None),
+            (1, token, _) => Ok(Some(token)),
+            (discriminant, token, _) => Err(error!(
+                Codec,
+                "expected the `Option<{inner_type}>` read from a `{storage_type}`, got variant `{discriminant}` holding `{token:?}`"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{ABIDecoder, ABIEncoder};
+
+    fn round_trip(token: &Token, param_type: &ParamType) -> Result<Token> {
+        let encoded = ABIEncoder::default().encode(&[token.clone()])?.resolve(0);
+
+        ABIDecoder::default().decode(param_type, &encoded)
+    }
+
+    // Whole words, so that the padding the encoder adds to heap data isn't decoded as content
+    const CONTENT: &str = "fuels-rs";
+
+    #[test]
+    fn storage_strings_round_trip() -> Result<()> {
+        for value in [Some(CONTENT.to_string()), None] {
+            let token = Token::storage_string(value.clone());
+
+            let decoded = round_trip(&token, &ParamType::storage_string())?;
+
+            assert_eq!(decoded, token);
+            assert_eq!(decoded.into_storage_string()?, value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn storage_bytes_round_trip() -> Result<()> {
+        for value in [Some(CONTENT.as_bytes().to_vec()), None] {
+            let token = Token::storage_bytes(value.clone());
+
+            let decoded = round_trip(&token, &ParamType::storage_bytes())?;
+
+            assert_eq!(decoded, token);
+            assert_eq!(decoded.into_storage_bytes()?, value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn plain_strings_are_rejected() {
+        let err = Token::String(CONTENT.to_string())
+            .into_storage_string()
+            .expect_err("should fail");
+
+        assert!(err
+            .to_string()
+            .contains("Decode it with `ParamType::storage_string()`"));
+    }
+
+    #[test]
+    fn storage_strings_are_not_storage_bytes() {
+        let err = Token::storage_string(Some(CONTENT.to_string()))
+            .into_storage_bytes()
+            .expect_err("should fail");
+
+        assert!(err.to_string().contains("to hold a `Token::Bytes`"));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::types::Token;
+
+impl Token {
+    /// Returns the content of a `String`, `StringSlice` or `StringArray` token, so that strings
+    /// can be handled regardless of the representation chosen by the ABI. Returns `None` for
+    /// any other token.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Token::String(value) => Some(value),
+            Token::StringSlice(value) | Token::StringArray(value) => Some(&value.data),
+            _ => None,
+        }
+    }
+
+    /// Compares the content of a string token with `s`, see [`as_str`](Self::as_str). Returns
+    /// `false` if the token isn't a string.
+    pub fn string_eq(&self, s: &str) -> bool {
+        self.as_str() == Some(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StaticStringToken;
+
+    #[test]
+    fn strings_are_compared_regardless_of_representation() {
+        let tokens = [
+            Token::String("fuel".to_string()),
+            Token::StringSlice(StaticStringToken::new("fuel".to_string(), None)),
+            Token::StringArray(StaticStringToken::new("fuel".to_string(), Some(4))),
+        ];
+
+        for token in tokens {
+            assert_eq!(token.as_str(), Some("fuel"));
+            assert!(token.string_eq("fuel"));
+            assert!(!token.string_eq("fuels"));
+        }
+    }
+
+    #[test]
+    fn non_string_tokens_have_no_content() {
+        assert_eq!(Token::Bytes(b"fuel".to_vec()).as_str(), None);
+        assert!(!Token::Unit.string_eq(""));
+    }
+}
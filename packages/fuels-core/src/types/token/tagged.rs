@@ -0,0 +1,379 @@
+use crate::{
+    codec::EncoderConfig,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        StaticStringToken, Token, U256,
+    },
+};
+
+/// The version written in front of every tagged value. Bumped on any incompatible change.
+pub const TAGGED_FORMAT_VERSION: u8 = 1;
+
+const UNIT: u8 = 0x00;
+const BOOL: u8 = 0x01;
+const U8: u8 = 0x02;
+const U16: u8 = 0x03;
+const U32: u8 = 0x04;
+const U64: u8 = 0x05;
+const U128: u8 = 0x06;
+const U256_TAG: u8 = 0x07;
+const B256: u8 = 0x08;
+const BYTES: u8 = 0x09;
+const RAW_SLICE: u8 = 0x0a;
+const STRING: u8 = 0x0b;
+const STRING_SLICE: u8 = 0x0c;
+const STRING_ARRAY: u8 = 0x0d;
+const TUPLE: u8 = 0x0e;
+const ARRAY: u8 = 0x0f;
+const VECTOR: u8 = 0x10;
+const STRUCT: u8 = 0x11;
+const ENUM: u8 = 0x12;
+
+impl Token {
+    /// Serializes the token into a minimal, versioned binary format meant for exchanging tokens
+    /// with clients written in other languages. Unlike the ABI encoding, every value carries a
+    /// tag naming its `Token` variant, so it can be produced without knowing the memory layout
+    /// of the VM.
+    ///
+    /// The bytes start with the format version, currently [`TAGGED_FORMAT_VERSION`], followed
+    /// by a single value. A value is a one byte tag followed by its payload. All integers are
+    /// big-endian and lengths and counts are `u32`s.
+    ///
+    /// | tag    | variant       | payload                                     |
+    /// |--------|---------------|---------------------------------------------|
+    /// | `0x00` | `Unit`        | none                                        |
+    /// | `0x01` | `Bool`        | 1 byte, `0` or `1`                          |
+    /// | `0x02` | `U8`          | 1 byte                                      |
+    /// | `0x03` | `U16`         | 2 bytes                                     |
+    /// | `0x04` | `U32`         | 4 bytes                                     |
+    /// | `0x05` | `U64`         | 8 bytes                                     |
+    /// | `0x06` | `U128`        | 16 bytes                                    |
+    /// | `0x07` | `U256`        | 32 bytes                                    |
+    /// | `0x08` | `B256`        | 32 bytes                                    |
+    /// | `0x09` | `Bytes`       | length, then the bytes                      |
+    /// | `0x0a` | `RawSlice`    | length, then the bytes                      |
+    /// | `0x0b` | `String`      | length, then the UTF-8 bytes                |
+    /// | `0x0c` | `StringSlice` | length, then the UTF-8 bytes                |
+    /// | `0x0d` | `StringArray` | length, then the UTF-8 bytes                |
+    /// | `0x0e` | `Tuple`       | count, then the elements                    |
+    /// | `0x0f` | `Array`       | count, then the elements                    |
+    /// | `0x10` | `Vector`      | count, then the elements                    |
+    /// | `0x11` | `Struct`      | count, then the fields in order             |
+    /// | `0x12` | `Enum`        | the discriminant as a `u64`, then the value |
+    ///
+    /// Enum variants are not part of the format, they are taken from the `ParamType` the bytes
+    /// are decoded with.
+    pub fn to_tagged_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![TAGGED_FORMAT_VERSION];
+        write_value(self, &mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Deserializes a token produced by [`Token::to_tagged_bytes`], taking the enum variants
+    /// from `param_type` and checking that the value matches it. Fails on unknown versions and
+    /// trailing bytes.
+    pub fn from_tagged_bytes(bytes: &[u8], param_type: &ParamType) -> Result<Token> {
+        let mut reader = Reader { bytes };
+
+        let version = reader.read_u8()?;
+        if version != TAGGED_FORMAT_VERSION {
+            return Err(error!(
+                Codec,
+                "unsupported tagged format version `{version}`, expected \
+                `{TAGGED_FORMAT_VERSION}`"
+            ));
+        }
+
+        let token = reader.read_value(param_type)?;
+        if !reader.bytes.is_empty() {
+            return Err(error!(
+                Codec,
+                "{} trailing bytes after the tagged value",
+                reader.bytes.len()
+            ));
+        }
+
+        param_type.validate_token(&token, EncoderConfig::default().max_depth)?;
+
+        Ok(token)
+    }
+}
+
+fn write_value(token: &Token, bytes: &mut Vec<u8>) -> Result<()> {
+    match token {
+        Token::Unit => bytes.push(UNIT),
+        Token::Bool(value) => bytes.extend([BOOL, u8::from(*value)]),
+        Token::U8(value) => bytes.extend([U8, *value]),
+        Token::U16(value) => write_tagged(bytes, U16, &value.to_be_bytes()),
+        Token::U32(value) => write_tagged(bytes, U32, &value.to_be_bytes()),
+        Token::U64(value) => write_tagged(bytes, U64, &value.to_be_bytes()),
+        Token::U128(value) => write_tagged(bytes, U128, &value.to_be_bytes()),
+        Token::U256(value) => {
+            let mut be_bytes = [0u8; 32];
+            value.to_big_endian(&mut be_bytes);
+            write_tagged(bytes, U256_TAG, &be_bytes);
+        }
+        Token::B256(value) => write_tagged(bytes, B256, value),
+        Token::Bytes(value) => write_with_len(bytes, BYTES, value)?,
+        Token::RawSlice(value) => write_with_len(bytes, RAW_SLICE, value)?,
+        Token::String(value) => write_with_len(bytes, STRING, value.as_bytes())?,
+        Token::StringSlice(value) => write_with_len(bytes, STRING_SLICE, value.data.as_bytes())?,
+        Token::StringArray(value) => write_with_len(bytes, STRING_ARRAY, value.data.as_bytes())?,
+        Token::Tuple(tokens) => write_all(bytes, TUPLE, tokens)?,
+        Token::Array(tokens) => write_all(bytes, ARRAY, tokens)?,
+        Token::Vector(tokens) => write_all(bytes, VECTOR, tokens)?,
+        Token::Struct(tokens) => write_all(bytes, STRUCT, tokens)?,
+        Token::Enum(selector) => {
+            let (discriminant, token, _) = selector.as_ref();
+            write_tagged(bytes, ENUM, &discriminant.to_be_bytes());
+            write_value(token, bytes)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_tagged(bytes: &mut Vec<u8>, tag: u8, payload: &[u8]) {
+    bytes.push(tag);
+    bytes.extend_from_slice(payload);
+}
+
+fn write_len(bytes: &mut Vec<u8>, tag: u8, len: usize) -> Result<()> {
+    let len = u32::try_from(len)
+        .map_err(|_| error!(Codec, "length `{len}` does not fit in the tagged format"))?;
+    write_tagged(bytes, tag, &len.to_be_bytes());
+
+    Ok(())
+}
+
+fn write_with_len(bytes: &mut Vec<u8>, tag: u8, payload: &[u8]) -> Result<()> {
+    write_len(bytes, tag, payload.len())?;
+    bytes.extend_from_slice(payload);
+
+    Ok(())
+}
+
+fn write_all(bytes: &mut Vec<u8>, tag: u8, tokens: &[Token]) -> Result<()> {
+    write_len(bytes, tag, tokens.len())?;
+    tokens
+        .iter()
+        .try_for_each(|token| write_value(token, bytes))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read_value(&mut self, param_type: &ParamType) -> Result<Token> {
+        let tag = self.read_u8()?;
+        let expected_tag = tag_of(param_type);
+        if tag != expected_tag {
+            return Err(error!(
+                Codec,
+                "expected tag `{expected_tag:#04x}` for `{param_type:?}`, got `{tag:#04x}`"
+            ));
+        }
+
+        let token = match param_type {
+            ParamType::Unit => Token::Unit,
+            ParamType::Bool => match self.read_u8()? {
+                0 => Token::Bool(false),
+                1 => Token::Bool(true),
+                other => return Err(error!(Codec, "`{other}` is not a valid bool")),
+            },
+            ParamType::U8 => Token::U8(self.read_u8()?),
+            ParamType::U16 => Token::U16(u16::from_be_bytes(self.read_fixed()?)),
+            ParamType::U32 => Token::U32(u32::from_be_bytes(self.read_fixed()?)),
+            ParamType::U64 => Token::U64(u64::from_be_bytes(self.read_fixed()?)),
+            ParamType::U128 => Token::U128(u128::from_be_bytes(self.read_fixed()?)),
+            ParamType::U256 => Token::U256(U256::from(self.read_fixed::<32>()?)),
+            ParamType::B256 => Token::B256(self.read_fixed()?),
+            ParamType::Bytes => Token::Bytes(self.read_with_len()?.to_vec()),
+            ParamType::RawSlice => Token::RawSlice(self.read_with_len()?.to_vec()),
+            ParamType::String => Token::String(self.read_string()?),
+            ParamType::StringSlice => {
+                Token::StringSlice(StaticStringToken::new(self.read_string()?, None))
+            }
+            ParamType::StringArray(len) => {
+                Token::StringArray(StaticStringToken::new(self.read_string()?, Some(*len)))
+            }
+            ParamType::Tuple(param_types) => Token::Tuple(self.read_fields(param_types.iter())?),
+            ParamType::Array(param_type, _) => Token::Array(self.read_elements(param_type)?),
+            ParamType::Vector(param_type) => Token::Vector(self.read_elements(param_type)?),
+            ParamType::Struct { fields, .. } => {
+                Token::Struct(self.read_fields(fields.iter().map(|(_, param_type)| param_type))?)
+            }
+            ParamType::Enum { enum_variants, .. } => {
+                let discriminant = u64::from_be_bytes(self.read_fixed()?);
+                let (_, variant_type) = enum_variants.select_variant(discriminant)?;
+                let token = self.read_value(variant_type)?;
+
+                Token::Enum(Box::new((discriminant, token, enum_variants.clone())))
+            }
+        };
+
+        Ok(token)
+    }
+
+    fn read_fields<'p>(
+        &mut self,
+        param_types: impl ExactSizeIterator<Item = &'p ParamType>,
+    ) -> Result<Vec<Token>> {
+        let count = self.read_len()?;
+        if count != param_types.len() {
+            return Err(error!(
+                Codec,
+                "expected {} fields, got {count}",
+                param_types.len()
+            ));
+        }
+
+        param_types
+            .map(|param_type| self.read_value(param_type))
+            .collect()
+    }
+
+    fn read_elements(&mut self, param_type: &ParamType) -> Result<Vec<Token>> {
+        let count = self.read_len()?;
+
+        // The count is untrusted, so it must not be used to preallocate.
+        let mut tokens = vec![];
+        for _ in 0..count {
+            tokens.push(self.read_value(param_type)?);
+        }
+
+        Ok(tokens)
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let bytes = self.read_with_len()?;
+
+        Ok(std::str::from_utf8(bytes)?.to_string())
+    }
+
+    fn read_with_len(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_len()?;
+
+        self.read(len)
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        Ok(u32::from_be_bytes(self.read_fixed()?) as usize)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        let [byte] = self.read_fixed()?;
+
+        Ok(byte)
+    }
+
+    fn read_fixed<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read(N)?.try_into()?)
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(error!(
+                Codec,
+                "expected {len} more bytes, only {} left",
+                self.bytes.len()
+            ));
+        }
+
+        let (read, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(read)
+    }
+}
+
+fn tag_of(param_type: &ParamType) -> u8 {
+    match param_type {
+        ParamType::Unit => UNIT,
+        ParamType::Bool => BOOL,
+        ParamType::U8 => U8,
+        ParamType::U16 => U16,
+        ParamType::U32 => U32,
+        ParamType::U64 => U64,
+        ParamType::U128 => U128,
+        ParamType::U256 => U256_TAG,
+        ParamType::B256 => B256,
+        ParamType::Bytes => BYTES,
+        ParamType::RawSlice => RAW_SLICE,
+        ParamType::String => STRING,
+        ParamType::StringSlice => STRING_SLICE,
+        ParamType::StringArray(_) => STRING_ARRAY,
+        ParamType::Tuple(_) => TUPLE,
+        ParamType::Array(..) => ARRAY,
+        ParamType::Vector(_) => VECTOR,
+        ParamType::Struct { .. } => STRUCT,
+        ParamType::Enum { .. } => ENUM,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_named, types::param_types::EnumVariants};
+
+    #[test]
+    fn token_roundtrips_through_tagged_bytes() -> Result<()> {
+        let enum_variants = EnumVariants::new(to_named(&[ParamType::Unit, ParamType::U64]))?;
+        let param_type = ParamType::Tuple(vec![
+            ParamType::Enum {
+                name: "Option".to_string(),
+                enum_variants: enum_variants.clone(),
+                generics: vec![],
+            },
+            ParamType::Vector(Box::new(ParamType::U16)),
+            ParamType::StringArray(4),
+        ]);
+        let token = Token::Tuple(vec![
+            Token::Enum(Box::new((1, Token::U64(42), enum_variants))),
+            Token::Vector(vec![Token::U16(0x0102)]),
+            Token::StringArray(StaticStringToken::new("fuel".to_string(), Some(4))),
+        ]);
+
+        let bytes = token.to_tagged_bytes()?;
+
+        let expected = [
+            vec![TAGGED_FORMAT_VERSION, TUPLE, 0, 0, 0, 3],
+            vec![ENUM, 0, 0, 0, 0, 0, 0, 0, 1, U64, 0, 0, 0, 0, 0, 0, 0, 42],
+            vec![VECTOR, 0, 0, 0, 1, U16, 1, 2],
+            vec![STRING_ARRAY, 0, 0, 0, 4],
+            b"fuel".to_vec(),
+        ]
+        .concat();
+        assert_eq!(bytes, expected);
+        assert_eq!(Token::from_tagged_bytes(&bytes, &param_type)?, token);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_tagged_bytes_are_rejected() -> Result<()> {
+        let bytes = Token::U64(1).to_tagged_bytes()?;
+
+        let err = Token::from_tagged_bytes(&bytes, &ParamType::U32)
+            .expect_err("should fail because the tag doesn't match the type");
+        assert!(err.to_string().contains("expected tag `0x04`"));
+
+        let err = Token::from_tagged_bytes(&[2, UNIT], &ParamType::Unit)
+            .expect_err("should fail because the version is unknown");
+        assert!(err
+            .to_string()
+            .contains("unsupported tagged format version `2`"));
+
+        let err = Token::from_tagged_bytes(&[&bytes[..], &[0]].concat(), &ParamType::U64)
+            .expect_err("should fail because of the trailing byte");
+        assert!(err.to_string().contains("1 trailing bytes"));
+
+        Token::from_tagged_bytes(&bytes[..5], &ParamType::U64)
+            .expect_err("should fail because the value is truncated");
+
+        Ok(())
+    }
+}
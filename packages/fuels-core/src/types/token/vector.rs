@@ -0,0 +1,93 @@
+use std::mem::discriminant;
+
+use crate::types::{
+    errors::{error, Result},
+    Token, U256,
+};
+
+macro_rules! impl_from_primitive {
+    ($($ty: ty => $variant: ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Token {
+                fn from(value: $ty) -> Self {
+                    Token::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    U256 => U256,
+    // `String` in Sway, use `Token::StringSlice` or `Token::StringArray` for `str`
+    String => String,
+);
+
+impl Token {
+    /// Creates the `Token::Vector` of a Sway `Vec` from Rust values, e.g.
+    /// `Token::vector_of(vec![1u64, 2, 3])`. Fails if the elements don't all convert into the
+    /// same `Token` variant, as a Sway `Vec` only holds values of one type.
+    pub fn vector_of<T: Into<Token>>(items: impl IntoIterator<Item = T>) -> Result<Token> {
+        let elements: Vec<Token> = items.into_iter().map(Into::into).collect();
+
+        if let Some((first, rest)) = elements.split_first() {
+            if let Some((index, other)) = rest
+                .iter()
+                .enumerate()
+                .find(|(_, element)| discriminant(*element) != discriminant(first))
+            {
+                return Err(error!(
+                    Codec,
+                    "vector elements must share a variant: element {} is `{other}` but the first one is `{first}`",
+                    index + 1
+                ));
+            }
+        }
+
+        Ok(Token::Vector(elements))
+    }
+
+    /// Creates the `Token::Bytes` of a Sway `Bytes` holding the bytes of `iter`. Note that
+    /// `Bytes` is encoded differently than a `Vec<u8>`, see [`vector_of`](Self::vector_of).
+    pub fn bytes_from(iter: impl IntoIterator<Item = u8>) -> Token {
+        Token::Bytes(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_are_created_from_rust_values() -> Result<()> {
+        assert_eq!(
+            Token::vector_of(vec![1u64, 2, 3])?,
+            Token::Vector(vec![Token::U64(1), Token::U64(2), Token::U64(3)])
+        );
+        assert_eq!(
+            Token::vector_of([true])?,
+            Token::Vector(vec![Token::Bool(true)])
+        );
+        assert_eq!(Token::vector_of(Vec::<u8>::new())?, Token::Vector(vec![]));
+        assert_eq!(Token::bytes_from([1, 2]), Token::Bytes(vec![1, 2]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn mixed_variants_are_rejected() {
+        let err =
+            Token::vector_of([Token::U8(1), Token::U8(2), Token::U16(3)]).expect_err("should fail");
+
+        assert_eq!(
+            err.to_string(),
+            "codec: vector elements must share a variant: element 2 is `U16(3)` but the first one is `U8(1)`"
+        );
+    }
+}
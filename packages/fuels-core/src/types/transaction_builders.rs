@@ -1,9 +1,12 @@
 #![cfg(feature = "std")]
 
 use std::{
-    collections::HashMap,
+    collections::{hash_map::DefaultHasher, HashMap},
     fmt::{Debug, Formatter},
+    hash::{Hash, Hasher},
     iter::repeat,
+    mem::discriminant,
+    sync::Arc,
 };
 
 use async_trait::async_trait;
@@ -61,6 +64,31 @@ impl<T: DryRunner> DryRunner for &T {
     }
 }
 
+/// The parts of a transaction that determine its size and, for identical scripts, its fee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TransactionShape {
+    pub num_inputs: usize,
+    pub num_outputs: usize,
+    pub num_witnesses: usize,
+    pub witnesses_size: usize,
+    pub script_len: usize,
+    pub script_data_len: usize,
+    pub num_storage_slots: usize,
+}
+
+/// Hashes the parts of `input` its size and predicate gas depend on, leaving out the coin.
+fn hash_fee_relevant_input(input: &Input, hasher: &mut impl Hasher) {
+    discriminant(input).hash(hasher);
+
+    if let Some(CoinType::Message(message)) = input.resource() {
+        message.data.hash(hasher);
+    }
+    if let Input::ResourcePredicate { code, data, .. } = input {
+        code.hash(hasher);
+        data.hash(hasher);
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct UnresolvedWitnessIndexes {
     owner_to_idx_offset: HashMap<Bech32Address, u64>,
@@ -119,9 +147,31 @@ pub trait TransactionBuilder: BuildableTransaction + Send + sealed::Sealed {
     type TxType: Transaction;
 
     fn add_signer(&mut self, signer: impl Signer + Send + Sync) -> Result<&mut Self>;
+    /// The index of the witness that will hold the signature of the signer with `address`, or
+    /// `None` if no such signer was added. Signatures are placed after the witnesses already in
+    /// the builder, so the index only holds as long as no witnesses are added or removed.
+    fn signer_witness_index(&self, address: &Bech32Address) -> Option<u64>;
+    /// Replaces the data of every predicate input owned by `predicate_address`. Predicate data
+    /// depending on the transaction, e.g. on the [`signer_witness_index`] of the signature the
+    /// predicate checks, can thus be set once the transaction is assembled and before building
+    /// it.
+    ///
+    /// [`signer_witness_index`]: Self::signer_witness_index
+    fn set_predicate_data(
+        &mut self,
+        predicate_address: &Bech32Address,
+        data: UnresolvedBytes,
+    ) -> &mut Self;
+    /// Moves the contract inputs before all other inputs, keeping the relative order of both
+    /// groups, and updates the `input_index` of the contract outputs to match. Inputs added at
+    /// the end of the transaction, e.g. to cover its fee, then leave the indexes of the contract
+    /// inputs untouched.
+    fn ensure_contract_inputs_first(&mut self) -> &mut Self;
     async fn fee_checked_from_tx(&self, provider: impl DryRunner)
         -> Result<Option<TransactionFee>>;
     fn with_tx_policies(self, tx_policies: TxPolicies) -> Self;
+    fn tx_policies(&self) -> &TxPolicies;
+    fn tx_policies_mut(&mut self) -> &mut TxPolicies;
     fn with_inputs(self, inputs: Vec<Input>) -> Self;
     fn with_outputs(self, outputs: Vec<Output>) -> Self;
     fn with_witnesses(self, witnesses: Vec<Witness>) -> Self;
@@ -132,6 +182,13 @@ pub trait TransactionBuilder: BuildableTransaction + Send + sealed::Sealed {
     fn witnesses(&self) -> &Vec<Witness>;
     fn witnesses_mut(&mut self) -> &mut Vec<Witness>;
     fn with_estimation_horizon(self, block_horizon: u32) -> Self;
+    fn estimation_horizon(&self) -> u32;
+    fn shape(&self) -> TransactionShape;
+    /// Identifies everything the fee of the transaction depends on besides the gas price: its
+    /// shape, the tx policies, the kinds of its inputs and outputs, the predicates along with
+    /// their data, the data of message inputs and, for scripts, the script and its data. The
+    /// coins used are left out, so transactions differing only in those share a fingerprint.
+    fn fee_fingerprint(&self) -> u64;
 }
 
 macro_rules! impl_tx_trait {
@@ -157,11 +214,67 @@ macro_rules! impl_tx_trait {
                 self.unresolved_witness_indexes
                     .owner_to_idx_offset
                     .insert(address.clone(), index_offset);
-                self.unresolved_signers.push(Box::new(signer));
+                self.unresolved_signers.push(Arc::new(signer));
 
                 Ok(self)
             }
 
+            fn signer_witness_index(&self, address: &Bech32Address) -> Option<u64> {
+                self.unresolved_witness_indexes
+                    .owner_to_idx_offset
+                    .get(address)
+                    .map(|offset| self.witnesses.len() as u64 + offset)
+            }
+
+            fn set_predicate_data(
+                &mut self,
+                predicate_address: &Bech32Address,
+                data: UnresolvedBytes,
+            ) -> &mut Self {
+                for input in &mut self.inputs {
+                    if let Input::ResourcePredicate {
+                        resource,
+                        data: predicate_data,
+                        ..
+                    } = input
+                    {
+                        if resource.owner() == predicate_address {
+                            *predicate_data = data.clone();
+                        }
+                    }
+                }
+
+                self
+            }
+
+            fn ensure_contract_inputs_first(&mut self) -> &mut Self {
+                let (contracts, others): (Vec<_>, Vec<_>) = std::mem::take(&mut self.inputs)
+                    .into_iter()
+                    .enumerate()
+                    .partition(|(_, input)| matches!(input, Input::Contract { .. }));
+
+                let mut new_indexes = vec![0; contracts.len() + others.len()];
+                self.inputs = contracts
+                    .into_iter()
+                    .chain(others)
+                    .enumerate()
+                    .map(|(new_index, (old_index, input))| {
+                        new_indexes[old_index] = new_index;
+                        input
+                    })
+                    .collect();
+
+                for output in &mut self.outputs {
+                    if let Output::Contract(contract) = output {
+                        if let Some(new_index) = new_indexes.get(contract.input_index as usize) {
+                            contract.input_index = *new_index as u8;
+                        }
+                    }
+                }
+
+                self
+            }
+
             async fn fee_checked_from_tx(
                 &self,
                 provider: impl DryRunner,
@@ -201,6 +314,14 @@ macro_rules! impl_tx_trait {
                 self
             }
 
+            fn tx_policies(&self) -> &TxPolicies {
+                &self.tx_policies
+            }
+
+            fn tx_policies_mut(&mut self) -> &mut TxPolicies {
+                &mut self.tx_policies
+            }
+
             fn with_inputs(mut self, inputs: Vec<Input>) -> Self {
                 self.inputs = inputs;
                 self
@@ -245,9 +366,43 @@ macro_rules! impl_tx_trait {
 
                 self
             }
+
+            fn estimation_horizon(&self) -> u32 {
+                self.gas_price_estimation_block_horizon
+            }
+
+            fn shape(&self) -> TransactionShape {
+                self.transaction_shape()
+            }
+
+            fn fee_fingerprint(&self) -> u64 {
+                let mut hasher = DefaultHasher::new();
+
+                self.transaction_shape().hash(&mut hasher);
+                self.tx_policies.hash(&mut hasher);
+                for input in &self.inputs {
+                    hash_fee_relevant_input(input, &mut hasher);
+                }
+                for output in &self.outputs {
+                    discriminant(output).hash(&mut hasher);
+                }
+                self.hash_fee_specifics(&mut hasher);
+
+                hasher.finish()
+            }
         }
 
         impl $ty {
+            fn base_shape(&self) -> TransactionShape {
+                TransactionShape {
+                    num_inputs: self.inputs.len(),
+                    num_outputs: self.outputs.len(),
+                    num_witnesses: self.witnesses.len() + self.unresolved_signers.len(),
+                    witnesses_size: calculate_witnesses_size(&self.witnesses),
+                    ..Default::default()
+                }
+            }
+
             fn set_witness_indexes(&mut self) {
                 self.unresolved_witness_indexes.owner_to_idx_offset = self
                     .inputs()
@@ -344,7 +499,10 @@ impl Debug for dyn Signer + Send + Sync {
     }
 }
 
-#[derive(Debug, Default)]
+/// Builders can be cloned to retry a transaction with different policies, e.g. a higher tip,
+/// without selecting its inputs again. Selected coins are only marked as used by the provider's
+/// coin cache once a transaction spending them is submitted, so clones don't reserve anything.
+#[derive(Debug, Default, Clone)]
 pub struct ScriptTransactionBuilder {
     pub script: Vec<u8>,
     pub script_data: Vec<u8>,
@@ -355,10 +513,11 @@ pub struct ScriptTransactionBuilder {
     pub gas_estimation_tolerance: f32,
     pub gas_price_estimation_block_horizon: u32,
     unresolved_witness_indexes: UnresolvedWitnessIndexes,
-    unresolved_signers: Vec<Box<dyn Signer + Send + Sync>>,
+    unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
 }
 
-#[derive(Default)]
+/// See [`ScriptTransactionBuilder`] on reusing a builder across attempts.
+#[derive(Default, Clone)]
 pub struct CreateTransactionBuilder {
     pub bytecode_length: u64,
     pub bytecode_witness_index: u8,
@@ -370,7 +529,7 @@ pub struct CreateTransactionBuilder {
     pub salt: Salt,
     pub gas_price_estimation_block_horizon: u32,
     unresolved_witness_indexes: UnresolvedWitnessIndexes,
-    unresolved_signers: Vec<Box<dyn Signer + Send + Sync>>,
+    unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
 }
 
 impl_tx_trait!(ScriptTransactionBuilder, ScriptTransaction);
@@ -516,6 +675,20 @@ impl ScriptTransactionBuilder {
         Ok(tx)
     }
 
+    fn transaction_shape(&self) -> TransactionShape {
+        TransactionShape {
+            script_len: self.script.len(),
+            script_data_len: self.script_data.len(),
+            ..self.base_shape()
+        }
+    }
+
+    // The script gas limit is estimated by executing the script, unless set in the tx policies
+    fn hash_fee_specifics(&self, hasher: &mut impl Hasher) {
+        self.script.hash(hasher);
+        self.script_data.hash(hasher);
+    }
+
     fn base_offset(&self, consensus_parameters: &ConsensusParameters) -> usize {
         offsets::base_offset_script(consensus_parameters)
             + padded_len_usize(self.script_data.len())
@@ -598,21 +771,43 @@ impl ScriptTransactionBuilder {
         inputs: Vec<Input>,
         tx_policies: TxPolicies,
     ) -> Self {
-        let script_data: Vec<u8> = [to.to_vec(), amount.to_be_bytes().to_vec()]
-            .into_iter()
-            .flatten()
-            .collect();
+        Self::prepare_message_to_output_with_data(to, amount, vec![], inputs, tx_policies)
+    }
+
+    /// Same as [`prepare_message_to_output`](Self::prepare_message_to_output) but the sent
+    /// message carries `data`.
+    pub fn prepare_message_to_output_with_data(
+        to: Address,
+        amount: u64,
+        data: Vec<u8>,
+        inputs: Vec<Input>,
+        tx_policies: TxPolicies,
+    ) -> Self {
+        let script_data: Vec<u8> = [
+            to.to_vec(),
+            amount.to_be_bytes().to_vec(),
+            (data.len() as u64).to_be_bytes().to_vec(),
+            data,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
 
         // This script loads:
         //  - a pointer to the recipient address,
         //  - the amount
-        // into the registers 0x10, 0x11
+        //  - a pointer to the message data
+        //  - the length of the message data
+        // into the registers 0x10, 0x11, 0x12, 0x13
         // and calls the SMO instruction
         let script: Vec<u8> = vec![
             op::gtf(0x10, 0x00, GTFArgs::ScriptData.into()),
             op::addi(0x11, 0x10, Bytes32::LEN as u16),
             op::lw(0x11, 0x11, 0),
-            op::smo(0x10, 0x00, 0x00, 0x11),
+            op::addi(0x12, 0x10, (Bytes32::LEN + WORD_SIZE) as u16),
+            op::lw(0x13, 0x12, 0),
+            op::addi(0x12, 0x12, WORD_SIZE as u16),
+            op::smo(0x10, 0x12, 0x13, 0x11),
             op::ret(RegId::ONE),
         ]
         .into_iter()
@@ -702,6 +897,16 @@ impl CreateTransactionBuilder {
         Ok(tx)
     }
 
+    fn transaction_shape(&self) -> TransactionShape {
+        TransactionShape {
+            num_storage_slots: self.storage_slots.len(),
+            ..self.base_shape()
+        }
+    }
+
+    // Storage slots have a fixed size and the bytecode is a witness, both covered by the shape
+    fn hash_fee_specifics(&self, _hasher: &mut impl Hasher) {}
+
     fn base_offset(&self, consensus_parameters: &ConsensusParameters) -> usize {
         offsets::base_offset_create(consensus_parameters)
     }
@@ -788,7 +993,12 @@ fn resolve_fuel_inputs(
                 resource,
                 code,
                 data,
-            } => resolve_predicate_resource(resource, code, data, &mut data_offset),
+            } => resolve_predicate_resource(
+                resource,
+                Arc::unwrap_or_clone(code),
+                data,
+                &mut data_offset,
+            ),
             Input::Contract {
                 utxo_id,
                 balance_root,
@@ -957,7 +1167,7 @@ pub fn create_coin_message_predicate(
 
 async fn generate_missing_witnesses(
     id: Bytes32,
-    unresolved_signatures: &[Box<dyn Signer + Send + Sync>],
+    unresolved_signatures: &[Arc<dyn Signer + Send + Sync>],
 ) -> Result<Vec<Witness>> {
     let mut witnesses = Vec::with_capacity(unresolved_signatures.len());
     for signer in unresolved_signatures {
@@ -978,7 +1188,7 @@ mod tests {
     use fuel_tx::{input::coin::CoinSigned, UtxoId};
 
     use super::*;
-    use crate::types::{bech32::Bech32Address, message::MessageStatus};
+    use crate::types::{bech32::Bech32Address, message::MessageStatus, unresolved_bytes::Data};
 
     #[test]
     fn storage_slots_are_sorted_when_set() {
@@ -1176,4 +1386,147 @@ mod tests {
         tb.add_signer(signer.clone()).unwrap();
         tb.add_signer(signer.clone()).unwrap();
     }
+
+    #[tokio::test]
+    async fn cloned_builder_is_reused_with_new_policies() -> Result<()> {
+        // given
+        let mut tb = ScriptTransactionBuilder::default()
+            .with_inputs(given_inputs(2))
+            .with_tx_policies(TxPolicies::default().with_tip(1));
+        tb.add_signer(MockSigner::default())?;
+
+        // when
+        let first_attempt = tb.clone().build(&MockDryRunner::default()).await?;
+        let second_attempt = tb
+            .with_tx_policies(TxPolicies::default().with_tip(2))
+            .build(&MockDryRunner::default())
+            .await?;
+
+        // then
+        assert_eq!(first_attempt.inputs(), second_attempt.inputs());
+        assert_eq!(first_attempt.tip(), 1);
+        assert_eq!(second_attempt.tip(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn signer_witness_index_follows_the_existing_witnesses() -> Result<()> {
+        let first = MockSigner {
+            address: Bech32Address::new("fuel", [1; 32]),
+        };
+        let second = MockSigner {
+            address: Bech32Address::new("fuel", [2; 32]),
+        };
+
+        let mut tb = ScriptTransactionBuilder::default().with_witnesses(given_witnesses(2));
+        tb.add_signer(first.clone())?;
+        tb.add_signer(second.clone())?;
+
+        assert_eq!(tb.signer_witness_index(&first.address), Some(2));
+        assert_eq!(tb.signer_witness_index(&second.address), Some(3));
+        assert_eq!(tb.signer_witness_index(&Bech32Address::default()), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fee_fingerprint_ignores_only_the_coins_spent() {
+        let fingerprint = |inputs, tip, script_data| {
+            ScriptTransactionBuilder::default()
+                .with_inputs(inputs)
+                .with_tx_policies(TxPolicies::default().with_tip(tip))
+                .with_script_data(script_data)
+                .fee_fingerprint()
+        };
+        let predicate_input = |code| {
+            Input::resource_predicate(
+                CoinType::Coin(Coin::default()),
+                code,
+                UnresolvedBytes::default(),
+            )
+        };
+        let base = fingerprint(given_inputs(2), 1, vec![1]);
+
+        let mut other_coins = given_inputs(3);
+        other_coins.remove(0);
+        assert_eq!(fingerprint(other_coins, 1, vec![1]), base);
+
+        assert_ne!(fingerprint(given_inputs(2), 2, vec![1]), base);
+        assert_ne!(fingerprint(given_inputs(2), 1, vec![2]), base);
+        assert_ne!(
+            fingerprint(vec![predicate_input(vec![1])], 1, vec![1]),
+            fingerprint(vec![predicate_input(vec![2])], 1, vec![1])
+        );
+    }
+
+    #[test]
+    fn predicate_data_is_replaced_only_for_the_given_predicate() {
+        let predicate_input = |owner: [u8; 32]| {
+            let coin = CoinType::Coin(Coin {
+                owner: Bech32Address::new("fuel", owner),
+                ..Default::default()
+            });
+            Input::resource_predicate(coin, vec![], UnresolvedBytes::default())
+        };
+        let mut tb = ScriptTransactionBuilder::default()
+            .with_inputs(vec![predicate_input([1; 32]), predicate_input([2; 32])]);
+
+        let data = UnresolvedBytes::new(vec![Data::Inline(vec![7])]);
+        tb.set_predicate_data(&Bech32Address::new("fuel", [1; 32]), data.clone());
+
+        let datas: Vec<_> = tb
+            .inputs()
+            .iter()
+            .filter_map(|input| match input {
+                Input::ResourcePredicate { data, .. } => Some(data.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(datas, vec![data, UnresolvedBytes::default()]);
+    }
+
+    #[test]
+    fn contract_inputs_are_moved_first_and_outputs_follow_them() {
+        let contract_input = |id: u8| {
+            Input::contract(
+                UtxoId::default(),
+                Bytes32::zeroed(),
+                Bytes32::zeroed(),
+                TxPointer::default(),
+                ContractId::new([id; 32]),
+            )
+        };
+        let coins = given_inputs(2);
+        let mut tb = ScriptTransactionBuilder::default()
+            .with_inputs(vec![
+                coins[0].clone(),
+                contract_input(1),
+                coins[1].clone(),
+                contract_input(2),
+            ])
+            .with_outputs(vec![
+                Output::contract(3, Bytes32::zeroed(), Bytes32::zeroed()),
+                Output::contract(1, Bytes32::zeroed(), Bytes32::zeroed()),
+            ]);
+
+        tb.ensure_contract_inputs_first();
+
+        assert_eq!(
+            tb.inputs(),
+            &vec![
+                contract_input(1),
+                contract_input(2),
+                coins[0].clone(),
+                coins[1].clone()
+            ]
+        );
+        assert_eq!(
+            tb.outputs(),
+            &vec![
+                Output::contract(1, Bytes32::zeroed(), Bytes32::zeroed()),
+                Output::contract(0, Bytes32::zeroed(), Bytes32::zeroed()),
+            ]
+        );
+    }
 }
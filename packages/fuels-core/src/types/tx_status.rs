@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use fuel_abi_types::error_codes::{
     FAILED_ASSERT_EQ_SIGNAL, FAILED_ASSERT_SIGNAL, FAILED_REQUIRE_SIGNAL,
     FAILED_SEND_MESSAGE_SIGNAL, FAILED_TRANSFER_TO_ADDRESS_SIGNAL,
@@ -9,10 +11,22 @@ use fuel_tx::Receipt;
 use fuel_vm::state::ProgramState;
 
 use crate::{
-    codec::LogDecoder,
-    types::errors::{transaction::Reason, Error, Result},
+    codec::{decode_logs_as_tokens, DecoderConfig, LogDecoder, LogId},
+    types::{
+        errors::{transaction::Reason, Error, Result},
+        param_types::ParamType,
+        Token,
+    },
 };
 
+/// The revert code of a reverted transaction, along with the value logged by a failing
+/// `require`, if its type was known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevertReason {
+    pub code: u64,
+    pub logged: Option<Token>,
+}
+
 #[derive(Debug, Clone)]
 pub enum TxStatus {
     Success {
@@ -77,6 +91,52 @@ impl TxStatus {
         }))
     }
 
+    /// Returns `None` unless the transaction reverted. The value logged by a failing `require`,
+    /// i.e. the log right before the revert, is decoded if its type is in `log_types`.
+    pub fn revert_reason(
+        &self,
+        log_types: &HashMap<LogId, ParamType>,
+    ) -> Result<Option<RevertReason>> {
+        let Self::Revert {
+            receipts,
+            revert_id,
+            ..
+        } = self
+        else {
+            return Ok(None);
+        };
+
+        let logged = if *revert_id == FAILED_REQUIRE_SIGNAL {
+            Self::log_preceding_revert(receipts)
+                .map(|log| decode_logs_as_tokens(log, log_types, DecoderConfig::default()))
+                .transpose()?
+                .and_then(|mut decoded| decoded.pop())
+                .map(|log| log.token)
+        } else {
+            None
+        };
+
+        Ok(Some(RevertReason {
+            code: *revert_id,
+            logged,
+        }))
+    }
+
+    /// The log receipt emitted right before the `Revert` receipt, which is the one a failing
+    /// `require` produces. Earlier logs belong to the regular flow of the call.
+    fn log_preceding_revert(receipts: &[Receipt]) -> Option<&[Receipt]> {
+        let revert_index = receipts
+            .iter()
+            .rposition(|receipt| matches!(receipt, Receipt::Revert { .. }))?;
+        let log_index = revert_index.checked_sub(1)?;
+
+        matches!(
+            receipts[log_index],
+            Receipt::Log { .. } | Receipt::LogData { .. }
+        )
+        .then(|| &receipts[log_index..revert_index])
+    }
+
     pub fn take_receipts_checked(self, log_decoder: Option<&LogDecoder>) -> Result<Vec<Receipt>> {
         self.check(log_decoder)?;
         Ok(self.take_receipts())
@@ -118,3 +178,90 @@ impl From<ClientTransactionStatus> for TxStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fuel_tx::ContractId;
+
+    use super::*;
+
+    #[test]
+    fn revert_reason_decodes_the_require_log() -> Result<()> {
+        let contract_id = ContractId::zeroed();
+        let log_id = LogId::new(contract_id, 7);
+        let receipts = vec![
+            Receipt::log(contract_id, 42, 7, 0, 0, 0, 0),
+            Receipt::revert(contract_id, FAILED_REQUIRE_SIGNAL, 0, 0),
+        ];
+        let log_types = HashMap::from([(log_id, ParamType::U64)]);
+
+        let reverted = TxStatus::Revert {
+            receipts: receipts.clone(),
+            reason: "Revert".to_string(),
+            revert_id: FAILED_REQUIRE_SIGNAL,
+        };
+        let expected = RevertReason {
+            code: FAILED_REQUIRE_SIGNAL,
+            logged: Some(Token::U64(42)),
+        };
+        assert_eq!(reverted.revert_reason(&log_types)?, Some(expected));
+
+        let reverted_with_code = TxStatus::Revert {
+            receipts,
+            reason: "Revert".to_string(),
+            revert_id: 3,
+        };
+        let expected = RevertReason {
+            code: 3,
+            logged: None,
+        };
+        assert_eq!(
+            reverted_with_code.revert_reason(&log_types)?,
+            Some(expected)
+        );
+
+        let succeeded = TxStatus::Success { receipts: vec![] };
+        assert_eq!(succeeded.revert_reason(&log_types)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn revert_reason_ignores_logs_not_right_before_the_revert() -> Result<()> {
+        let contract_id = ContractId::zeroed();
+        let log_types = HashMap::from([(LogId::new(contract_id, 7), ParamType::U64)]);
+        let revert_with = |receipts| TxStatus::Revert {
+            receipts,
+            reason: "Revert".to_string(),
+            revert_id: FAILED_REQUIRE_SIGNAL,
+        };
+
+        let logged_before_the_require = revert_with(vec![
+            Receipt::log(contract_id, 1, 7, 0, 0, 0, 0),
+            Receipt::log(contract_id, 42, 7, 0, 0, 0, 0),
+            Receipt::revert(contract_id, FAILED_REQUIRE_SIGNAL, 0, 0),
+        ]);
+        assert_eq!(
+            logged_before_the_require.revert_reason(&log_types)?,
+            Some(RevertReason {
+                code: FAILED_REQUIRE_SIGNAL,
+                logged: Some(Token::U64(42)),
+            })
+        );
+
+        let not_logged_by_the_require = revert_with(vec![
+            Receipt::log(contract_id, 1, 7, 0, 0, 0, 0),
+            Receipt::ret(contract_id, 0, 0, 0),
+            Receipt::revert(contract_id, FAILED_REQUIRE_SIGNAL, 0, 0),
+        ]);
+        assert_eq!(
+            not_logged_by_the_require.revert_reason(&log_types)?,
+            Some(RevertReason {
+                code: FAILED_REQUIRE_SIGNAL,
+                logged: None,
+            })
+        );
+
+        Ok(())
+    }
+}
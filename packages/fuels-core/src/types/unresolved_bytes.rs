@@ -35,6 +35,17 @@ impl UnresolvedBytes {
         Default::default()
     }
 
+    /// Inserts `prefix` in front of the encoded data. The dynamic data stays after all of the
+    /// inline data, so its pointers account for the prefix once resolved.
+    pub fn prepend_static(&mut self, prefix: &[u8]) {
+        self.data.insert(0, Data::Inline(prefix.to_vec()));
+    }
+
+    /// The number of bytes preceding the dynamic data once resolved.
+    pub(crate) fn inline_len(&self) -> usize {
+        Self::amount_of_inline_bytes(&self.data) as usize
+    }
+
     /// Uses the `start_addr` to resolve any pointers contained within. Once
     /// they are resolved the raw bytes are returned.
     ///
@@ -95,3 +106,29 @@ impl UnresolvedBytes {
             .sum()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepending_shifts_pointers_to_dynamic_data() {
+        let mut bytes = UnresolvedBytes::new(vec![
+            Data::Dynamic(vec![Data::Inline(vec![7; WORD_SIZE])]),
+            Data::Inline(vec![1; WORD_SIZE]),
+        ]);
+
+        bytes.prepend_static(&[9; 4]);
+        let resolved = bytes.resolve(100);
+
+        let expected_ptr = 100 + 4 + 2 * WORD_SIZE as u64;
+        let expected = [
+            vec![9; 4],
+            expected_ptr.to_be_bytes().to_vec(),
+            vec![1; WORD_SIZE],
+            vec![7; WORD_SIZE],
+        ]
+        .concat();
+        assert_eq!(resolved, expected);
+    }
+}
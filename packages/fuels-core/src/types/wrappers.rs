@@ -9,6 +9,7 @@ pub mod message_proof;
 pub mod node_info;
 pub mod transaction;
 pub mod transaction_response;
+pub mod unsigned_transaction;
 pub mod output {
     pub use fuel_tx::Output;
 }
@@ -6,6 +6,8 @@ use fuel_core_client::client::types::{
     primitives::Bytes32,
 };
 
+use crate::types::transaction_response::TransactionResponse;
+
 #[derive(Debug, Clone)]
 pub struct Header {
     pub id: Bytes32,
@@ -55,3 +57,11 @@ impl From<ClientBlock> for Block {
         }
     }
 }
+
+/// A block together with all of its transactions.
+#[derive(Debug, Clone)]
+pub struct FullBlock {
+    pub id: Bytes32,
+    pub header: Header,
+    pub transactions: Vec<TransactionResponse>,
+}
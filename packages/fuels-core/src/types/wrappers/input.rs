@@ -1,11 +1,26 @@
 #![cfg(feature = "std")]
 
-use std::hash::Hash;
+use std::{hash::Hash, sync::Arc};
 
-use fuel_tx::{TxPointer, UtxoId};
-use fuel_types::{AssetId, Bytes32, ContractId};
+use fuel_tx::{
+    input::{
+        coin::{CoinPredicate, CoinSigned},
+        contract::Contract,
+        message::{
+            MessageCoinPredicate, MessageCoinSigned, MessageDataPredicate, MessageDataSigned,
+        },
+    },
+    Input as FuelInput, TxPointer, UtxoId,
+};
+use fuel_types::{Address, AssetId, Bytes32, ContractId, Nonce};
 
-use crate::types::{coin_type::CoinType, unresolved_bytes::UnresolvedBytes};
+use crate::types::{
+    bech32::Bech32Address,
+    coin::Coin,
+    coin_type::CoinType,
+    message::Message,
+    unresolved_bytes::{Data, UnresolvedBytes},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Input {
@@ -14,7 +29,8 @@ pub enum Input {
     },
     ResourcePredicate {
         resource: CoinType,
-        code: Vec<u8>,
+        /// Shared with the predicate and its other inputs.
+        code: Arc<Vec<u8>>,
         data: UnresolvedBytes,
     },
     Contract {
@@ -31,18 +47,27 @@ impl Input {
         Self::ResourceSigned { resource }
     }
 
-    pub const fn resource_predicate(
+    pub fn resource_predicate(
         resource: CoinType,
-        code: Vec<u8>,
+        code: impl Into<Arc<Vec<u8>>>,
         data: UnresolvedBytes,
     ) -> Self {
         Self::ResourcePredicate {
             resource,
-            code,
+            code: code.into(),
             data,
         }
     }
 
+    pub fn resource(&self) -> Option<&CoinType> {
+        match self {
+            Self::ResourceSigned { resource, .. } | Self::ResourcePredicate { resource, .. } => {
+                Some(resource)
+            }
+            _ => None,
+        }
+    }
+
     pub fn amount(&self) -> Option<u64> {
         match self {
             Self::ResourceSigned { resource, .. } | Self::ResourcePredicate { resource, .. } => {
@@ -61,6 +86,15 @@ impl Input {
         }
     }
 
+    pub fn owner(&self) -> Option<&Bech32Address> {
+        match self {
+            Self::ResourceSigned { resource, .. } | Self::ResourcePredicate { resource, .. } => {
+                Some(resource.owner())
+            }
+            _ => None,
+        }
+    }
+
     pub fn contains_data(&self) -> bool {
         match self {
             Self::ResourceSigned {
@@ -91,3 +125,168 @@ impl Input {
         }
     }
 }
+
+/// Recovers the resource an already built input spends. Predicate data is carried over as is.
+impl From<FuelInput> for Input {
+    fn from(input: FuelInput) -> Self {
+        match input {
+            FuelInput::CoinSigned(CoinSigned {
+                utxo_id,
+                owner,
+                amount,
+                asset_id,
+                ..
+            }) => Self::resource_signed(CoinType::Coin(coin(utxo_id, owner, amount, asset_id))),
+            FuelInput::CoinPredicate(CoinPredicate {
+                utxo_id,
+                owner,
+                amount,
+                asset_id,
+                predicate,
+                predicate_data,
+                ..
+            }) => Self::resource_predicate(
+                CoinType::Coin(coin(utxo_id, owner, amount, asset_id)),
+                predicate,
+                inline_data(predicate_data),
+            ),
+            FuelInput::MessageCoinSigned(MessageCoinSigned {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                ..
+            }) => Self::resource_signed(CoinType::Message(message(
+                sender,
+                recipient,
+                amount,
+                nonce,
+                vec![],
+            ))),
+            FuelInput::MessageDataSigned(MessageDataSigned {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                data,
+                ..
+            }) => Self::resource_signed(CoinType::Message(message(
+                sender, recipient, amount, nonce, data,
+            ))),
+            FuelInput::MessageCoinPredicate(MessageCoinPredicate {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                predicate,
+                predicate_data,
+                ..
+            }) => Self::resource_predicate(
+                CoinType::Message(message(sender, recipient, amount, nonce, vec![])),
+                predicate,
+                inline_data(predicate_data),
+            ),
+            FuelInput::MessageDataPredicate(MessageDataPredicate {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                data,
+                predicate,
+                predicate_data,
+                ..
+            }) => Self::resource_predicate(
+                CoinType::Message(message(sender, recipient, amount, nonce, data)),
+                predicate,
+                inline_data(predicate_data),
+            ),
+            FuelInput::Contract(Contract {
+                utxo_id,
+                balance_root,
+                state_root,
+                tx_pointer,
+                contract_id,
+            }) => Self::contract(utxo_id, balance_root, state_root, tx_pointer, contract_id),
+        }
+    }
+}
+
+fn coin(utxo_id: UtxoId, owner: Address, amount: u64, asset_id: AssetId) -> Coin {
+    Coin {
+        amount,
+        asset_id,
+        utxo_id,
+        owner: owner.into(),
+        ..Default::default()
+    }
+}
+
+fn message(
+    sender: Address,
+    recipient: Address,
+    amount: u64,
+    nonce: Nonce,
+    data: Vec<u8>,
+) -> Message {
+    Message {
+        amount,
+        sender: sender.into(),
+        recipient: recipient.into(),
+        nonce,
+        data,
+        da_height: 0,
+        status: Default::default(),
+    }
+}
+
+fn inline_data(data: Vec<u8>) -> UnresolvedBytes {
+    UnresolvedBytes::new(vec![Data::Inline(data)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_coin_input_is_converted_back_into_a_resource() {
+        let coin = Coin {
+            amount: 100,
+            asset_id: AssetId::from([1; 32]),
+            utxo_id: UtxoId::new([2; 32].into(), 0),
+            owner: Address::from([3; 32]).into(),
+            ..Default::default()
+        };
+        let fuel_input = FuelInput::coin_signed(
+            coin.utxo_id,
+            (&coin.owner).into(),
+            coin.amount,
+            coin.asset_id,
+            TxPointer::default(),
+            0,
+        );
+
+        let input = Input::from(fuel_input);
+
+        assert_eq!(input, Input::resource_signed(CoinType::Coin(coin)));
+    }
+
+    #[test]
+    fn predicate_data_is_kept_inline() {
+        let fuel_input = FuelInput::message_coin_predicate(
+            Default::default(),
+            Default::default(),
+            10,
+            Default::default(),
+            0,
+            vec![1, 2],
+            vec![3, 4],
+        );
+
+        let Input::ResourcePredicate { code, data, .. } = Input::from(fuel_input) else {
+            panic!("expected a predicate input");
+        };
+
+        assert_eq!(*code, vec![1, 2]);
+        assert_eq!(data.resolve(0), vec![3, 4]);
+    }
+}
@@ -4,8 +4,8 @@ use async_trait::async_trait;
 use fuel_crypto::{Message, Signature};
 use fuel_tx::{
     field::{
-        Inputs, Maturity, MintAmount, MintAssetId, Outputs, Script as ScriptField, ScriptData,
-        ScriptGasLimit, WitnessLimit, Witnesses,
+        Inputs, Maturity, MintAmount, MintAssetId, Outputs, Policies as PoliciesField,
+        Script as ScriptField, ScriptData, ScriptGasLimit, WitnessLimit, Witnesses,
     },
     input::{
         coin::{CoinPredicate, CoinSigned},
@@ -13,6 +13,7 @@ use fuel_tx::{
             MessageCoinPredicate, MessageCoinSigned, MessageDataPredicate, MessageDataSigned,
         },
     },
+    policies::PolicyType,
     Buildable, Bytes32, Cacheable, Chargeable, ConsensusParameters, Create, FormatValidityChecks,
     Input, Mint, Output, Salt as FuelSalt, Script, StorageSlot, Transaction as FuelTransaction,
     TransactionFee, UniqueIdentifier, Witness,
@@ -28,7 +29,7 @@ use crate::{
     traits::Signer,
     types::{
         bech32::Bech32Address,
-        errors::{error_transaction, Result},
+        errors::{error_transaction, Error, Result},
     },
     utils::{calculate_witnesses_size, sealed},
 };
@@ -103,7 +104,7 @@ impl MintTransaction {
     }
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 //ANCHOR: tx_policies_struct
 pub struct TxPolicies {
     tip: Option<u64>,
@@ -188,6 +189,16 @@ pub enum TransactionType {
     Mint(MintTransaction),
 }
 
+impl TransactionType {
+    pub fn id(&self, chain_id: ChainId) -> Bytes32 {
+        match self {
+            TransactionType::Script(tx) => tx.id(chain_id),
+            TransactionType::Create(tx) => tx.id(chain_id),
+            TransactionType::Mint(tx) => tx.id(chain_id),
+        }
+    }
+}
+
 pub trait EstimablePredicates: sealed::Sealed {
     /// If a transaction contains predicates, we have to estimate them
     /// before sending the transaction to the node. The estimation will check
@@ -233,6 +244,10 @@ pub trait Transaction:
     /// <https://github.com/FuelLabs/fuel-specs/blob/master/src/tx-format/transaction.md>
     fn check(&self, block_height: u32, consensus_parameters: &ConsensusParameters) -> Result<()>;
 
+    /// The id the transaction has on the chain `chain_id`. It doesn't depend on the witnesses
+    /// or on what the provider sets while sending, like the gas used by predicates, so the id
+    /// of a built transaction is known before it is submitted, e.g. to record it and retry
+    /// safely if the submission is interrupted.
     fn id(&self, chain_id: ChainId) -> Bytes32;
 
     fn maturity(&self) -> u32;
@@ -319,6 +334,21 @@ macro_rules! impl_tx_wrapper {
             }
         }
 
+        impl TryFrom<FuelTransaction> for $wrapper {
+            type Error = Error;
+
+            fn try_from(tx: FuelTransaction) -> Result<Self> {
+                match tx {
+                    FuelTransaction::$wrapped(tx) => Ok(tx.into()),
+                    _ => Err(error_transaction!(
+                        Other,
+                        "expected a `{}` transaction",
+                        stringify!($wrapped)
+                    )),
+                }
+            }
+        }
+
         impl From<$wrapped> for $wrapper {
             fn from(tx: $wrapped) -> Self {
                 let is_using_predicates = tx.inputs().iter().any(|input| {
@@ -554,6 +584,10 @@ impl ScriptTransaction {
         self.tx.set_script_gas_limit(gas_limit);
         self
     }
+
+    pub fn tip(&self) -> u64 {
+        self.tx.policies().get(PolicyType::Tip).unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
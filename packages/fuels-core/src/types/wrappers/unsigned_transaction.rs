@@ -0,0 +1,97 @@
+use fuel_crypto::Signature;
+use fuel_tx::Transaction as FuelTransaction;
+use fuel_types::{
+    canonical::{Deserialize, Serialize},
+    ChainId,
+};
+
+use crate::{
+    traits::Signer,
+    types::{
+        errors::{error, Error, Result},
+        transaction::Transaction,
+    },
+};
+
+const CHAIN_ID_SIZE: usize = std::mem::size_of::<u64>();
+
+/// A fully funded transaction that is only missing its signatures. It can be built where no
+/// keys are available and moved, via [`to_bytes`](Self::to_bytes) and
+/// [`from_bytes`](Self::from_bytes), to wherever the keys are kept to be signed.
+///
+/// The signed inputs were assigned witness indexes in the order in which their owners first
+/// appear in the inputs, so the owners have to sign in that same order. Transactions spending
+/// only predicate inputs need no signatures.
+#[derive(Debug, Clone)]
+pub struct UnsignedTransaction<T> {
+    tx: T,
+    chain_id: ChainId,
+}
+
+impl<T: Transaction> UnsignedTransaction<T> {
+    pub fn new(tx: T, chain_id: ChainId) -> Self {
+        Self { tx, chain_id }
+    }
+
+    pub fn transaction(&self) -> &T {
+        &self.tx
+    }
+
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
+    /// Signs the transaction with `signer` and appends the signature as the next witness.
+    pub async fn add_signature(
+        &mut self,
+        signer: &(impl Signer + Send + Sync),
+    ) -> Result<Signature> {
+        self.tx.sign_with(signer, self.chain_id).await
+    }
+
+    /// Signs the transaction with `signer`, the only owner of its signed inputs, and returns it
+    /// ready to be sent.
+    pub async fn sign(mut self, signer: &(impl Signer + Send + Sync)) -> Result<T> {
+        self.add_signature(signer).await?;
+
+        Ok(self.tx)
+    }
+
+    /// Returns the transaction as is, e.g. once all signatures were added or if it only
+    /// spends predicate inputs.
+    pub fn into_transaction(self) -> T {
+        self.tx
+    }
+
+    /// Serializes the chain id followed by the canonical encoding of the transaction.
+    pub fn to_bytes(&self) -> Vec<u8>
+    where
+        T: Clone + Into<FuelTransaction>,
+    {
+        let tx: FuelTransaction = self.tx.clone().into();
+
+        [self.chain_id.to_be_bytes().to_vec(), tx.to_bytes()].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self>
+    where
+        T: TryFrom<FuelTransaction, Error = Error>,
+    {
+        if bytes.len() < CHAIN_ID_SIZE {
+            return Err(error!(
+                Codec,
+                "unsigned transaction is missing its chain id"
+            ));
+        }
+        let (chain_id, tx_bytes) = bytes.split_at(CHAIN_ID_SIZE);
+        let chain_id = u64::from_be_bytes(chain_id.try_into()?);
+
+        let tx = FuelTransaction::from_bytes(tx_bytes)
+            .map_err(|err| error!(Codec, "cannot decode unsigned transaction: {err:?}"))?;
+
+        Ok(Self {
+            tx: tx.try_into()?,
+            chain_id: chain_id.into(),
+        })
+    }
+}
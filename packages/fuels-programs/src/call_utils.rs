@@ -6,6 +6,7 @@ use fuel_tx::{AssetId, Bytes32, ContractId, Output, PanicReason, Receipt, TxPoin
 use fuel_types::{Address, Word};
 use fuels_accounts::Account;
 use fuels_core::{
+    codec::{ABIEncoder, EncoderConfig},
     constants::WORD_SIZE,
     error,
     offsets::call_script_data_offset,
@@ -18,6 +19,8 @@ use fuels_core::{
         transaction_builders::{
             BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
         },
+        unresolved_bytes::UnresolvedBytes,
+        Token,
     },
 };
 use itertools::{chain, Itertools};
@@ -183,6 +186,20 @@ fn compute_calls_instructions_len(calls: &[ContractCall]) -> Result<usize> {
         .process_results(|c| c.sum())
 }
 
+/// Encodes the arguments of a contract or script call once `encoder_config` is validated.
+/// An invalid config fails the call, while its notes about likely misconfigured limits are
+/// logged as warnings.
+pub(crate) fn encode_call_args(
+    args: &[Token],
+    encoder_config: EncoderConfig,
+) -> Result<UnresolvedBytes> {
+    for note in encoder_config.validate()? {
+        tracing::warn!("encoder config: {note}");
+    }
+
+    ABIEncoder::new(encoder_config).encode(args)
+}
+
 /// Compute how much of each asset is required based on all `CallParameters` of the `ContractCalls`
 pub(crate) fn calculate_required_asset_amounts(calls: &[ContractCall]) -> Vec<(AssetId, u64)> {
     let call_param_assets = calls
@@ -12,7 +12,7 @@ use fuel_tx::{
 };
 use fuels_accounts::{provider::TransactionCost, Account};
 use fuels_core::{
-    codec::{ABIEncoder, DecoderConfig, EncoderConfig, LogDecoder},
+    codec::{DecoderConfig, EncoderConfig, LogDecoder},
     constants::{BASE_ASSET_ID, DEFAULT_CALL_PARAMS_AMOUNT},
     traits::{Parameterize, Tokenizable},
     types::{
@@ -31,7 +31,7 @@ use fuels_core::{
 use crate::{
     call_response::FuelCallResponse,
     call_utils::{
-        build_tx_from_contract_calls, new_variable_outputs, sealed,
+        build_tx_from_contract_calls, encode_call_args, new_variable_outputs, sealed,
         transaction_builder_from_contract_calls, TxDependencyExtension,
     },
     receipt_parser::ReceiptParser,
@@ -470,6 +470,8 @@ pub struct ContractCallHandler<T: Account, D> {
     pub contract_call: ContractCall,
     pub tx_policies: TxPolicies,
     decoder_config: DecoderConfig,
+    // Kept so that the call can be re-encoded with a different `EncoderConfig`
+    args: Vec<Token>,
     // Initially `None`, gets set to the right tx id after the transaction is submitted
     cached_tx_id: Option<Bytes32>,
     pub account: T,
@@ -560,6 +562,14 @@ where
         self
     }
 
+    /// Re-encodes the arguments of this call only with `encoder_config`, leaving the
+    /// configuration of the contract instance untouched. An invalid `encoder_config`, see
+    /// [`EncoderConfig::validate`], makes the call fail.
+    pub fn with_encoder_config(mut self, encoder_config: EncoderConfig) -> Self {
+        self.contract_call.encoded_args = encode_call_args(&self.args, encoder_config);
+        self
+    }
+
     /// Sets the call parameters for a given contract call.
     /// Note that this is a builder method, i.e. use it as a chain:
     ///
@@ -728,7 +738,7 @@ pub fn method_hash<D: Tokenizable + Parameterize + Debug, T: Account>(
 
     let compute_custom_input_offset = should_compute_custom_input_offset(args);
 
-    let unresolved_bytes = ABIEncoder::new(encoder_config).encode(args);
+    let unresolved_bytes = encode_call_args(args, encoder_config);
     let contract_call = ContractCall {
         contract_id,
         encoded_selector,
@@ -750,6 +760,7 @@ pub fn method_hash<D: Tokenizable + Parameterize + Debug, T: Account>(
         datatype: PhantomData,
         log_decoder,
         decoder_config: Default::default(),
+        args: args.to_vec(),
     }
 }
 
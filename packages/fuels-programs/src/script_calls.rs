@@ -7,7 +7,7 @@ use fuels_accounts::{
     Account,
 };
 use fuels_core::{
-    codec::{DecoderConfig, LogDecoder},
+    codec::{DecoderConfig, EncoderConfig, LogDecoder},
     error,
     offsets::base_offset_script,
     traits::{Parameterize, Tokenizable},
@@ -21,6 +21,7 @@ use fuels_core::{
         },
         tx_status::TxStatus,
         unresolved_bytes::UnresolvedBytes,
+        Token,
     },
 };
 use itertools::chain;
@@ -28,8 +29,8 @@ use itertools::chain;
 use crate::{
     call_response::FuelCallResponse,
     call_utils::{
-        generate_contract_inputs, generate_contract_outputs, new_variable_outputs, sealed,
-        TxDependencyExtension,
+        encode_call_args, generate_contract_inputs, generate_contract_outputs,
+        new_variable_outputs, sealed, TxDependencyExtension,
     },
     contract::SettableContract,
     receipt_parser::ReceiptParser,
@@ -84,6 +85,9 @@ pub struct ScriptCallHandler<T: Account, D> {
     // Initially `None`, gets set to the right tx id after the transaction is submitted
     cached_tx_id: Option<Bytes32>,
     decoder_config: DecoderConfig,
+    // Kept so that the call can be re-encoded with a different `EncoderConfig`, `None` if the
+    // handler was created from already encoded arguments
+    args: Option<Vec<Token>>,
     pub account: T,
     pub provider: Provider,
     pub datatype: PhantomData<D>,
@@ -118,6 +122,25 @@ where
             datatype: PhantomData,
             log_decoder,
             decoder_config: DecoderConfig::default(),
+            args: None,
+        }
+    }
+
+    /// Like [`ScriptCallHandler::new`], but encodes `args` with `encoder_config` and keeps
+    /// them, so that the call can be re-encoded with [`ScriptCallHandler::with_encoder_config`].
+    pub fn new_with_args(
+        script_binary: Vec<u8>,
+        args: Vec<Token>,
+        encoder_config: EncoderConfig,
+        account: T,
+        provider: Provider,
+        log_decoder: LogDecoder,
+    ) -> Self {
+        let encoded_args = encode_call_args(&args, encoder_config);
+
+        Self {
+            args: Some(args),
+            ..Self::new(script_binary, encoded_args, account, provider, log_decoder)
         }
     }
 
@@ -139,6 +162,21 @@ where
         self
     }
 
+    /// Re-encodes the arguments of this call only with `encoder_config`, leaving the
+    /// configuration of the script instance untouched. An invalid `encoder_config`, see
+    /// [`EncoderConfig::validate`], makes the call fail, as does a handler created from
+    /// already encoded arguments with [`ScriptCallHandler::new`].
+    pub fn with_encoder_config(mut self, encoder_config: EncoderConfig) -> Self {
+        self.script_call.encoded_args = match &self.args {
+            Some(args) => encode_call_args(args, encoder_config),
+            None => Err(error!(
+                Codec,
+                "the arguments of this script call were given already encoded"
+            )),
+        };
+        self
+    }
+
     pub fn with_outputs(mut self, outputs: Vec<Output>) -> Self {
         self.script_call = self.script_call.with_outputs(outputs);
         self
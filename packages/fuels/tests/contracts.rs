@@ -726,6 +726,45 @@ async fn test_output_variable_estimation() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn minted_and_burned_coins_are_decoded_from_the_receipts() -> Result<()> {
+    use fuels::accounts::asset_change::{AssetChange, AssetChangeKind};
+
+    abigen!(Contract(
+        name = "MyContract",
+        abi = "packages/fuels/tests/contracts/token_ops/out/debug/token_ops-abi.json"
+    ));
+
+    let (wallets, _, mint_asset_id, contract_id) = setup_output_variable_estimation_test().await?;
+
+    let contract_instance = MyContract::new(contract_id.clone(), wallets[0].clone());
+    let contract_methods = contract_instance.methods();
+
+    let response = contract_methods.mint_coins(1000).call().await?;
+    assert_eq!(
+        AssetChange::from_receipts(&response.receipts),
+        vec![AssetChange {
+            kind: AssetChangeKind::Mint,
+            asset_id: mint_asset_id,
+            amount: 1000,
+            recipient: contract_id.clone(),
+        }]
+    );
+
+    let response = contract_methods.burn_coins(400).call().await?;
+    assert_eq!(
+        AssetChange::from_receipts(&response.receipts),
+        vec![AssetChange {
+            kind: AssetChangeKind::Burn,
+            asset_id: mint_asset_id,
+            amount: 400,
+            recipient: contract_id,
+        }]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_output_variable_estimation_default_attempts() -> Result<()> {
     abigen!(Contract(
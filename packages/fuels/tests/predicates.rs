@@ -4,6 +4,7 @@ use fuels::{
     core::{
         codec::{ABIEncoder, EncoderConfig},
         traits::Tokenizable,
+        ConfigurableSlots,
     },
     prelude::*,
     types::{coin::Coin, coin_type::CoinType, input::Input, message::Message, output::Output},
@@ -467,6 +468,70 @@ async fn predicate_transfer_with_signed_resources() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn owned_and_predicate_coins_can_be_spent_together() -> Result<()> {
+    abigen!(Predicate(
+        name = "MyPredicate",
+        abi =
+            "packages/fuels/tests/types/predicates/predicate_vector/out/debug/predicate_vector-abi.json"
+    ));
+
+    let predicate_data = MyPredicateEncoder::default().encode_data(2, 40, vec![2, 4, 42])?;
+    let mut predicate: Predicate = Predicate::load_from(
+        "tests/types/predicates/predicate_vector/out/debug/predicate_vector.bin",
+    )?
+    .with_data(predicate_data);
+
+    let amount = 1000;
+    let mut wallet = WalletUnlocked::new_random(None);
+    let receiver = WalletUnlocked::new_random(None);
+
+    let (mut coins, _, asset_id) =
+        get_test_coins_and_messages(predicate.address(), 1, 0, amount, 0);
+    coins.extend(get_test_coins_and_messages(wallet.address(), 1, 0, amount, 0).0);
+
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet.set_provider(provider.clone());
+    predicate.set_provider(provider.clone());
+
+    let transfer_amount = 1500;
+    // A transaction has a single change output per asset, so the change of both accounts goes
+    // to the wallet
+    let outputs = vec![
+        Output::coin(receiver.address().into(), transfer_amount, asset_id),
+        Output::change(wallet.address().into(), 0, asset_id),
+    ];
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(vec![], outputs, Default::default());
+
+    wallet
+        .add_inputs_for_amount(&mut tb, asset_id, amount)
+        .await?;
+    predicate
+        .add_inputs_for_amount(&mut tb, asset_id, amount)
+        .await?;
+    wallet.adjust_for_fee(&mut tb, transfer_amount).await?;
+
+    let owners = tb
+        .inputs()
+        .iter()
+        .filter_map(|input| input.owner())
+        .collect::<Vec<_>>();
+    assert!(owners.contains(&wallet.address()));
+    assert!(owners.contains(&predicate.address()));
+
+    let tx = tb.build(&provider).await?;
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+
+    assert_address_balance(predicate.address(), &provider, asset_id, 0).await;
+    assert_address_balance(receiver.address(), &provider, asset_id, transfer_amount).await;
+    assert!(wallet.get_asset_balance(&asset_id).await? > 0);
+
+    Ok(())
+}
+
 #[tokio::test]
 #[allow(unused_variables)]
 async fn contract_tx_and_call_params_with_predicate() -> Result<()> {
@@ -659,6 +724,45 @@ async fn predicate_configurables() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn predicate_configurables_can_be_set_from_tokens() -> Result<()> {
+    abigen!(Predicate(
+        name = "MyPredicate",
+        abi = "packages/fuels/tests/predicates/predicate_configurables/out/debug/predicate_configurables-abi.json"
+    ));
+    let code_path =
+        "tests/predicates/predicate_configurables/out/debug/predicate_configurables.bin";
+
+    let new_struct = StructWithGeneric {
+        field_1: 32u8,
+        field_2: 64,
+    };
+    let new_enum = EnumWithGeneric::VariantTwo;
+
+    let configurables = MyPredicateConfigurables::default()
+        .with_STRUCT(new_struct.clone())?
+        .with_ENUM(new_enum.clone())?;
+    let expected = Predicate::load_from(code_path)?.with_configurables(configurables);
+
+    // ANCHOR: predicate_configurable_tokens
+    let slots = ConfigurableSlots::load_from(
+        "tests/predicates/predicate_configurables/out/debug/predicate_configurables-abi.json",
+    )?;
+    let predicate = Predicate::load_from(code_path)?.with_configurable_tokens(
+        &slots,
+        [
+            ("STRUCT", new_struct.into_token()),
+            ("ENUM", new_enum.into_token()),
+        ],
+    )?;
+    // ANCHOR_END: predicate_configurable_tokens
+
+    assert_eq!(predicate.code(), expected.code());
+    assert_eq!(predicate.address(), expected.address());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn predicate_adjust_fee_persists_message_w_data() -> Result<()> {
     abigen!(Predicate(
@@ -677,7 +781,7 @@ async fn predicate_adjust_fee_persists_message_w_data() -> Result<()> {
     let message = get_test_message_w_data(predicate.address(), amount, Default::default());
     let message_input = Input::resource_predicate(
         CoinType::Message(message.clone()),
-        predicate.code().clone(),
+        predicate.code().to_vec(),
         predicate.data().clone(),
     );
 
@@ -1067,3 +1171,92 @@ async fn predicate_validation() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn spendable_resources_of_many_predicates_are_fetched_together() -> Result<()> {
+    let predicates: Vec<_> = (0..3).map(|i| Predicate::from_code(vec![i])).collect();
+
+    let amount = 100;
+    let coins = predicates
+        .iter()
+        .enumerate()
+        .flat_map(|(i, predicate)| {
+            get_test_coins_and_messages(predicate.address(), i as u64 + 1, 0, amount, 0).0
+        })
+        .collect();
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+
+    let filters = predicates
+        .iter()
+        .map(|predicate| ResourceFilter {
+            from: predicate.address().clone(),
+            amount: 1,
+            ..Default::default()
+        })
+        .collect();
+    let resources = provider.get_spendable_resources_by_owner(filters).await?;
+
+    assert_eq!(resources.len(), predicates.len());
+    for predicate in &predicates {
+        assert!(!resources[predicate.address()].is_empty());
+    }
+
+    let duplicated_filters = (0..2)
+        .map(|_| ResourceFilter {
+            from: predicates[0].address().clone(),
+            ..Default::default()
+        })
+        .collect();
+    let err = provider
+        .get_spendable_resources_by_owner(duplicated_filters)
+        .await
+        .expect_err("should fail because the owner is repeated");
+    assert!(err.to_string().contains("more than one resource filter"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_spending_predicates_is_estimated_in_one_call() -> Result<()> {
+    abigen!(Predicate(
+        name = "MyPredicate",
+        abi = "packages/fuels/tests/predicates/basic_predicate/out/debug/basic_predicate-abi.json"
+    ));
+
+    let predicate_data = MyPredicateEncoder::default().encode_data(4097, 4097)?;
+    let mut predicate: Predicate =
+        Predicate::load_from("tests/predicates/basic_predicate/out/debug/basic_predicate.bin")?
+            .with_data(predicate_data);
+
+    let (provider, _, receiver, _, asset_id) =
+        setup_predicate_test(predicate.address(), 2, 0, 100).await?;
+    predicate.set_provider(provider.clone());
+
+    let tx = predicate
+        .fund_transfer(receiver.address(), 150, asset_id, TxPolicies::default())
+        .await?
+        .build(&provider)
+        .await?;
+
+    let estimate = provider.estimate_transaction(tx).await?;
+
+    assert_eq!(
+        estimate.predicate_gas_used.len(),
+        estimate.tx.inputs().len()
+    );
+    assert!(estimate
+        .predicate_gas_used
+        .iter()
+        .all(|gas| matches!(gas, Some(gas) if *gas > 0)));
+    assert_eq!(
+        estimate.metered_bytes_size,
+        estimate.tx.metered_bytes_size() as u64
+    );
+
+    provider
+        .send_transaction_and_await_commit(estimate.tx)
+        .await?
+        .check(None)?;
+
+    Ok(())
+}
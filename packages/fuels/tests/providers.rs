@@ -6,17 +6,19 @@ use fuels::{
     client::{PageDirection, PaginationRequest},
     crypto::SecretKey,
     prelude::*,
-    tx::Receipt,
+    test_helpers::ChainConfig,
+    tx::{ConsensusParameters, Receipt, TxId, UtxoId},
     types::{
         block::Block,
         coin_type::CoinType,
-        errors::transaction::Reason,
+        errors::{request::Failure, transaction::Reason},
         message::Message,
         transaction_builders::{BuildableTransaction, ScriptTransactionBuilder},
         tx_status::TxStatus,
-        Bits256,
+        Bits256, Bytes32, ChainId,
     },
 };
+use futures::StreamExt;
 
 #[tokio::test]
 async fn test_provider_launch_and_connect() -> Result<()> {
@@ -64,6 +66,331 @@ async fn test_provider_launch_and_connect() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_contract_bytecode_and_root() -> Result<()> {
+    let wallet = launch_provider_and_get_wallet().await?;
+    let provider = wallet.try_provider()?;
+
+    let binary_path = "tests/contracts/contract_test/out/debug/contract_test.bin";
+    let contract = Contract::load_from(binary_path, LoadConfiguration::default())?;
+    let code_root = contract.code_root();
+    let contract_id = contract.deploy(&wallet, TxPolicies::default()).await?;
+
+    let bytecode = provider
+        .contract_bytecode(&contract_id)
+        .await?
+        .expect("contract should be deployed");
+    assert_eq!(bytecode, std::fs::read(binary_path)?);
+    assert_eq!(provider.contract_root(&contract_id).await?, Some(code_root));
+
+    let unknown_id = Bech32ContractId::from(ContractId::zeroed());
+    assert!(provider.contract_bytecode(&unknown_id).await?.is_none());
+    assert!(provider.contract_root(&unknown_id).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn contract_deployment_finds_the_create_transaction() -> Result<()> {
+    let wallet = launch_provider_and_get_wallet().await?;
+    let provider = wallet.try_provider()?;
+
+    let contract_id = Contract::load_from(
+        "tests/contracts/contract_test/out/debug/contract_test.bin",
+        LoadConfiguration::default(),
+    )?
+    .deploy(&wallet, TxPolicies::default())
+    .await?;
+
+    let (tx_id, header) = provider
+        .contract_deployment(&contract_id, 100)
+        .await?
+        .expect("contract should be deployed");
+
+    let response = provider.get_transaction_by_id(&tx_id).await?.unwrap();
+    assert!(matches!(response.transaction, TransactionType::Create(_)));
+    let block = provider.block(&header.id).await?.unwrap();
+    assert!(block.transactions.contains(&tx_id));
+
+    let unknown_id = Bech32ContractId::from(ContractId::zeroed());
+    assert!(provider
+        .contract_deployment(&unknown_id, 100)
+        .await?
+        .is_none());
+
+    let err = provider
+        .contract_deployment(&contract_id, 0)
+        .await
+        .expect_err("no transaction is scanned");
+    assert!(err
+        .to_string()
+        .contains("not found within the first `0` transactions"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn current_gas_price_is_cached_and_pushed() -> Result<()> {
+    let provider = setup_test_provider(vec![], vec![], None, None)
+        .await?
+        .with_gas_price_cache_ttl(std::time::Duration::from_secs(60));
+
+    let latest = provider.latest_gas_price().await?.gas_price;
+    assert_eq!(provider.current_gas_price().await?, latest);
+    assert_eq!(provider.current_gas_price().await?, latest);
+
+    let updates = provider.gas_price_updates(std::time::Duration::from_millis(100));
+    futures::pin_mut!(updates);
+    assert_eq!(updates.next().await.expect("stream never ends")?, latest);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bootstrap_refreshes_provider_state() -> Result<()> {
+    let mut provider = setup_test_provider(vec![], vec![], None, None).await?;
+
+    let state = provider.bootstrap().await?;
+
+    assert_eq!(state.chain_id(), provider.chain_id());
+    assert_eq!(state.base_asset_id(), BASE_ASSET_ID);
+    assert_eq!(
+        state.node_info.node_version,
+        provider.node_info().await?.node_version
+    );
+    assert_eq!(
+        provider.state().consensus_parameters,
+        *provider.consensus_parameters()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_raw_graphql_query() -> Result<()> {
+    let provider = setup_test_provider(vec![], vec![], None, None).await?;
+
+    let data = provider
+        .query_raw("query { nodeInfo { nodeVersion } }", serde_json::json!({}))
+        .await?;
+
+    assert_eq!(
+        data["nodeInfo"]["nodeVersion"],
+        provider.node_info().await?.node_version
+    );
+
+    let err = provider
+        .query_raw("query { notAField }", serde_json::json!({}))
+        .await
+        .expect_err("should fail because the field doesn't exist");
+    assert!(matches!(err, Error::Request(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_commit_can_be_awaited_by_polling() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 1, 1000);
+    let provider = setup_test_provider(coins, vec![], None, None)
+        .await?
+        .with_polling_strategy(PollingStrategy {
+            initial_delay: std::time::Duration::from_millis(10),
+            timeout: std::time::Duration::from_secs(10),
+            ..Default::default()
+        });
+    wallet.set_provider(provider.clone());
+
+    let recipient = WalletUnlocked::new_random(None);
+    let tx = wallet
+        .fund_transfer(
+            recipient.address(),
+            100,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?
+        .build(&provider)
+        .await?;
+    let tx_id = provider.send_transaction(tx).await?;
+
+    let status = provider.poll_transaction_commit(tx_id).await?;
+
+    assert!(matches!(status, TxStatus::Success { .. }));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_consensus_parameters_change_detection() -> Result<()> {
+    let mut provider = setup_test_provider(vec![], vec![], None, None).await?;
+    let hash = provider.consensus_parameters_hash();
+
+    assert!(!provider.consensus_parameters_changed().await?);
+
+    provider.bootstrap().await?;
+    assert_eq!(provider.consensus_parameters_hash(), hash);
+
+    let other_chain_config = ChainConfig {
+        consensus_parameters: ConsensusParameters {
+            chain_id: ChainId::new(1),
+            ..Default::default()
+        },
+        ..ChainConfig::local_testnet()
+    };
+    let other_provider =
+        setup_test_provider(vec![], vec![], None, Some(other_chain_config)).await?;
+    assert_ne!(other_provider.consensus_parameters_hash(), hash);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fee_history() -> Result<()> {
+    let wallet = launch_provider_and_get_wallet().await?;
+    let provider = wallet.try_provider()?;
+
+    let tip = 5;
+    let (tx_id, _) = wallet
+        .transfer(
+            &Bech32Address::default(),
+            100,
+            BASE_ASSET_ID,
+            TxPolicies::default().with_tip(tip),
+        )
+        .await?;
+    provider.produce_blocks(2, None).await?;
+
+    let history = provider.fee_history(3).await?;
+
+    assert_eq!(history.len(), 3);
+    assert!(history
+        .windows(2)
+        .all(|pair| pair[0].height == pair[1].height + 1));
+
+    let transfer_block = history
+        .iter()
+        .find(|info| !info.tips.is_empty())
+        .unwrap_or_else(|| panic!("transfer `{tx_id}` should be in the history"));
+    assert_eq!(transfer_block.max_tip(), Some(tip));
+    assert!(transfer_block.gas_used > 0);
+    assert_eq!(
+        transfer_block.utilization(transfer_block.gas_used * 4),
+        0.25
+    );
+    assert!(history
+        .iter()
+        .filter(|info| info.tips.is_empty())
+        .all(|info| info.gas_used == 0));
+
+    let err = provider
+        .fee_history(u32::MAX)
+        .await
+        .expect_err("block count doesn't fit a page request");
+    assert!(err.to_string().contains("the maximum is `2147483647`"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn multi_node_provider_fails_over_to_a_healthy_node() -> Result<()> {
+    let preferred = FuelService::start(Config::default()).await?;
+    let backup = FuelService::start(Config::default()).await?;
+    let urls = [preferred.bound_address(), backup.bound_address()].map(|addr| addr.to_string());
+
+    let policy = FailoverPolicy {
+        max_failures: 1,
+        recheck_after: std::time::Duration::from_secs(60),
+    };
+    let provider = Provider::connect_multi(&urls, policy).await?;
+    assert_eq!(provider.url(), urls[0]);
+
+    // Pinned providers don't fail over
+    let pinned = provider.pinned();
+    preferred.stop().await.unwrap();
+    assert!(matches!(
+        pinned.latest_block_height().await,
+        Err(Error::Request(Failure::Connection(_)))
+    ));
+
+    provider.latest_block_height().await?;
+    assert_eq!(provider.url(), urls[1]);
+    assert_eq!(
+        provider.node_health(),
+        [(urls[0].as_str(), false), (urls[1].as_str(), true)]
+    );
+
+    let err = Provider::connect_multi(&[], FailoverPolicy::default())
+        .await
+        .expect_err("should fail without any node");
+    assert!(err
+        .to_string()
+        .contains("at least one node url is required"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn consensus_parameters_change_is_detected_after_failing_over() -> Result<()> {
+    let preferred = FuelService::start(Config::default()).await?;
+    let backup = FuelService::start(Config {
+        chain_conf: ChainConfig {
+            consensus_parameters: ConsensusParameters {
+                chain_id: ChainId::new(1),
+                ..Default::default()
+            },
+            ..ChainConfig::local_testnet()
+        },
+        ..Config::default()
+    })
+    .await?;
+    let urls = [preferred.bound_address(), backup.bound_address()].map(|addr| addr.to_string());
+
+    let mut provider = Provider::connect_multi(&urls, FailoverPolicy::default())
+        .await?
+        .with_fee_cache()
+        .with_gas_price_cache_ttl(std::time::Duration::from_secs(60));
+    provider.current_gas_price().await?;
+    assert!(!provider.consensus_parameters_changed().await?);
+
+    preferred.stop().await.unwrap();
+
+    // Drops the gas price cached from the preferred node, so it is fetched from the backup
+    assert!(provider.consensus_parameters_changed().await?);
+    assert_eq!(provider.chain_id(), ChainId::new(1));
+    assert!(!provider.consensus_parameters_changed().await?);
+    assert_eq!(
+        provider.current_gas_price().await?,
+        provider.latest_gas_price().await?.gas_price
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn multi_node_provider_fails_over_on_the_first_failure_with_the_default_policy() -> Result<()>
+{
+    let preferred = FuelService::start(Config::default()).await?;
+    let backup = FuelService::start(Config::default()).await?;
+    let urls = [preferred.bound_address(), backup.bound_address()].map(|addr| addr.to_string());
+
+    let provider = Provider::connect_multi(&urls, FailoverPolicy::default()).await?;
+    preferred.stop().await.unwrap();
+
+    // The preferred node is only marked unhealthy after `max_failures` failed requests, but
+    // each of them still reaches the backup
+    for _ in 0..FailoverPolicy::default().max_failures {
+        provider.latest_block_height().await?;
+    }
+    assert_eq!(
+        provider.node_health(),
+        [(urls[0].as_str(), false), (urls[1].as_str(), true)]
+    );
+    assert_eq!(provider.url(), urls[1]);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_network_error() -> Result<()> {
     abigen!(Contract(
@@ -89,7 +416,10 @@ async fn test_network_error() -> Result<()> {
     .deploy(&wallet, TxPolicies::default())
     .await;
 
-    assert!(matches!(response, Err(Error::Provider(_))));
+    assert!(matches!(
+        response,
+        Err(Error::Request(Failure::Connection(_)))
+    ));
     Ok(())
 }
 
@@ -211,6 +541,19 @@ async fn can_increase_block_height() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn latest_finalized_block_follows_produced_blocks() -> Result<()> {
+    let provider = setup_test_provider(vec![], vec![], None, None).await?;
+
+    provider.produce_blocks(2, None).await?;
+
+    let header = provider.latest_finalized_block().await?;
+    assert_eq!(header.height, provider.latest_block_height().await?);
+    assert_eq!(header.height, 2);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn can_set_custom_block_time() -> Result<()> {
     // ANCHOR: use_produce_blocks_custom_time
@@ -499,7 +842,7 @@ async fn test_gas_errors() -> Result<()> {
         .await
         .expect_err("should error");
 
-    let expected = "provider: Response errors; Validity(InsufficientFeeAmount";
+    let expected = "request: Response errors; Validity(InsufficientFeeAmount";
     assert!(response.to_string().contains(expected));
 
     Ok(())
@@ -664,6 +1007,127 @@ async fn test_parse_block_time() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_block_with_transactions() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), AssetId::BASE, 1, DEFAULT_COIN_AMOUNT);
+    let provider = setup_test_provider(coins.clone(), vec![], None, None).await?;
+    wallet.set_provider(provider);
+    let tx_policies = TxPolicies::default().with_script_gas_limit(2000);
+
+    let wallet_2 = WalletUnlocked::new_random(None).lock();
+    let (tx_id, _) = wallet
+        .transfer(wallet_2.address(), 100, BASE_ASSET_ID, tx_policies)
+        .await?;
+
+    let provider = wallet.try_provider()?;
+    let block_height = provider
+        .get_transaction_by_id(&tx_id)
+        .await?
+        .unwrap()
+        .block_height
+        .unwrap();
+
+    let block = provider
+        .block_by_height_with_transactions(block_height)
+        .await?
+        .unwrap();
+    assert_eq!(block.header.height, *block_height);
+    assert_eq!(
+        block.transactions.len() as u64,
+        block.header.transactions_count
+    );
+    assert!(block
+        .transactions
+        .iter()
+        .all(|tx| tx.block_height == Some(block_height)));
+
+    let same_block = provider.block_with_transactions(&block.id).await?.unwrap();
+    assert_eq!(same_block.transactions.len(), block.transactions.len());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_block_returns_the_including_block_header() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), AssetId::BASE, 1, DEFAULT_COIN_AMOUNT);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet.set_provider(provider.clone());
+
+    let receiver = WalletUnlocked::new_random(None).lock();
+    let (tx_id, _) = wallet
+        .transfer(
+            receiver.address(),
+            100,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?;
+
+    let header = provider.transaction_block(&tx_id).await?.unwrap();
+
+    let block_height = provider
+        .get_transaction_by_id(&tx_id)
+        .await?
+        .unwrap()
+        .block_height
+        .unwrap();
+    assert_eq!(header.height, *block_height);
+    let block = provider.block(&header.id).await?.unwrap();
+    assert!(block.transactions.contains(&tx_id));
+
+    assert!(provider.transaction_block(&TxId::zeroed()).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_history_lists_the_transactions_of_the_account() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), AssetId::BASE, 2, DEFAULT_COIN_AMOUNT);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet.set_provider(provider.clone());
+
+    let mut receiver = WalletUnlocked::new_random(None);
+    receiver.set_provider(provider.clone());
+
+    let mut tx_ids = vec![];
+    for _ in 0..2 {
+        let (tx_id, _) = wallet
+            .transfer(
+                receiver.address(),
+                100,
+                BASE_ASSET_ID,
+                TxPolicies::default(),
+            )
+            .await?;
+        tx_ids.push(tx_id);
+    }
+
+    let request = PaginationRequest {
+        cursor: None,
+        results: 10,
+        direction: PageDirection::Forward,
+    };
+    let history = wallet.transaction_history(request.clone()).await?;
+    assert!(!history.has_next_page);
+    assert_eq!(history.results, tx_ids);
+
+    let received = receiver.transaction_history(request.clone()).await?.results;
+    assert_eq!(received, tx_ids);
+
+    let chain_id = provider.chain_id();
+    let transactions = wallet.get_transactions(request).await?.results;
+    let ids: Vec<_> = transactions
+        .iter()
+        .map(|response| response.transaction.id(chain_id))
+        .collect();
+    assert_eq!(ids, tx_ids);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_spendable_with_exclusion() -> Result<()> {
     let coin_amount_1 = 1000;
@@ -721,6 +1185,95 @@ async fn test_get_spendable_with_exclusion() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn coins_can_be_fetched_by_utxo_id() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 1, 1000);
+    let utxo_id = coins[0].utxo_id;
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet.set_provider(provider.clone());
+
+    let coin = provider.coin(&utxo_id).await?.expect("coin should exist");
+    assert_eq!(coin.amount, 1000);
+    assert_eq!(&coin.owner, wallet.address());
+
+    let recipient = WalletUnlocked::new_random(None);
+    wallet
+        .transfer(
+            recipient.address(),
+            100,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?;
+
+    let unknown_utxo_id = UtxoId::new(Bytes32::zeroed(), 0);
+    let fetched = provider.coins(&[utxo_id, unknown_utxo_id]).await?;
+    assert_eq!(fetched, vec![None, None]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_spendable_with_amount_bounds() -> Result<()> {
+    let dust_amount = 10;
+    let regular_amount = 500;
+    let large_amount = 100_000;
+
+    let wallet = WalletUnlocked::new_random(None);
+    let address = wallet.address();
+
+    let coins = [dust_amount, regular_amount, large_amount]
+        .into_iter()
+        .flat_map(|amount| setup_single_asset_coins(address, BASE_ASSET_ID, 1, amount))
+        .collect::<Vec<_>>();
+    let message = given_a_message(address.clone(), large_amount);
+
+    let provider = setup_test_provider(coins, vec![message], None, None).await?;
+
+    {
+        let filter = ResourceFilter {
+            from: address.clone(),
+            amount: 1,
+            max_amount: Some(dust_amount),
+            ..Default::default()
+        };
+        let resources = provider.get_spendable_resources(filter).await?;
+
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].amount(), dust_amount);
+    }
+    {
+        let filter = ResourceFilter {
+            from: address.clone(),
+            amount: regular_amount,
+            min_amount: Some(dust_amount + 1),
+            max_amount: Some(large_amount - 1),
+            ..Default::default()
+        };
+        let resources = provider.get_spendable_resources(filter).await?;
+
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].amount(), regular_amount);
+    }
+    {
+        let filter = ResourceFilter {
+            from: address.clone(),
+            amount: regular_amount + 1,
+            min_amount: Some(dust_amount + 1),
+            max_amount: Some(large_amount - 1),
+            ..Default::default()
+        };
+
+        provider
+            .get_spendable_resources(filter)
+            .await
+            .expect_err("not enough resources within the bounds");
+    }
+
+    Ok(())
+}
+
 fn given_a_message(address: Bech32Address, message_amount: u64) -> Message {
     setup_single_message(
         &Bech32Address::default(),
@@ -1012,3 +1565,45 @@ async fn can_produce_blocks_with_trig_never() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn submit_and_await_commit_fails_once_the_deadline_passes() -> Result<()> {
+    let config = Config {
+        block_production: Trigger::Never,
+        ..Config::default()
+    };
+    let wallets =
+        launch_custom_provider_and_get_wallets(WalletsConfig::default(), Some(config), None)
+            .await?;
+    let wallet = &wallets[0];
+    let provider = wallet.try_provider()?;
+
+    let tx = wallet
+        .fund_transfer(
+            &Bech32Address::default(),
+            100,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?
+        .build(provider)
+        .await?;
+    let expected_tx_id = tx.id(provider.chain_id());
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(500);
+    let err = provider
+        .submit_and_await_commit_with_deadline(tx, deadline)
+        .await
+        .expect_err("should time out since no block is produced");
+
+    let Error::Transaction(Reason::Timeout { tx_id }) = err else {
+        panic!("expected a timeout, got: {err}");
+    };
+    assert_eq!(tx_id, expected_tx_id);
+
+    provider.produce_blocks(1, None).await?;
+    let status = provider.tx_status(&tx_id).await?;
+    assert!(matches!(status, TxStatus::Success { .. }));
+
+    Ok(())
+}
@@ -437,4 +437,25 @@ async fn script_encoder_config_is_applied() {
             "cannot encode script call arguments: codec: token limit `1` reached while encoding"
         ));
     }
+    {
+        let encoding_error = script_instance_without_encoder_config
+            .main(1, 2)
+            .with_encoder_config(EncoderConfig {
+                max_tokens: 1,
+                ..Default::default()
+            })
+            .call()
+            .await
+            .expect_err("should error");
+
+        assert!(encoding_error.to_string().contains(
+            "cannot encode script call arguments: codec: token limit `1` reached while encoding"
+        ));
+
+        let _encoding_ok = script_instance_without_encoder_config
+            .main(1, 2)
+            .call()
+            .await
+            .expect("the instance keeps the default encoder config");
+    }
 }
@@ -1,7 +1,19 @@
 use std::iter::repeat;
 
 use fuel_tx::{input::coin::CoinSigned, Bytes32, Input, Output, TxPointer, UtxoId};
-use fuels::prelude::*;
+use fuels::{
+    accounts::{
+        precondition::StatePrecondition,
+        template::{TemplateAmount, TransactionTemplate},
+    },
+    prelude::*,
+    test_helpers::ChainConfig,
+    tx::{ConsensusParameters, TxParameters},
+    types::{
+        errors::transaction::Reason, tx_status::TxStatus,
+        unsigned_transaction::UnsignedTransaction, ChainId, Nonce,
+    },
+};
 
 #[tokio::test]
 async fn test_wallet_balance_api_multi_asset() -> Result<()> {
@@ -318,6 +330,74 @@ async fn transfer_coins_with_change() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn cannot_bump_fee_of_included_transaction() -> Result<()> {
+    const AMOUNT: u64 = 5;
+    let (wallet_1, wallet_2) = setup_transfer_test(AMOUNT).await?;
+
+    let (tx_id, _) = wallet_1
+        .transfer(wallet_2.address(), 2, BASE_ASSET_ID, TxPolicies::default())
+        .await?;
+
+    let err = wallet_1
+        .bump_fee(&tx_id, 1)
+        .await
+        .expect_err("should fail since the transaction was already included");
+
+    assert!(err
+        .to_string()
+        .contains("is already included in a block and its inputs are spent"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn bumped_transaction_replaces_the_pending_one() -> Result<()> {
+    let config = Config {
+        block_production: Trigger::Never,
+        ..Config::default()
+    };
+    let wallets =
+        launch_custom_provider_and_get_wallets(WalletsConfig::default(), Some(config), None)
+            .await?;
+    let wallet = &wallets[0];
+    let provider = wallet.try_provider()?;
+
+    let tx = wallet
+        .fund_transfer(
+            &Bech32Address::default(),
+            100,
+            BASE_ASSET_ID,
+            TxPolicies::default().with_tip(1),
+        )
+        .await?
+        .build(provider)
+        .await?;
+    let tx_id = provider.send_transaction(tx).await?;
+
+    const NEW_TIP: u64 = 10;
+    let replacement_id = wallet.bump_fee(&tx_id, NEW_TIP).await?;
+    provider.produce_blocks(1, None).await?;
+
+    let status = provider.tx_status(&replacement_id).await?;
+    assert!(matches!(status, TxStatus::Success { .. }));
+
+    let response = provider
+        .get_transaction_by_id(&replacement_id)
+        .await?
+        .expect("replacement should be known to the node");
+    let TransactionType::Script(tx) = response.transaction else {
+        panic!("expected a script transaction");
+    };
+    assert_eq!(tx.tip(), NEW_TIP);
+
+    // The node drops the original, so it may not even be known anymore
+    let status = provider.tx_status(&tx_id).await;
+    assert!(!matches!(status, Ok(TxStatus::Success { .. })));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_wallet_get_coins() -> Result<()> {
     const AMOUNT: u64 = 1000;
@@ -337,6 +417,22 @@ async fn test_wallet_get_coins() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn utxo_count_spans_several_pages() -> Result<()> {
+    // More than a page of coins
+    const NUM_COINS: u64 = 150;
+    let mut wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, NUM_COINS, 10);
+
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet.set_provider(provider);
+
+    assert_eq!(wallet.utxo_count(BASE_ASSET_ID).await?, NUM_COINS);
+    assert_eq!(wallet.utxo_count(AssetId::from([1; 32])).await?, 0);
+
+    Ok(())
+}
+
 async fn setup_transfer_test(amount: u64) -> Result<(WalletUnlocked, WalletUnlocked)> {
     let mut wallet_1 = WalletUnlocked::new_random(None);
     let mut wallet_2 = WalletUnlocked::new_random(None);
@@ -372,6 +468,75 @@ async fn transfer_more_than_owned() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn transfer_with_data_attaches_it_to_the_transaction() -> Result<()> {
+    const AMOUNT: u64 = 1000000;
+    let (wallet_1, wallet_2) = setup_transfer_test(AMOUNT).await?;
+    let provider = wallet_1.try_provider()?;
+
+    let invoice_ref = b"invoice-42".to_vec();
+    let (tx_id, _receipts) = wallet_1
+        .transfer_with_data(
+            wallet_2.address(),
+            1,
+            BASE_ASSET_ID,
+            invoice_ref.clone(),
+            TxPolicies::default(),
+        )
+        .await?;
+
+    let response = provider.get_transaction_by_id(&tx_id).await?.unwrap();
+    let TransactionType::Script(tx) = response.transaction else {
+        panic!("Received unexpected tx type!");
+    };
+    assert_eq!(*tx.script_data(), invoice_ref);
+
+    let max_len = provider
+        .consensus_parameters()
+        .script_params
+        .max_script_data_length;
+    let err = wallet_1
+        .transfer_with_data(
+            wallet_2.address(),
+            1,
+            BASE_ASSET_ID,
+            vec![0; max_len as usize + 1],
+            TxPolicies::default(),
+        )
+        .await
+        .expect_err("should fail since the data is too long");
+    assert!(err.to_string().contains("but `max_script_data_length` is"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn withdraw_to_base_layer_with_data_sends_it_in_the_message() -> Result<()> {
+    const AMOUNT: u64 = 1000000;
+    let (wallet, _) = setup_transfer_test(AMOUNT).await?;
+    let provider = wallet.try_provider()?;
+
+    let data = b"invoice-42".to_vec();
+    let (tx_id, nonce, _receipts) = wallet
+        .withdraw_to_base_layer_with_data(
+            wallet.address(),
+            1000,
+            data.clone(),
+            TxPolicies::default(),
+        )
+        .await?;
+
+    provider.produce_blocks(1, None).await?;
+
+    let proof = provider
+        .get_message_proof(&tx_id, &nonce, None, Some(2))
+        .await?
+        .expect("failed to retrieve message proof");
+    assert_eq!(proof.data, data);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn transfer_coins_of_non_base_asset() -> Result<()> {
     const AMOUNT: u64 = 10000;
@@ -410,6 +575,545 @@ async fn transfer_coins_of_non_base_asset() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn fund_transfer_covers_fee_with_base_asset() -> Result<()> {
+    const AMOUNT: u64 = 10000;
+    let mut wallet_1 = WalletUnlocked::new_random(None);
+    let wallet_2 = WalletUnlocked::new_random(None);
+
+    let asset_id: AssetId = AssetId::from([1; 32usize]);
+    let mut coins = setup_single_asset_coins(wallet_1.address(), asset_id, 1, AMOUNT);
+    coins.extend(setup_single_asset_coins(
+        wallet_1.address(),
+        BASE_ASSET_ID,
+        1,
+        AMOUNT,
+    ));
+
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet_1.set_provider(provider.clone());
+
+    const SEND_AMOUNT: u64 = 200;
+    let tb = wallet_1
+        .fund_transfer(
+            wallet_2.address(),
+            SEND_AMOUNT,
+            asset_id,
+            TxPolicies::default(),
+        )
+        .await?;
+
+    let input_assets = tb
+        .inputs()
+        .iter()
+        .filter_map(|input| input.asset_id())
+        .collect::<Vec<_>>();
+    assert!(input_assets.contains(&asset_id));
+    assert!(input_assets.contains(&BASE_ASSET_ID));
+
+    let tx = tb.build(&provider).await?;
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+
+    assert_eq!(
+        wallet_1.get_asset_balance(&asset_id).await?,
+        AMOUNT - SEND_AMOUNT
+    );
+    assert_eq!(
+        provider
+            .get_asset_balance(wallet_2.address(), asset_id)
+            .await?,
+        SEND_AMOUNT
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transfer_fees_can_be_paid_by_another_account() -> Result<()> {
+    const AMOUNT: u64 = 10000;
+    const SEND_AMOUNT: u64 = 200;
+    let mut sender = WalletUnlocked::new_random(None);
+    let mut fee_payer = WalletUnlocked::new_random(None);
+    let recipient = WalletUnlocked::new_random(None);
+
+    let asset_id: AssetId = AssetId::from([1; 32usize]);
+    let mut coins = setup_single_asset_coins(sender.address(), asset_id, 1, AMOUNT);
+    coins.extend(setup_single_asset_coins(
+        sender.address(),
+        BASE_ASSET_ID,
+        1,
+        AMOUNT,
+    ));
+    coins.extend(setup_single_asset_coins(
+        fee_payer.address(),
+        BASE_ASSET_ID,
+        2,
+        AMOUNT,
+    ));
+
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    sender.set_provider(provider.clone());
+    fee_payer.set_provider(provider.clone());
+
+    // The local node charges no gas, so the fee is exactly the tip
+    const TIP: u64 = 100;
+    let mut fee_payer_balance = 2 * AMOUNT;
+    for asset_id in [asset_id, BASE_ASSET_ID] {
+        sender
+            .transfer_with_fee_payer(
+                recipient.address(),
+                SEND_AMOUNT,
+                asset_id,
+                &fee_payer,
+                TxPolicies::default().with_tip(TIP),
+            )
+            .await?;
+
+        assert_eq!(
+            sender.get_asset_balance(&asset_id).await?,
+            AMOUNT - SEND_AMOUNT
+        );
+        assert_eq!(
+            provider
+                .get_asset_balance(recipient.address(), asset_id)
+                .await?,
+            SEND_AMOUNT
+        );
+
+        fee_payer_balance -= TIP;
+        assert_eq!(
+            fee_payer.get_asset_balance(&BASE_ASSET_ID).await?,
+            fee_payer_balance
+        );
+
+        if asset_id != BASE_ASSET_ID {
+            assert_eq!(sender.get_asset_balance(&BASE_ASSET_ID).await?, AMOUNT);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fragmented_coins_are_consolidated_automatically() -> Result<()> {
+    const NUM_COINS: usize = 20;
+    let wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, NUM_COINS as u64, 100);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+
+    let mut wallet = wallet.auto_consolidate_when_inputs_exceed(2);
+    wallet.set_provider(provider.clone());
+
+    let recipient = WalletUnlocked::new_random(None);
+    let tb = wallet
+        .fund_transfer(
+            recipient.address(),
+            500,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?;
+
+    // The coins that weren't selected are merged into the same transaction
+    assert_eq!(tb.inputs().len(), NUM_COINS);
+
+    let tx = tb.build(&provider).await?;
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+
+    assert_eq!(wallet.get_coins(BASE_ASSET_ID).await?.len(), 1);
+    assert_eq!(
+        provider
+            .get_asset_balance(recipient.address(), BASE_ASSET_ID)
+            .await?,
+        500
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn consolidation_is_skipped_once_the_input_limit_is_reached() -> Result<()> {
+    const NUM_COINS: u64 = 20;
+    const MAX_INPUTS: u16 = 8;
+    let wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, NUM_COINS, 100);
+    let chain_config = ChainConfig {
+        consensus_parameters: ConsensusParameters {
+            tx_params: TxParameters::default().with_max_inputs(MAX_INPUTS),
+            ..Default::default()
+        },
+        ..ChainConfig::local_testnet()
+    };
+    let provider = setup_test_provider(coins, vec![], None, Some(chain_config)).await?;
+
+    let mut wallet = wallet.auto_consolidate_when_inputs_exceed(2);
+    wallet.set_provider(provider.clone());
+
+    // Needs at least 5 coins, more than half of the input limit, so no coin can be merged
+    let recipient = WalletUnlocked::new_random(None);
+    let tb = wallet
+        .fund_transfer(
+            recipient.address(),
+            450,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?;
+    let selected = tb.inputs().len();
+    assert!(selected <= usize::from(MAX_INPUTS));
+
+    let tx = tb.build(&provider).await?;
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+
+    // The transfer went through without merging, leaving the other coins and the change
+    assert_eq!(
+        wallet.get_coins(BASE_ASSET_ID).await?.len() as u64,
+        NUM_COINS - selected as u64 + 1
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn spent_inputs_are_replaced_before_sending() -> Result<()> {
+    let wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 4, 100);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+
+    let mut wallet = wallet.revalidate_inputs_before_sending(true);
+    wallet.set_provider(provider.clone());
+
+    let recipient = WalletUnlocked::new_random(None);
+    let tb = wallet
+        .fund_transfer(
+            recipient.address(),
+            50,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?;
+    let selected: Vec<_> = tb
+        .inputs()
+        .iter()
+        .filter_map(|input| input.resource().map(|resource| resource.id()))
+        .collect();
+
+    // Another process spends the selected coins in the meantime
+    let tx = tb.clone().build(&provider).await?;
+    provider.send_transaction_and_await_commit(tx).await?;
+
+    let mut replaced_tb = tb.clone();
+    let replaced = wallet.replace_spent_inputs(&mut replaced_tb).await?;
+
+    assert_eq!(replaced, selected.len());
+    assert!(replaced_tb
+        .inputs()
+        .iter()
+        .filter_map(|input| input.resource())
+        .all(|resource| !selected.contains(&resource.id())));
+
+    // The builder with the spent inputs is revalidated right before being sent
+    wallet.send_funded(tb, 50).await?;
+
+    assert_eq!(
+        provider
+            .get_asset_balance(recipient.address(), BASE_ASSET_ID)
+            .await?,
+        100
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dust_change_is_absorbed_into_the_fee() -> Result<()> {
+    const COIN_AMOUNT: u64 = 100_000;
+    const DUST: u64 = 10;
+
+    let wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 1, COIN_AMOUNT);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+
+    let mut wallet = wallet.suppress_change_below(100);
+    wallet.set_provider(provider.clone());
+    let recipient = WalletUnlocked::new_random(None);
+
+    let fee = wallet
+        .fund_transfer(recipient.address(), 1, BASE_ASSET_ID, TxPolicies::default())
+        .await?
+        .fee_checked_from_tx(&provider)
+        .await?
+        .expect("fee should be computable")
+        .max_fee();
+    let amount = COIN_AMOUNT - fee - DUST;
+
+    let tb = wallet
+        .fund_transfer(
+            recipient.address(),
+            amount,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?;
+
+    assert!(!tb
+        .outputs()
+        .iter()
+        .any(|output| matches!(output, Output::Change { .. })));
+    assert!(tb.tx_policies().tip().unwrap() >= DUST);
+
+    let tx = tb.build(&provider).await?;
+    provider.send_transaction_and_await_commit(tx).await?;
+
+    assert_eq!(
+        provider
+            .get_asset_balance(recipient.address(), BASE_ASSET_ID)
+            .await?,
+        amount
+    );
+    assert!(wallet.get_coins(BASE_ASSET_ID).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dust_change_of_another_address_is_kept() -> Result<()> {
+    let wallet = WalletUnlocked::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 1, 100_000);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+
+    let mut wallet = wallet.suppress_change_below(u64::MAX);
+    wallet.set_provider(provider);
+    let recipient = WalletUnlocked::new_random(None);
+
+    let outputs = vec![Output::change(recipient.address().into(), 0, BASE_ASSET_ID)];
+    let mut tb =
+        ScriptTransactionBuilder::prepare_transfer(vec![], outputs.clone(), TxPolicies::default());
+    wallet.adjust_for_fee(&mut tb, 0).await?;
+
+    assert_eq!(tb.outputs(), &outputs);
+    assert_eq!(tb.tx_policies().tip(), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn send_if_aborts_when_the_precondition_fails() -> Result<()> {
+    let (wallet, recipient) = setup_transfer_test(100).await?;
+    let provider = wallet.try_provider()?.clone();
+    let contract_id = Bech32ContractId::new("fuel", [1; 32]);
+
+    let tx = wallet
+        .fund_transfer(
+            recipient.address(),
+            10,
+            BASE_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await?
+        .build(&provider)
+        .await?;
+
+    let stale = StatePrecondition::contract_balance(contract_id.clone(), BASE_ASSET_ID, 1);
+    let err = wallet
+        .send_if(tx.clone(), stale)
+        .await
+        .expect_err("should fail");
+    assert!(matches!(
+        err,
+        Error::Transaction(Reason::PreconditionFailed(_))
+    ));
+    assert_eq!(recipient.get_asset_balance(&BASE_ASSET_ID).await?, 0);
+
+    let current = StatePrecondition::contract_balance(contract_id, BASE_ASSET_ID, 0);
+    wallet.send_if(tx, current).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_fee_resources_keeps_the_callers_outputs() -> Result<()> {
+    let (wallet, recipient) = setup_transfer_test(100).await?;
+    let provider = wallet.try_provider()?.clone();
+
+    let outputs = vec![
+        Output::coin(recipient.address().into(), 10, BASE_ASSET_ID),
+        Output::variable(Default::default(), 0, Default::default()),
+        Output::coin(recipient.address().into(), 20, BASE_ASSET_ID),
+    ];
+    let mut tb = ScriptTransactionBuilder::default().with_outputs(outputs.clone());
+
+    wallet.add_fee_resources(&mut tb).await?;
+
+    assert_eq!(tb.outputs()[..outputs.len()], outputs);
+    assert_eq!(
+        tb.outputs()[outputs.len()..],
+        [Output::change(wallet.address().into(), 0, BASE_ASSET_ID)]
+    );
+
+    let tx = tb.build(&provider).await?;
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+
+    assert_eq!(recipient.get_asset_balance(&BASE_ASSET_ID).await?, 30);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_spending_two_assets_is_funded_in_one_call() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let (coins, asset_ids) = setup_multiple_assets_coins(wallet.address(), 3, 4, 100);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet.set_provider(provider.clone());
+    let recipient = WalletUnlocked::new_random(None);
+
+    let (asset_a, asset_b) = (asset_ids[0], asset_ids[1]);
+    let outputs = vec![
+        Output::coin(recipient.address().into(), 250, asset_a),
+        Output::coin(recipient.address().into(), 120, asset_b),
+    ];
+    let mut tb = ScriptTransactionBuilder::default().with_outputs(outputs);
+
+    // `asset_a` is required twice, e.g. by two legs of a swap
+    wallet
+        .fund_for_amounts(&mut tb, &[(asset_a, 200), (asset_b, 120), (asset_a, 50)])
+        .await?;
+
+    for asset_id in [asset_a, asset_b, BASE_ASSET_ID] {
+        let change_outputs = tb
+            .outputs()
+            .iter()
+            .filter(
+                |output| matches!(output, Output::Change { asset_id: id, .. } if *id == asset_id),
+            )
+            .count();
+        assert_eq!(change_outputs, 1);
+    }
+
+    let tx = tb.build(&provider).await?;
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+
+    assert_eq!(recipient.get_asset_balance(&asset_a).await?, 250);
+    assert_eq!(recipient.get_asset_balance(&asset_b).await?, 120);
+    assert_eq!(wallet.get_asset_balance(&asset_a).await?, 150);
+    assert_eq!(wallet.get_asset_balance(&asset_b).await?, 280);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_template_is_instantiated_with_different_amounts() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let (coins, asset_ids) = setup_multiple_assets_coins(wallet.address(), 2, 4, 100);
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    wallet.set_provider(provider.clone());
+    let recipient = WalletUnlocked::new_random(None);
+
+    let asset_id = asset_ids[0];
+    let template = TransactionTemplate::new()
+        .with_transfer(
+            recipient.address(),
+            asset_id,
+            TemplateAmount::placeholder("amount"),
+        )
+        .with_transfer(recipient.address(), BASE_ASSET_ID, 5);
+
+    for amount in [30, 120] {
+        let tx = template
+            .instantiate(&wallet, &[("amount", amount)])
+            .await?
+            .build(&provider)
+            .await?;
+        provider
+            .send_transaction_and_await_commit(tx)
+            .await?
+            .check(None)?;
+    }
+
+    assert_eq!(recipient.get_asset_balance(&asset_id).await?, 150);
+    assert_eq!(recipient.get_asset_balance(&BASE_ASSET_ID).await?, 10);
+    assert_eq!(wallet.get_asset_balance(&asset_id).await?, 250);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rotate_provider_rejects_a_different_chain() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+
+    let provider = setup_test_provider(vec![], vec![], None, None).await?;
+    let backup_provider = setup_test_provider(vec![], vec![], None, None).await?;
+    let other_chain_provider = {
+        let chain_config = ChainConfig {
+            consensus_parameters: ConsensusParameters {
+                chain_id: ChainId::new(42),
+                ..Default::default()
+            },
+            ..ChainConfig::local_testnet()
+        };
+
+        setup_test_provider(vec![], vec![], None, Some(chain_config)).await?
+    };
+
+    wallet.rotate_provider(provider)?;
+    wallet.rotate_provider(backup_provider.clone())?;
+
+    let err = wallet
+        .rotate_provider(other_chain_provider.clone())
+        .expect_err("should fail because the chain ids differ");
+    assert!(err.to_string().contains("cannot rotate"));
+    assert_eq!(wallet.try_provider()?.url(), backup_provider.url());
+
+    wallet.set_provider(other_chain_provider);
+    assert_eq!(wallet.try_provider()?.chain_id(), ChainId::new(42));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn await_message_spendable_returns_relayed_message() -> Result<()> {
+    let mut wallet = WalletUnlocked::new_random(None);
+    let nonce = Nonce::from([1; 32]);
+    let messages = vec![setup_single_message(
+        &Bech32Address::default(),
+        wallet.address(),
+        DEFAULT_COIN_AMOUNT,
+        nonce,
+        vec![],
+    )];
+
+    let provider = setup_test_provider(vec![], messages, None, None).await?;
+    wallet.set_provider(provider);
+
+    let message = wallet
+        .await_message_spendable(&nonce, std::time::Duration::from_secs(5))
+        .await?;
+    assert_eq!(message.amount, DEFAULT_COIN_AMOUNT);
+
+    let err = wallet
+        .await_message_spendable(&Nonce::from([2; 32]), std::time::Duration::from_secs(1))
+        .await
+        .expect_err("should time out because the message doesn't exist");
+    assert!(err.to_string().contains("did not become spendable"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_transfer_with_multiple_signatures() -> Result<()> {
     let wallet_config = base_asset_wallet_config(5);
@@ -455,3 +1159,35 @@ async fn test_transfer_with_multiple_signatures() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn locked_wallet_builds_a_transfer_signed_elsewhere() -> Result<()> {
+    let wallet = launch_provider_and_get_wallet().await?;
+    let provider = wallet.try_provider()?.clone();
+    let locked = wallet.clone().lock();
+    let receiver = WalletUnlocked::new_random(None);
+
+    let tb = ScriptTransactionBuilder::prepare_transfer(
+        vec![],
+        vec![Output::coin(receiver.address().into(), 100, BASE_ASSET_ID)],
+        TxPolicies::default(),
+    );
+    let unsigned = locked.build_unsigned(tb, 100).await?;
+    assert!(unsigned.transaction().witnesses().is_empty());
+
+    let received = UnsignedTransaction::<ScriptTransaction>::from_bytes(&unsigned.to_bytes())?;
+    let tx = received.sign(&wallet).await?;
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+
+    assert_eq!(
+        provider
+            .get_asset_balance(receiver.address(), BASE_ASSET_ID)
+            .await?,
+        100
+    );
+
+    Ok(())
+}
@@ -196,7 +196,7 @@ mod tests {
         let mut expected_code = code.clone();
         *expected_code.last_mut().unwrap() = value as u8;
 
-        assert_eq!(*predicate.code(), expected_code);
+        assert_eq!(predicate.code(), expected_code);
 
         let expected_address = Bech32Address::from_str(
             "fuel14z2xsxcp47z9zfhj9atrmd66ujvwy8ujgn4j0xsh95fjh2px4mcq4f7k3w",